@@ -376,6 +376,7 @@ fn bench_search_messages(c: &mut Criterion) {
                         black_box(q.to_string()),
                         black_box(serde_json::json!({})),
                         None,
+                        None,
                     )
                     .await
                 })
@@ -438,6 +439,7 @@ fn bench_get_project_stats_summary(c: &mut Criterion) {
                             black_box(path_str.clone()),
                             black_box(None),
                             black_box(None),
+                            black_box(None),
                         )
                         .await
                     })
@@ -506,6 +508,8 @@ fn bench_get_global_stats_summary(c: &mut Criterion) {
                     rt.block_on(async {
                         claude_code_history_viewer_lib::commands::stats::get_global_stats_summary(
                             black_box(path_str.clone()),
+                            None,
+                            None,
                         )
                         .await
                     })
@@ -567,6 +571,7 @@ fn bench_get_project_token_stats(c: &mut Criterion) {
                             black_box(None),
                             black_box(None),
                             black_box(None),
+                            black_box(None),
                         )
                         .await
                     })
@@ -601,6 +606,7 @@ fn bench_get_recent_edits(c: &mut Criterion) {
                             black_box(path_str.clone()),
                             black_box(None),
                             black_box(None),
+                            black_box(None),
                         )
                         .await
                     })
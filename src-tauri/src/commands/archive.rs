@@ -0,0 +1,946 @@
+//! Session archive
+//!
+//! Old sessions are moved out of `~/.claude/projects` into
+//! `~/.claude-history-viewer/archive/projects`, which mirrors the projects
+//! layout with every transcript gzipped to `<name>.jsonl.gz`. Other data
+//! roots are archived under `archive/roots/<root>/projects`. Archived
+//! sessions are read-only: they can be loaded and searched in place and are
+//! only written again when restored.
+
+use super::bundle::session_files;
+use super::claude_dir::claude_dir;
+use super::fs_utils::{atomic_rename, sha256_hex};
+use super::metadata::{modify_user_metadata, read_user_metadata, MetadataState};
+use super::roots::same_folder;
+use super::session::SessionScanCache;
+use crate::models::{
+    ArchiveFailure, ArchiveOptions, ArchiveReason, ArchiveReport, ArchiveRestoreResult,
+    ArchivedSessionEntry, ClaudeProject, RestoredSession, UserMetadata,
+};
+use crate::utils::{
+    detect_git_worktree_info, estimate_message_count_from_size, extract_project_name,
+    resolve_project_path,
+};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use memmap2::Mmap;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::State;
+use walkdir::WalkDir;
+
+const SESSION_SUFFIX: &str = ".jsonl";
+const ARCHIVED_SUFFIX: &str = ".jsonl.gz";
+const SESSIONS_INDEX: &str = "sessions-index.json";
+
+/// Sessions touched this recently may still be in use and are never archived
+const ACTIVE_SESSION_GRACE: Duration = Duration::from_secs(60 * 60);
+
+/// Root of the session archive (`~/.claude-history-viewer/archive`)
+pub(crate) fn archive_root() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home.join(".claude-history-viewer").join("archive"))
+}
+
+/// Archive folder for the projects of one Claude folder
+///
+/// The configured Claude folder keeps `archive/projects`; every other root
+/// gets its own folder, so same-named projects from two roots never collide.
+pub(crate) fn archive_projects_dir(claude_folder: &Path) -> Result<PathBuf, String> {
+    let archive = archive_root()?;
+    if claude_dir().is_ok_and(|default| same_folder(&default, claude_folder)) {
+        return Ok(archive.join("projects"));
+    }
+    Ok(archive
+        .join("roots")
+        .join(root_key(claude_folder))
+        .join("projects"))
+}
+
+/// Readable, collision-free folder name for a Claude folder
+fn root_key(claude_folder: &Path) -> String {
+    let path = claude_folder
+        .canonicalize()
+        .unwrap_or_else(|_| claude_folder.to_path_buf());
+    let path = path.to_string_lossy();
+    let readable: Vec<char> = path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let tail: String = readable[readable.len().saturating_sub(40)..]
+        .iter()
+        .collect();
+    format!(
+        "{}-{}",
+        tail.trim_matches('-'),
+        &sha256_hex(path.as_bytes())[..12]
+    )
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or("")
+}
+
+/// Whether `path` is a compressed transcript in the archive
+pub(crate) fn is_archived_session(path: &Path) -> bool {
+    file_name(path).ends_with(ARCHIVED_SUFFIX)
+}
+
+/// Whether `path` is a transcript, live (`.jsonl`) or archived (`.jsonl.gz`)
+pub(crate) fn is_session_file(path: &Path) -> bool {
+    let name = file_name(path);
+    name.ends_with(SESSION_SUFFIX) || name.ends_with(ARCHIVED_SUFFIX)
+}
+
+/// File name without its `.jsonl` or `.jsonl.gz` suffix
+pub(crate) fn session_stem(path: &Path) -> Option<String> {
+    let name = file_name(path);
+    name.strip_suffix(ARCHIVED_SUFFIX)
+        .or_else(|| name.strip_suffix(SESSION_SUFFIX))
+        .filter(|stem| !stem.is_empty())
+        .map(str::to_string)
+}
+
+/// Contents of a session file: mapped when live, inflated when archived
+pub(crate) enum SessionBytes {
+    Mapped(Mmap),
+    Inflated(Vec<u8>),
+}
+
+impl Deref for SessionBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Inflated(bytes) => bytes,
+        }
+    }
+}
+
+/// Read a whole session file, decompressing archived sessions in memory
+#[allow(unsafe_code)] // Required for mmap performance optimization
+pub(crate) fn read_session_bytes(path: &Path) -> io::Result<SessionBytes> {
+    let file = fs::File::open(path)?;
+    if is_archived_session(path) {
+        let mut bytes = Vec::new();
+        GzDecoder::new(file).read_to_end(&mut bytes)?;
+        return Ok(SessionBytes::Inflated(bytes));
+    }
+
+    // SAFETY: We're only reading the file. No concurrent modifications expected
+    // as session files are append-only by Claude.
+    let mmap = unsafe { Mmap::map(&file) }?;
+    Ok(SessionBytes::Mapped(mmap))
+}
+
+/// Buffered line reader over a session file, decompressing archived sessions
+pub(crate) fn open_session_reader(path: &Path) -> io::Result<Box<dyn BufRead + Send>> {
+    let file = fs::File::open(path)?;
+    if is_archived_session(path) {
+        Ok(Box::new(BufReader::with_capacity(
+            64 * 1024,
+            GzDecoder::new(file),
+        )))
+    } else {
+        Ok(Box::new(BufReader::with_capacity(64 * 1024, file)))
+    }
+}
+
+/// Archive folder holding sessions moved out of a live project folder
+pub(crate) fn archived_project_dir(project_path: &Path) -> Option<PathBuf> {
+    let projects_path = project_path.parent()?;
+    if project_path.starts_with(archive_root().ok()?) || file_name(projects_path) != "projects" {
+        return None;
+    }
+    let archive_projects = archive_projects_dir(projects_path.parent()?).ok()?;
+    let dir = archive_projects.join(project_path.file_name()?);
+    dir.is_dir().then_some(dir)
+}
+
+/// Uncompressed size from the gzip trailer (exact below 4 GiB)
+fn inflated_size(path: &Path) -> Option<u64> {
+    let mut file = fs::File::open(path).ok()?;
+    file.seek(SeekFrom::End(-4)).ok()?;
+    let mut trailer = [0u8; 4];
+    file.read_exact(&mut trailer).ok()?;
+    Some(u64::from(u32::from_le_bytes(trailer)))
+}
+
+fn to_rfc3339(time: SystemTime) -> String {
+    let dt: DateTime<Utc> = time.into();
+    dt.to_rfc3339()
+}
+
+/// Discards output, keeping only its length
+#[derive(Default)]
+struct ByteCounter(u64);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn compress_into<W: Write>(source: &Path, out: W) -> io::Result<W> {
+    let mut encoder = GzEncoder::new(out, Compression::default());
+    io::copy(&mut fs::File::open(source)?, &mut encoder)?;
+    encoder.finish()
+}
+
+/// Write `source` to `dest` through a temp file, keeping the source's mtime
+fn write_via_temp(
+    source: &Path,
+    dest: &Path,
+    copy: impl FnOnce(fs::File) -> io::Result<fs::File>,
+) -> Result<u64, String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    let temp = dest.with_file_name(format!("{}.tmp", file_name(dest)));
+    let file = fs::File::create(&temp).and_then(copy).and_then(|file| {
+        if let Ok(modified) = fs::metadata(source).and_then(|m| m.modified()) {
+            file.set_modified(modified)?;
+        }
+        file.sync_all()?;
+        Ok(file)
+    });
+    let size = match file {
+        Ok(file) => file.metadata().map_or(0, |m| m.len()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            return Err(format!("Failed to write {}: {e}", dest.display()));
+        }
+    };
+    atomic_rename(&temp, dest)?;
+    Ok(size)
+}
+
+/// Remove the per-session subagent folder once it is empty
+fn remove_empty_session_dir(session_path: &Path) {
+    if let (Some(parent), Some(stem)) = (session_path.parent(), session_stem(session_path)) {
+        let dir = parent.join(stem);
+        for entry in WalkDir::new(&dir)
+            .contents_first(true)
+            .into_iter()
+            .flatten()
+        {
+            if entry.file_type().is_dir() {
+                let _ = fs::remove_dir(entry.path());
+            }
+        }
+    }
+}
+
+/// Delete an archived session and its archived subagent transcripts
+pub(crate) fn remove_archived_session(archive_path: &Path) -> Result<(), String> {
+    for (path, _) in session_files(archive_path)? {
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {e}", path.display()))?;
+    }
+    remove_empty_session_dir(archive_path);
+    Ok(())
+}
+
+/// A session selected for archiving
+struct ArchiveCandidate {
+    project_dir: String,
+    session_path: PathBuf,
+    files: Vec<(PathBuf, String)>,
+    original_bytes: u64,
+    last_modified: SystemTime,
+    reason: ArchiveReason,
+}
+
+/// Pick the top-level sessions under `projects_path` that match `options`
+fn plan_archive(
+    projects_path: &Path,
+    metadata: &UserMetadata,
+    options: &ArchiveOptions,
+    now: SystemTime,
+) -> Vec<ArchiveCandidate> {
+    let max_age = options
+        .older_than_days
+        .map(|days| Duration::from_secs(u64::from(days) * 24 * 60 * 60));
+    let mut candidates = Vec::new();
+
+    for project in WalkDir::new(projects_path)
+        .min_depth(1)
+        .max_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_dir())
+    {
+        let project_dir = project.file_name().to_string_lossy().to_string();
+        let hidden = options.include_hidden_projects
+            && metadata.is_project_hidden(&project.path().to_string_lossy());
+
+        for entry in WalkDir::new(project.path())
+            .min_depth(1)
+            .max_depth(1)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            let path = entry.path();
+            // Legacy `agent-*` transcripts are archived with their parent session
+            if !entry.file_type().is_file()
+                || !file_name(path).ends_with(SESSION_SUFFIX)
+                || file_name(path).starts_with("agent-")
+            {
+                continue;
+            }
+            let Ok(files) = session_files(path) else {
+                continue;
+            };
+
+            let mut original_bytes = 0;
+            let mut last_modified = SystemTime::UNIX_EPOCH;
+            for (file, _) in &files {
+                if let Ok(meta) = fs::metadata(file) {
+                    original_bytes += meta.len();
+                    if let Ok(modified) = meta.modified() {
+                        last_modified = last_modified.max(modified);
+                    }
+                }
+            }
+
+            let age = now.duration_since(last_modified).unwrap_or_default();
+            if age < ACTIVE_SESSION_GRACE {
+                continue;
+            }
+            let reason = if hidden {
+                ArchiveReason::HiddenProject
+            } else if max_age.is_some_and(|max| age >= max) {
+                ArchiveReason::Age
+            } else {
+                continue;
+            };
+
+            candidates.push(ArchiveCandidate {
+                project_dir: project_dir.clone(),
+                session_path: path.to_path_buf(),
+                files,
+                original_bytes,
+                last_modified,
+                reason,
+            });
+        }
+    }
+    candidates
+}
+
+/// Compress one session into the archive and remove the originals
+fn archive_session(
+    candidate: &ArchiveCandidate,
+    archive_projects: &Path,
+    dry_run: bool,
+) -> Result<u64, String> {
+    let target_dir = archive_projects.join(&candidate.project_dir);
+    let mut compressed_bytes = 0;
+
+    for (path, relative) in &candidate.files {
+        compressed_bytes += if dry_run {
+            compress_into(path, ByteCounter::default())
+                .map_err(|e| format!("Failed to compress {}: {e}", path.display()))?
+                .0
+        } else {
+            write_via_temp(path, &target_dir.join(format!("{relative}.gz")), |file| {
+                compress_into(path, file)
+            })?
+        };
+    }
+    if dry_run {
+        return Ok(compressed_bytes);
+    }
+
+    // Keep the original project path resolvable even if the live folder goes away
+    if let Some(project_dir) = candidate.session_path.parent() {
+        let index = project_dir.join(SESSIONS_INDEX);
+        if index.is_file() {
+            let _ = fs::copy(&index, target_dir.join(SESSIONS_INDEX));
+        }
+    }
+
+    // A file that cannot be removed stays live only, so it is never counted twice
+    let archived = |relative: &str| target_dir.join(format!("{relative}.gz"));
+    for (index, (path, relative)) in candidate.files.iter().enumerate() {
+        if let Err(e) = fs::remove_file(path) {
+            let error = format!("Failed to remove {}: {e}", path.display());
+            if index == 0 {
+                // The transcript itself is still live: drop the whole archived copy
+                for (_, relative) in &candidate.files {
+                    let _ = fs::remove_file(archived(relative));
+                }
+                remove_empty_session_dir(&archived(relative));
+                return Err(error);
+            }
+            log::warn!("{error}");
+            let _ = fs::remove_file(archived(relative));
+        }
+    }
+    remove_empty_session_dir(&candidate.session_path);
+    Ok(compressed_bytes)
+}
+
+/// Archive the sessions under `projects_path` that match `options`
+///
+/// Sessions that fail are left live and listed in `failed`.
+pub(crate) fn archive_sessions_in(
+    projects_path: &Path,
+    archive_projects: &Path,
+    metadata: &UserMetadata,
+    options: &ArchiveOptions,
+    now: SystemTime,
+) -> ArchiveReport {
+    let mut report = ArchiveReport {
+        dry_run: options.dry_run,
+        ..ArchiveReport::default()
+    };
+
+    for candidate in plan_archive(projects_path, metadata, options, now) {
+        let compressed_bytes = match archive_session(&candidate, archive_projects, options.dry_run)
+        {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                report.failed.push(ArchiveFailure {
+                    session_path: candidate.session_path.to_string_lossy().to_string(),
+                    error,
+                });
+                continue;
+            }
+        };
+        let archive_path = archive_projects
+            .join(&candidate.project_dir)
+            .join(format!("{}.gz", file_name(&candidate.session_path)));
+
+        report.original_bytes += candidate.original_bytes;
+        report.compressed_bytes += compressed_bytes;
+        report.sessions.push(ArchivedSessionEntry {
+            project_name: extract_project_name(&candidate.project_dir),
+            session_id: session_stem(&candidate.session_path).unwrap_or_default(),
+            source_path: candidate.session_path.to_string_lossy().to_string(),
+            archive_path: archive_path.to_string_lossy().to_string(),
+            file_count: candidate.files.len(),
+            original_bytes: candidate.original_bytes,
+            compressed_bytes,
+            last_modified: to_rfc3339(candidate.last_modified),
+            reason: candidate.reason,
+        });
+    }
+
+    report.bytes_reclaimed = report
+        .original_bytes
+        .saturating_sub(report.compressed_bytes);
+    report
+}
+
+/// Move archived sessions back into `projects_path`
+///
+/// A session is left in the archive when any of its files already exists
+/// in the live folder.
+pub(crate) fn restore_sessions(
+    archive_projects: &Path,
+    projects_path: &Path,
+    archive_paths: &[PathBuf],
+) -> Result<ArchiveRestoreResult, String> {
+    let canonical_root = archive_projects
+        .canonicalize()
+        .map_err(|e| format!("Archive folder not found: {e}"))?;
+    let mut result = ArchiveRestoreResult::default();
+
+    for archive_path in archive_paths {
+        let canonical = archive_path
+            .canonicalize()
+            .map_err(|e| format!("Archived session not found: {e}"))?;
+        let project_dir = canonical
+            .parent()
+            .filter(|parent| parent.parent() == Some(canonical_root.as_path()))
+            .ok_or_else(|| {
+                format!(
+                    "Not an archived session: {}",
+                    archive_path.to_string_lossy()
+                )
+            })?;
+        if !is_archived_session(&canonical) {
+            return Err(format!(
+                "Not an archived session: {}",
+                archive_path.to_string_lossy()
+            ));
+        }
+
+        let live_dir = projects_path.join(project_dir.file_name().unwrap_or_default());
+        let targets: Vec<(PathBuf, PathBuf)> = session_files(&canonical)?
+            .into_iter()
+            .map(|(path, relative)| {
+                let restored = relative.strip_suffix(".gz").unwrap_or(&relative);
+                (path, live_dir.join(restored))
+            })
+            .collect();
+
+        let entry = RestoredSession {
+            archive_path: archive_path.to_string_lossy().to_string(),
+            file_path: targets
+                .first()
+                .map(|(_, target)| target.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+        if targets.iter().any(|(_, target)| target.exists()) {
+            result.skipped.push(entry);
+            continue;
+        }
+
+        for (path, target) in &targets {
+            write_via_temp(path, target, |mut file| {
+                io::copy(&mut GzDecoder::new(fs::File::open(path)?), &mut file)?;
+                Ok(file)
+            })?;
+        }
+        for (path, _) in &targets {
+            fs::remove_file(path)
+                .map_err(|e| format!("Failed to remove {}: {e}", path.display()))?;
+        }
+        remove_empty_session_dir(&canonical);
+
+        // Drop the archive folder once its last session is restored
+        let has_sessions = WalkDir::new(project_dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .any(|e| is_archived_session(e.path()));
+        if !has_sessions {
+            let _ = fs::remove_dir_all(project_dir);
+        }
+        result.restored.push(entry);
+    }
+    Ok(result)
+}
+
+/// Fold archived sessions into `projects` so archived projects still appear
+///
+/// Archived sessions count towards their live project; projects whose live
+/// folder is gone are listed under their original projects path.
+pub(crate) fn merge_archived_projects(
+    projects: &mut Vec<ClaudeProject>,
+    projects_path: &Path,
+    archive_projects: &Path,
+) {
+    for archived in WalkDir::new(archive_projects)
+        .min_depth(1)
+        .max_depth(1)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_dir())
+    {
+        let raw_name = archived.file_name().to_string_lossy().to_string();
+        let live_path = projects_path.join(&raw_name);
+        // Archived sessions opened in the viewer are cached with their live project
+        let cache = SessionScanCache::load(&live_path);
+
+        let mut session_count = 0;
+        let mut message_count = 0;
+        let mut is_estimate = false;
+        let mut cwd_counts: HashMap<String, usize> = HashMap::new();
+        let mut last_modified: Option<SystemTime> = None;
+        for entry in WalkDir::new(archived.path())
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| is_archived_session(e.path()))
+        {
+            session_count += 1;
+            if let Some(scan) = cache.get(&entry.path().to_path_buf()) {
+                message_count += scan.message_count;
+                for (cwd, count) in scan.cwd_counts {
+                    *cwd_counts.entry(cwd).or_insert(0) += count;
+                }
+            } else {
+                is_estimate = true;
+                message_count +=
+                    estimate_message_count_from_size(inflated_size(entry.path()).unwrap_or(0));
+            }
+            if let Some(modified) = entry.metadata().ok().and_then(|m| m.modified().ok()) {
+                last_modified = last_modified.max(Some(modified));
+            }
+        }
+        if session_count == 0 {
+            continue;
+        }
+
+        let live_path = live_path.to_string_lossy().to_string();
+        let last_modified = last_modified.map(to_rfc3339).unwrap_or_default();
+
+        if let Some(project) = projects.iter_mut().find(|p| p.path == live_path) {
+            project.session_count += session_count;
+            project.message_count += message_count;
+            project.archived_session_count += session_count;
+            project.is_estimate |= is_estimate;
+            if last_modified > project.last_modified {
+                project.last_modified = last_modified;
+            }
+            continue;
+        }
+
+        let storage_path = if archived.path().join(SESSIONS_INDEX).is_file() {
+            archived.path().to_string_lossy().to_string()
+        } else {
+            live_path.clone()
+        };
+        let (actual_path, path_resolution) = resolve_project_path(&storage_path, &cwd_counts);
+        let git_info = detect_git_worktree_info(&actual_path);
+        projects.push(ClaudeProject {
+            name: extract_project_name(&raw_name),
+            path: live_path,
+            actual_path,
+            session_count,
+            message_count,
+            last_modified,
+            git_info,
+            archived_session_count: session_count,
+            is_estimate,
+            root: None,
+            path_resolution: Some(path_resolution),
+        });
+    }
+}
+
+/// Archive old sessions and sessions of hidden projects, or report what would be archived
+#[tauri::command]
+pub async fn archive_sessions(
+    claude_path: String,
+    options: ArchiveOptions,
+    state: State<'_, MetadataState>,
+) -> Result<ArchiveReport, String> {
+    let cached = state
+        .metadata
+        .lock()
+        .map_err(|e| format!("Failed to lock metadata: {e}"))?
+        .clone();
+
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let metadata = match cached {
+            Some(metadata) => metadata,
+            None => read_user_metadata()?,
+        };
+        let claude_path = PathBuf::from(&claude_path);
+        Ok::<_, String>(archive_sessions_in(
+            &claude_path.join("projects"),
+            &archive_projects_dir(&claude_path)?,
+            &metadata,
+            &options,
+            SystemTime::now(),
+        ))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))??;
+
+    // Session metadata is keyed by file path, so it follows the session
+    if !report.dry_run && !report.sessions.is_empty() {
+        modify_user_metadata(&state, |metadata| {
+            for session in &report.sessions {
+                if let Some(moved) = metadata.sessions.remove(&session.source_path) {
+                    metadata
+                        .sessions
+                        .insert(session.archive_path.clone(), moved);
+                }
+            }
+        })
+        .await?;
+    }
+
+    Ok(report)
+}
+
+/// Restore archived sessions into the Claude folder they were archived from
+#[tauri::command]
+pub async fn restore_archived_sessions(
+    claude_path: String,
+    archive_paths: Vec<String>,
+    state: State<'_, MetadataState>,
+) -> Result<ArchiveRestoreResult, String> {
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let paths: Vec<PathBuf> = archive_paths.iter().map(PathBuf::from).collect();
+        restore_sessions(
+            &archive_projects_dir(Path::new(&claude_path))?,
+            &PathBuf::from(&claude_path).join("projects"),
+            &paths,
+        )
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))??;
+
+    if !result.restored.is_empty() {
+        modify_user_metadata(&state, |metadata| {
+            for session in &result.restored {
+                if let Some(moved) = metadata.sessions.remove(&session.archive_path) {
+                    metadata.sessions.insert(session.file_path.clone(), moved);
+                }
+            }
+        })
+        .await?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectMetadata;
+    use tempfile::TempDir;
+
+    const OLD_SESSION: &str = "11111111-2222-3333-4444-555555555555";
+    const NEW_SESSION: &str = "66666666-7777-8888-9999-000000000000";
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn line(session_id: &str, text: &str) -> String {
+        format!(
+            r#"{{"uuid":"u-{text}","sessionId":"{session_id}","timestamp":"2025-01-01T00:00:00Z","type":"user","message":{{"role":"user","content":"{text}"}}}}"#
+        ) + "\n"
+    }
+
+    fn write_session(project_dir: &Path, session_id: &str, age: Duration) -> PathBuf {
+        let path = project_dir.join(format!("{session_id}.jsonl"));
+        fs::write(&path, line(session_id, "hello archive").repeat(50)).unwrap();
+        let modified = SystemTime::now() - age;
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        path
+    }
+
+    /// A project with one 40-day-old session (plus a subagent) and one fresh session
+    fn setup(temp: &TempDir) -> (PathBuf, PathBuf, PathBuf) {
+        let projects = temp.path().join(".claude").join("projects");
+        let archive = temp.path().join("archive").join("projects");
+        let project_dir = projects.join("-work-app");
+        fs::create_dir_all(project_dir.join(OLD_SESSION).join("subagents")).unwrap();
+        let subagent = project_dir
+            .join(OLD_SESSION)
+            .join("subagents")
+            .join("agent-a1.jsonl");
+        fs::write(&subagent, line(OLD_SESSION, "subagent work")).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&subagent)
+            .unwrap()
+            .set_modified(SystemTime::now() - DAY * 40)
+            .unwrap();
+        write_session(&project_dir, OLD_SESSION, DAY * 40);
+        write_session(&project_dir, NEW_SESSION, DAY);
+        (projects, archive, project_dir)
+    }
+
+    fn older_than(days: u32, dry_run: bool) -> ArchiveOptions {
+        ArchiveOptions {
+            older_than_days: Some(days),
+            include_hidden_projects: false,
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_moving() {
+        let temp = TempDir::new().unwrap();
+        let (projects, archive, project_dir) = setup(&temp);
+
+        let report = archive_sessions_in(
+            &projects,
+            &archive,
+            &UserMetadata::new(),
+            &older_than(30, true),
+            SystemTime::now(),
+        );
+
+        assert!(report.dry_run);
+        assert_eq!(report.sessions.len(), 1);
+        assert_eq!(report.sessions[0].session_id, OLD_SESSION);
+        assert_eq!(report.sessions[0].file_count, 2);
+        assert_eq!(report.sessions[0].reason, ArchiveReason::Age);
+        assert!(report.compressed_bytes < report.original_bytes);
+        assert_eq!(
+            report.bytes_reclaimed,
+            report.original_bytes - report.compressed_bytes
+        );
+        assert!(project_dir.join(format!("{OLD_SESSION}.jsonl")).exists());
+        assert!(!archive.exists());
+    }
+
+    #[test]
+    fn test_archive_is_readable_and_restorable() {
+        let temp = TempDir::new().unwrap();
+        let (projects, archive, project_dir) = setup(&temp);
+        let original = fs::read(project_dir.join(format!("{OLD_SESSION}.jsonl"))).unwrap();
+
+        let report = archive_sessions_in(
+            &projects,
+            &archive,
+            &UserMetadata::new(),
+            &older_than(30, false),
+            SystemTime::now(),
+        );
+        assert_eq!(report.sessions.len(), 1);
+
+        let archive_path = PathBuf::from(&report.sessions[0].archive_path);
+        assert!(is_archived_session(&archive_path));
+        assert!(archive
+            .join("-work-app")
+            .join(OLD_SESSION)
+            .join("subagents")
+            .join("agent-a1.jsonl.gz")
+            .exists());
+        assert!(!project_dir.join(format!("{OLD_SESSION}.jsonl")).exists());
+        assert!(!project_dir.join(OLD_SESSION).exists());
+        assert!(project_dir.join(format!("{NEW_SESSION}.jsonl")).exists());
+        assert_eq!(&*read_session_bytes(&archive_path).unwrap(), &original[..]);
+        assert_eq!(inflated_size(&archive_path), Some(original.len() as u64));
+
+        let result = restore_sessions(&archive, &projects, &[archive_path]).unwrap();
+        assert_eq!(result.restored.len(), 1);
+        assert!(result.skipped.is_empty());
+        let restored = project_dir.join(format!("{OLD_SESSION}.jsonl"));
+        assert_eq!(fs::read(&restored).unwrap(), original);
+        assert!(project_dir
+            .join(OLD_SESSION)
+            .join("subagents")
+            .join("agent-a1.jsonl")
+            .exists());
+        // The restored file keeps its age, and the emptied archive folder is removed
+        let age = SystemTime::now()
+            .duration_since(fs::metadata(&restored).unwrap().modified().unwrap())
+            .unwrap();
+        assert!(age > DAY * 39);
+        assert!(!archive.join("-work-app").exists());
+    }
+
+    #[test]
+    fn test_restore_skips_existing_and_rejects_outside_paths() {
+        let temp = TempDir::new().unwrap();
+        let (projects, archive, project_dir) = setup(&temp);
+        let report = archive_sessions_in(
+            &projects,
+            &archive,
+            &UserMetadata::new(),
+            &older_than(30, false),
+            SystemTime::now(),
+        );
+        let archive_path = PathBuf::from(&report.sessions[0].archive_path);
+
+        fs::write(project_dir.join(format!("{OLD_SESSION}.jsonl")), "newer").unwrap();
+        let result =
+            restore_sessions(&archive, &projects, std::slice::from_ref(&archive_path)).unwrap();
+        assert!(result.restored.is_empty());
+        assert_eq!(result.skipped.len(), 1);
+        assert!(archive_path.exists());
+
+        let outside = project_dir.join(format!("{NEW_SESSION}.jsonl"));
+        assert!(restore_sessions(&archive, &projects, &[outside]).is_err());
+    }
+
+    #[test]
+    fn test_hidden_projects_and_active_sessions() {
+        let temp = TempDir::new().unwrap();
+        let (projects, archive, project_dir) = setup(&temp);
+        write_session(&project_dir, "active", Duration::from_secs(60));
+        let mut metadata = UserMetadata::new();
+        metadata.projects.insert(
+            project_dir.to_string_lossy().to_string(),
+            ProjectMetadata {
+                hidden: Some(true),
+                ..ProjectMetadata::default()
+            },
+        );
+
+        let options = ArchiveOptions {
+            older_than_days: None,
+            include_hidden_projects: true,
+            dry_run: true,
+        };
+        let report =
+            archive_sessions_in(&projects, &archive, &metadata, &options, SystemTime::now());
+
+        let ids: Vec<&str> = report
+            .sessions
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect();
+        assert_eq!(ids, vec![OLD_SESSION, NEW_SESSION]);
+        assert!(report
+            .sessions
+            .iter()
+            .all(|s| s.reason == ArchiveReason::HiddenProject));
+    }
+
+    #[test]
+    fn test_archive_is_keyed_by_root() {
+        let temp = TempDir::new().unwrap();
+        let work = temp.path().join("work").join(".claude");
+        let home = temp.path().join("home").join(".claude");
+
+        let work_archive = archive_projects_dir(&work).unwrap();
+        let home_archive = archive_projects_dir(&home).unwrap();
+        assert_ne!(work_archive, home_archive);
+        assert!(work_archive.starts_with(archive_root().unwrap().join("roots")));
+        assert!(work_archive.ends_with("projects"));
+        assert_eq!(archive_projects_dir(&work).unwrap(), work_archive);
+    }
+
+    #[test]
+    fn test_merge_archived_projects() {
+        let temp = TempDir::new().unwrap();
+        let (projects, archive, project_dir) = setup(&temp);
+        fs::write(
+            project_dir.join(SESSIONS_INDEX),
+            r#"{"originalPath":"/work/app"}"#,
+        )
+        .unwrap();
+        archive_sessions_in(
+            &projects,
+            &archive,
+            &UserMetadata::new(),
+            &older_than(30, false),
+            SystemTime::now(),
+        );
+
+        let live = ClaudeProject {
+            name: "app".to_string(),
+            path: project_dir.to_string_lossy().to_string(),
+            actual_path: "/work/app".to_string(),
+            session_count: 1,
+            message_count: 50,
+            last_modified: "2025-01-01T00:00:00+00:00".to_string(),
+            git_info: None,
+            archived_session_count: 0,
+            is_estimate: false,
+            root: None,
+            path_resolution: None,
+        };
+        let mut merged = vec![live.clone()];
+        merge_archived_projects(&mut merged, &projects, &archive);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].session_count, 3);
+        assert_eq!(merged[0].archived_session_count, 2);
+        assert!(merged[0].message_count > live.message_count);
+
+        // Without a live folder the project is still listed from the archive
+        let mut archived_only = Vec::new();
+        merge_archived_projects(&mut archived_only, &projects, &archive);
+        assert_eq!(archived_only.len(), 1);
+        assert_eq!(archived_only[0].path, live.path);
+        assert_eq!(archived_only[0].actual_path, "/work/app");
+        assert_eq!(archived_only[0].archived_session_count, 2);
+    }
+}
@@ -0,0 +1,808 @@
+//! Session bundle (`.cchv`) export and import
+//!
+//! A bundle is a zip archive holding selected session files, their subagent
+//! transcripts, the matching `UserMetadata` session entries and a
+//! `manifest.json` with SHA-256 checksums. Imports verify every checksum
+//! before writing and never replace an existing session unless asked to.
+
+use super::archive::{
+    archive_projects_dir, is_archived_session, is_session_file, open_session_reader,
+    read_session_bytes, remove_archived_session, session_stem,
+};
+use super::claude_settings::is_safe_path;
+use super::fs_utils::{atomic_rename, sha256_hex};
+use super::metadata::{modify_user_metadata, read_user_metadata, MetadataState};
+use super::roots::ensure_known_claude_folder;
+use crate::models::{
+    BundleConflict, BundleConflictPolicy, BundleImportResult, BundleInspection, BundleManifest,
+    BundledFile, BundledSession, ImportedSession, SessionMetadata, BUNDLE_FORMAT_VERSION,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::State;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const MANIFEST_NAME: &str = "manifest.json";
+/// Largest manifest read from a bundle, so a crafted one cannot inflate without bound
+const MAX_MANIFEST_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Path relative to `base` with `/` separators, as stored in the manifest
+fn relative_path(path: &Path, base: &Path) -> Option<String> {
+    let relative = path.strip_prefix(base).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Some(parts.join("/"))
+}
+
+/// Manifest paths must stay inside the target folder
+fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains('\\')
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Session id recorded in the first lines of a transcript
+fn transcript_session_id(path: &Path) -> Option<String> {
+    open_session_reader(path)
+        .ok()?
+        .lines()
+        .take(20)
+        .map_while(Result::ok)
+        .find_map(|line| {
+            serde_json::from_str::<serde_json::Value>(&line)
+                .ok()?
+                .get("sessionId")?
+                .as_str()
+                .map(str::to_string)
+        })
+}
+
+/// The session file followed by its subagent transcripts, relative to the project folder
+///
+/// Subagents live either under `<project>/<session-id>/` or, in older
+/// layouts, as `<project>/agent-*.jsonl` pointing back at the session.
+/// Works for archived (`.jsonl.gz`) sessions as well.
+pub(crate) fn session_files(session_path: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+    let project_dir = session_path
+        .parent()
+        .ok_or_else(|| format!("Invalid session path: {}", session_path.display()))?;
+    let session_id = session_stem(session_path)
+        .ok_or_else(|| format!("Invalid session path: {}", session_path.display()))?;
+
+    let mut files = vec![(
+        session_path.to_path_buf(),
+        relative_path(session_path, project_dir).unwrap_or_default(),
+    )];
+
+    let mut subagents: Vec<PathBuf> = WalkDir::new(project_dir.join(&session_id))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file() && is_session_file(e.path()))
+        .map(|e| e.path().to_path_buf())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(project_dir) {
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("agent-")
+                && is_session_file(&path)
+                && transcript_session_id(&path).as_deref() == Some(session_id.as_str())
+            {
+                subagents.push(path);
+            }
+        }
+    }
+
+    subagents.sort();
+    for path in subagents {
+        if let Some(relative) = relative_path(&path, project_dir) {
+            files.push((path, relative));
+        }
+    }
+    Ok(files)
+}
+
+/// Write a bundle of `sessions` (session file path and its metadata) to `output`
+pub(crate) fn write_bundle(
+    sessions: &[(PathBuf, Option<SessionMetadata>)],
+    output: &Path,
+) -> Result<BundleManifest, String> {
+    let temp_path = output.with_extension("cchv.tmp");
+    let file = fs::File::create(&temp_path)
+        .map_err(|e| format!("Failed to create bundle {}: {e}", temp_path.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        sessions: Vec::with_capacity(sessions.len()),
+    };
+
+    for (session_path, metadata) in sessions {
+        let project_dir = session_path
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Invalid session path: {}", session_path.display()))?;
+        let session_id = session_stem(session_path)
+            .ok_or_else(|| format!("Invalid session path: {}", session_path.display()))?;
+
+        let mut bundled = BundledSession {
+            session_id,
+            project_dir: project_dir.clone(),
+            original_path: session_path.to_string_lossy().to_string(),
+            metadata: metadata.clone(),
+            files: Vec::new(),
+        };
+
+        for (path, relative) in session_files(session_path)? {
+            // Archived transcripts are bundled inflated, under their live name
+            let bytes = read_session_bytes(&path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+            let relative = relative
+                .strip_suffix(".gz")
+                .map(str::to_string)
+                .unwrap_or(relative);
+            zip.start_file(format!("projects/{project_dir}/{relative}"), options)
+                .map_err(|e| format!("Failed to add {relative} to bundle: {e}"))?;
+            zip.write_all(&bytes)
+                .map_err(|e| format!("Failed to write {relative} to bundle: {e}"))?;
+            bundled.files.push(BundledFile {
+                relative_path: relative,
+                size: bytes.len() as u64,
+                sha256: sha256_hex(&bytes),
+            });
+        }
+        manifest.sessions.push(bundled);
+    }
+
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {e}"))?;
+    zip.start_file(MANIFEST_NAME, options)
+        .map_err(|e| format!("Failed to add manifest: {e}"))?;
+    zip.write_all(manifest_json.as_bytes())
+        .map_err(|e| format!("Failed to write manifest: {e}"))?;
+    zip.finish()
+        .map_err(|e| format!("Failed to finish bundle: {e}"))?;
+
+    atomic_rename(&temp_path, output)?;
+    Ok(manifest)
+}
+
+fn open_bundle(bundle: &Path) -> Result<(ZipArchive<fs::File>, BundleManifest), String> {
+    let file = fs::File::open(bundle)
+        .map_err(|e| format!("Failed to open bundle {}: {e}", bundle.display()))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| format!("Not a valid session bundle: {e}"))?;
+
+    let mut manifest_json = String::new();
+    archive
+        .by_name(MANIFEST_NAME)
+        .map_err(|_| "Bundle has no manifest.json".to_string())?
+        .take(MAX_MANIFEST_BYTES + 1)
+        .read_to_string(&mut manifest_json)
+        .map_err(|e| format!("Failed to read manifest: {e}"))?;
+    if manifest_json.len() as u64 > MAX_MANIFEST_BYTES {
+        return Err("Bundle manifest is too large".to_string());
+    }
+    let manifest: BundleManifest =
+        serde_json::from_str(&manifest_json).map_err(|e| format!("Invalid manifest: {e}"))?;
+
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Bundle format version {} is newer than supported version {BUNDLE_FORMAT_VERSION}",
+            manifest.format_version
+        ));
+    }
+    for session in &manifest.sessions {
+        let main_file = format!("{}.jsonl", session.session_id);
+        if !is_safe_relative(&session.project_dir)
+            || session.project_dir.contains('/')
+            || session.files.first().map(|f| f.relative_path.as_str()) != Some(main_file.as_str())
+            || !session
+                .files
+                .iter()
+                .all(|f| is_safe_relative(&f.relative_path))
+        {
+            return Err(format!(
+                "Bundle manifest has an unsafe path for session {}",
+                session.session_id
+            ));
+        }
+    }
+    Ok((archive, manifest))
+}
+
+/// Session files already in `<claude>/projects` or in the archive, keyed by session id
+///
+/// Live sessions win when a session is in both.
+fn existing_sessions(claude_dir: &Path, archive_projects: &Path) -> HashMap<String, PathBuf> {
+    let top_level = |dir: PathBuf| {
+        WalkDir::new(dir)
+            .min_depth(2)
+            .max_depth(2)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file() && is_session_file(e.path()))
+            .filter_map(|e| Some((session_stem(e.path())?, e.path().to_path_buf())))
+    };
+    let mut existing: HashMap<String, PathBuf> =
+        top_level(archive_projects.to_path_buf()).collect();
+    existing.extend(top_level(claude_dir.join("projects")));
+    existing
+}
+
+fn find_conflicts(
+    manifest: &BundleManifest,
+    existing: &HashMap<String, PathBuf>,
+) -> Vec<BundleConflict> {
+    manifest
+        .sessions
+        .iter()
+        .filter_map(|session| {
+            existing
+                .get(&session.session_id)
+                .map(|path| BundleConflict {
+                    session_id: session.session_id.clone(),
+                    existing_path: path.to_string_lossy().to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Read a bundle's manifest and report which sessions already exist, live or archived
+pub(crate) fn inspect_bundle(
+    bundle: &Path,
+    claude_dir: &Path,
+    archive_projects: &Path,
+) -> Result<BundleInspection, String> {
+    let (_, manifest) = open_bundle(bundle)?;
+    let conflicts = find_conflicts(&manifest, &existing_sessions(claude_dir, archive_projects));
+    Ok(BundleInspection {
+        manifest,
+        conflicts,
+    })
+}
+
+/// Session metadata to restore: new file path, metadata, and whether the
+/// session replaced an existing one
+type ImportedMetadata = (String, SessionMetadata, bool);
+
+/// Import a bundle into `claude_dir`
+///
+/// Every checksum is verified before anything is written. Sessions in
+/// `archive_projects` conflict like live ones; overwriting one writes it live
+/// and drops the archived copy. Returns the import result and the metadata
+/// to store, keyed by the new session file path.
+pub(crate) fn import_bundle(
+    bundle: &Path,
+    claude_dir: &Path,
+    archive_projects: &Path,
+    policy: BundleConflictPolicy,
+) -> Result<(BundleImportResult, Vec<ImportedMetadata>), String> {
+    let (mut archive, manifest) = open_bundle(bundle)?;
+
+    // Pass 1: read and verify everything
+    let mut contents: Vec<Vec<Vec<u8>>> = Vec::with_capacity(manifest.sessions.len());
+    for session in &manifest.sessions {
+        let mut files = Vec::with_capacity(session.files.len());
+        for file in &session.files {
+            let name = format!("projects/{}/{}", session.project_dir, file.relative_path);
+            let mut bytes = Vec::new();
+            // Inflate no more than the manifest promises
+            archive
+                .by_name(&name)
+                .map_err(|_| format!("Bundle is missing {name}"))?
+                .take(file.size.saturating_add(1))
+                .read_to_end(&mut bytes)
+                .map_err(|e| format!("Failed to read {name}: {e}"))?;
+            if bytes.len() as u64 > file.size {
+                return Err(format!(
+                    "{name} is larger than the manifest says; the bundle is corrupt"
+                ));
+            }
+            if bytes.len() as u64 != file.size || sha256_hex(&bytes) != file.sha256 {
+                return Err(format!(
+                    "Checksum mismatch for {name}; the bundle is corrupt"
+                ));
+            }
+            files.push(bytes);
+        }
+        contents.push(files);
+    }
+
+    // Pass 2: write sessions that do not conflict (or may be overwritten)
+    let existing = existing_sessions(claude_dir, archive_projects);
+    let mut result = BundleImportResult::default();
+    let mut metadata = Vec::new();
+
+    for (session, files) in manifest.sessions.iter().zip(contents) {
+        let existing_path = existing.get(&session.session_id);
+        let archived = existing_path.filter(|path| is_archived_session(path));
+        // Archived sessions are replaced in their live project folder
+        let target_dir = match existing_path.and_then(|path| path.parent()) {
+            Some(dir) if archived.is_some() => claude_dir
+                .join("projects")
+                .join(dir.file_name().unwrap_or_default()),
+            Some(dir) => dir.to_path_buf(),
+            None => claude_dir.join("projects").join(&session.project_dir),
+        };
+        let targets: Vec<PathBuf> = session
+            .files
+            .iter()
+            .map(|file| target_dir.join(&file.relative_path))
+            .collect();
+
+        let conflict = existing_path
+            .cloned()
+            .or_else(|| targets.iter().find(|path| path.exists()).cloned());
+        if let (Some(path), BundleConflictPolicy::Skip) = (&conflict, policy) {
+            result.skipped.push(BundleConflict {
+                session_id: session.session_id.clone(),
+                existing_path: path.to_string_lossy().to_string(),
+            });
+            continue;
+        }
+
+        for (target, bytes) in targets.iter().zip(files) {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+            }
+            let temp_path = target.with_extension("jsonl.tmp");
+            fs::write(&temp_path, bytes)
+                .map_err(|e| format!("Failed to write {}: {e}", temp_path.display()))?;
+            atomic_rename(&temp_path, target)?;
+        }
+        if let Some(archived) = archived {
+            remove_archived_session(archived)?;
+        }
+
+        let file_path = targets[0].to_string_lossy().to_string();
+        let overwritten = conflict.is_some();
+        if let Some(session_metadata) = &session.metadata {
+            metadata.push((file_path.clone(), session_metadata.clone(), overwritten));
+        }
+        result.imported.push(ImportedSession {
+            session_id: session.session_id.clone(),
+            file_path,
+            overwritten,
+        });
+    }
+
+    Ok((result, metadata))
+}
+
+/// Export sessions, their subagent transcripts and metadata as a `.cchv` bundle
+#[tauri::command]
+pub async fn export_session_bundle(
+    session_paths: Vec<String>,
+    output_path: String,
+    state: State<'_, MetadataState>,
+) -> Result<BundleManifest, String> {
+    let cached = state
+        .metadata
+        .lock()
+        .map_err(|e| format!("Failed to lock metadata: {e}"))?
+        .clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = PathBuf::from(&output_path);
+        is_safe_path(&output)?;
+
+        let user_metadata = match cached {
+            Some(metadata) => metadata,
+            None => read_user_metadata()?,
+        };
+        // Session metadata is keyed by file path
+        let sessions: Vec<(PathBuf, Option<SessionMetadata>)> = session_paths
+            .iter()
+            .map(|path| {
+                (
+                    PathBuf::from(path),
+                    user_metadata.get_session(path).cloned(),
+                )
+            })
+            .collect();
+
+        write_bundle(&sessions, &output)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Show a bundle's sessions and which of them already exist, before importing
+#[tauri::command]
+pub async fn inspect_session_bundle(
+    bundle_path: String,
+    claude_path: String,
+) -> Result<BundleInspection, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let bundle = PathBuf::from(&bundle_path);
+        is_safe_path(&bundle)?;
+        ensure_known_claude_folder(&claude_path)?;
+        inspect_bundle(
+            &bundle,
+            Path::new(&claude_path),
+            &archive_projects_dir(Path::new(&claude_path))?,
+        )
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Import a bundle into a Claude folder and restore its session metadata
+#[tauri::command]
+pub async fn import_session_bundle(
+    bundle_path: String,
+    claude_path: String,
+    policy: Option<BundleConflictPolicy>,
+    state: State<'_, MetadataState>,
+) -> Result<BundleImportResult, String> {
+    let (result, metadata) = tauri::async_runtime::spawn_blocking(move || {
+        let bundle = PathBuf::from(&bundle_path);
+        is_safe_path(&bundle)?;
+        ensure_known_claude_folder(&claude_path)?;
+        import_bundle(
+            &bundle,
+            Path::new(&claude_path),
+            &archive_projects_dir(Path::new(&claude_path))?,
+            policy.unwrap_or_default(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))??;
+
+    if !metadata.is_empty() {
+        modify_user_metadata(&state, |user_metadata| {
+            for (file_path, session_metadata, overwritten) in metadata {
+                if overwritten {
+                    user_metadata.sessions.insert(file_path, session_metadata);
+                } else {
+                    user_metadata
+                        .sessions
+                        .entry(file_path)
+                        .or_insert(session_metadata);
+                }
+            }
+        })
+        .await?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SESSION_ID: &str = "11111111-2222-3333-4444-555555555555";
+
+    fn create_source(claude_dir: &Path) -> PathBuf {
+        let project_dir = claude_dir.join("projects").join("-work-app");
+        fs::create_dir_all(project_dir.join(SESSION_ID).join("subagents")).unwrap();
+        let session_path = project_dir.join(format!("{SESSION_ID}.jsonl"));
+        fs::write(
+            &session_path,
+            format!(r#"{{"sessionId":"{SESSION_ID}","type":"user"}}"#),
+        )
+        .unwrap();
+        fs::write(
+            project_dir
+                .join(SESSION_ID)
+                .join("subagents")
+                .join("agent-a1.jsonl"),
+            "{}",
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("agent-legacy.jsonl"),
+            format!(r#"{{"sessionId":"{SESSION_ID}","isSidechain":true}}"#),
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("agent-other.jsonl"),
+            r#"{"sessionId":"another-session"}"#,
+        )
+        .unwrap();
+        session_path
+    }
+
+    fn no_archive(temp: &TempDir) -> PathBuf {
+        temp.path().join("archive").join("projects")
+    }
+
+    fn bundle_from_source(temp: &TempDir) -> PathBuf {
+        let session_path = create_source(&temp.path().join("source"));
+        let metadata = SessionMetadata {
+            custom_name: Some("Login fix".to_string()),
+            tags: vec!["auth".to_string()],
+            ..Default::default()
+        };
+        let bundle = temp.path().join("sessions.cchv");
+        write_bundle(&[(session_path, Some(metadata))], &bundle).unwrap();
+        bundle
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let bundle = bundle_from_source(&temp);
+        let target = temp.path().join("target");
+
+        let inspection = inspect_bundle(&bundle, &target, &no_archive(&temp)).unwrap();
+        let session = &inspection.manifest.sessions[0];
+        let files: Vec<&str> = session
+            .files
+            .iter()
+            .map(|f| f.relative_path.as_str())
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                format!("{SESSION_ID}.jsonl").as_str(),
+                format!("{SESSION_ID}/subagents/agent-a1.jsonl").as_str(),
+                "agent-legacy.jsonl",
+            ]
+        );
+        assert!(inspection.conflicts.is_empty());
+
+        let (result, metadata) = import_bundle(
+            &bundle,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Skip,
+        )
+        .unwrap();
+        let imported_path = target
+            .join("projects")
+            .join("-work-app")
+            .join(format!("{SESSION_ID}.jsonl"));
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(
+            result.imported[0].file_path,
+            imported_path.to_string_lossy()
+        );
+        assert!(target
+            .join("projects/-work-app")
+            .join(SESSION_ID)
+            .join("subagents/agent-a1.jsonl")
+            .exists());
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata[0].0, imported_path.to_string_lossy());
+        assert_eq!(metadata[0].1.custom_name.as_deref(), Some("Login fix"));
+    }
+
+    #[test]
+    fn test_bundle_archived_session() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let temp = TempDir::new().unwrap();
+        let live = create_source(&temp.path().join("source"));
+        let original = fs::read(&live).unwrap();
+        let subagent = live
+            .with_extension("")
+            .join("subagents")
+            .join("agent-a1.jsonl");
+        for path in [&live, &subagent] {
+            let mut encoder = GzEncoder::new(
+                fs::File::create(format!("{}.gz", path.display())).unwrap(),
+                Compression::default(),
+            );
+            encoder.write_all(&fs::read(path).unwrap()).unwrap();
+            encoder.finish().unwrap();
+            fs::remove_file(path).unwrap();
+        }
+        let archived = live.with_extension("jsonl.gz");
+
+        let bundle = temp.path().join("archived.cchv");
+        let manifest = write_bundle(&[(archived, None)], &bundle).unwrap();
+        assert_eq!(manifest.sessions[0].session_id, SESSION_ID);
+        assert_eq!(
+            manifest.sessions[0].files[1].relative_path,
+            format!("{SESSION_ID}/subagents/agent-a1.jsonl")
+        );
+
+        let target = temp.path().join("target");
+        let (result, _) = import_bundle(
+            &bundle,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Skip,
+        )
+        .unwrap();
+        assert_eq!(result.imported.len(), 1);
+        let imported = fs::read(&result.imported[0].file_path).unwrap();
+        assert_eq!(imported, original);
+    }
+
+    #[test]
+    fn test_import_never_overwrites_silently() {
+        let temp = TempDir::new().unwrap();
+        let bundle = bundle_from_source(&temp);
+
+        // The same session already exists in another project folder
+        let target = temp.path().join("target");
+        let existing_dir = target.join("projects").join("-moved-app");
+        fs::create_dir_all(&existing_dir).unwrap();
+        let existing = existing_dir.join(format!("{SESSION_ID}.jsonl"));
+        fs::write(&existing, "local changes").unwrap();
+
+        assert_eq!(
+            inspect_bundle(&bundle, &target, &no_archive(&temp))
+                .unwrap()
+                .conflicts
+                .len(),
+            1
+        );
+
+        let (result, _) = import_bundle(
+            &bundle,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Skip,
+        )
+        .unwrap();
+        assert!(result.imported.is_empty());
+        assert_eq!(result.skipped[0].existing_path, existing.to_string_lossy());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "local changes");
+
+        let (result, _) = import_bundle(
+            &bundle,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Overwrite,
+        )
+        .unwrap();
+        assert!(result.imported[0].overwritten);
+        assert_ne!(fs::read_to_string(&existing).unwrap(), "local changes");
+        assert!(!target.join("projects").join("-work-app").exists());
+    }
+
+    #[test]
+    fn test_import_conflicts_with_archived_session() {
+        use flate2::write::GzEncoder;
+
+        let temp = TempDir::new().unwrap();
+        let bundle = bundle_from_source(&temp);
+        let target = temp.path().join("target");
+        let archived_dir = no_archive(&temp).join("-old-app");
+        fs::create_dir_all(&archived_dir).unwrap();
+        let archived = archived_dir.join(format!("{SESSION_ID}.jsonl.gz"));
+        let mut encoder = GzEncoder::new(
+            fs::File::create(&archived).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(b"archived").unwrap();
+        encoder.finish().unwrap();
+
+        let conflicts = inspect_bundle(&bundle, &target, &no_archive(&temp))
+            .unwrap()
+            .conflicts;
+        assert_eq!(conflicts[0].existing_path, archived.to_string_lossy());
+
+        let (result, _) = import_bundle(
+            &bundle,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Skip,
+        )
+        .unwrap();
+        assert!(result.imported.is_empty());
+        assert!(archived.exists());
+
+        // Overwriting replaces the archived copy with a live one
+        let (result, _) = import_bundle(
+            &bundle,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Overwrite,
+        )
+        .unwrap();
+        let live = target
+            .join("projects")
+            .join("-old-app")
+            .join(format!("{SESSION_ID}.jsonl"));
+        assert_eq!(result.imported[0].file_path, live.to_string_lossy());
+        assert!(live.exists());
+        assert!(!archived.exists());
+    }
+
+    fn write_raw_bundle(path: &Path, manifest: &BundleManifest, entries: &[(&str, &[u8])]) {
+        let mut zip = ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, bytes) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(bytes).unwrap();
+        }
+        zip.start_file(MANIFEST_NAME, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(serde_json::to_string(manifest).unwrap().as_bytes())
+            .unwrap();
+        zip.finish().unwrap();
+    }
+
+    fn manifest_with(project_dir: &str, relative_path: &str, sha256: String) -> BundleManifest {
+        BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            created_at: String::new(),
+            app_version: String::new(),
+            sessions: vec![BundledSession {
+                session_id: "s1".to_string(),
+                project_dir: project_dir.to_string(),
+                original_path: String::new(),
+                metadata: None,
+                files: vec![BundledFile {
+                    relative_path: relative_path.to_string(),
+                    size: 2,
+                    sha256,
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_import_rejects_corrupt_or_unsafe_bundles() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("target");
+
+        let corrupt = temp.path().join("corrupt.cchv");
+        write_raw_bundle(
+            &corrupt,
+            &manifest_with("-p", "s1.jsonl", sha256_hex(b"{}")),
+            &[("projects/-p/s1.jsonl", b"[]")],
+        );
+        let err = import_bundle(
+            &corrupt,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Skip,
+        )
+        .unwrap_err();
+        assert!(err.contains("Checksum mismatch"));
+        assert!(!target.exists());
+
+        // Entries may not inflate past the size in the manifest
+        let oversized = temp.path().join("oversized.cchv");
+        write_raw_bundle(
+            &oversized,
+            &manifest_with("-p", "s1.jsonl", sha256_hex(b"{}")),
+            &[("projects/-p/s1.jsonl", &[b' '; 4096])],
+        );
+        let err = import_bundle(
+            &oversized,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Skip,
+        )
+        .unwrap_err();
+        assert!(err.contains("larger than the manifest"));
+
+        let traversal = temp.path().join("traversal.cchv");
+        write_raw_bundle(
+            &traversal,
+            &manifest_with("..", "s1.jsonl", sha256_hex(b"{}")),
+            &[("projects/../s1.jsonl", b"{}")],
+        );
+        let err = import_bundle(
+            &traversal,
+            &target,
+            &no_archive(&temp),
+            BundleConflictPolicy::Skip,
+        )
+        .unwrap_err();
+        assert!(err.contains("unsafe path"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Claude folder resolution
+//!
+//! Single place that decides where Claude Code keeps its data. Honors
+//! `CLAUDE_CONFIG_DIR`, falls back to `~/.claude` and the XDG config folder,
+//! and under WSL also looks at Windows-side installs in `/mnt/c/Users`.
+
+use crate::models::{ClaudeDirCandidate, ClaudeDirResolution, ClaudeDirSource};
+use std::path::{Path, PathBuf};
+
+const CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
+const GLOBAL_CONFIG_FILE: &str = ".claude.json";
+
+/// Windows profiles that never hold a user's Claude data
+const SKIPPED_WINDOWS_PROFILES: [&str; 4] = ["Public", "Default", "Default User", "All Users"];
+
+/// Inputs to resolution, captured from the process so tests can supply their own
+#[derive(Debug, Default, Clone)]
+struct Environment {
+    config_dir: Option<PathBuf>,
+    home: Option<PathBuf>,
+    xdg_config_home: Option<PathBuf>,
+    /// `/mnt/c/Users` when running under WSL
+    windows_users: Option<PathBuf>,
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+#[cfg(target_os = "linux")]
+fn is_wsl() -> bool {
+    std::env::var_os("WSL_DISTRO_NAME").is_some()
+        || std::fs::read_to_string("/proc/sys/kernel/osrelease")
+            .is_ok_and(|release| release.to_lowercase().contains("microsoft"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_wsl() -> bool {
+    false
+}
+
+impl Environment {
+    fn current() -> Self {
+        Self {
+            config_dir: env_path(CONFIG_DIR_ENV),
+            home: dirs::home_dir(),
+            xdg_config_home: env_path("XDG_CONFIG_HOME"),
+            windows_users: is_wsl().then(|| PathBuf::from("/mnt/c/Users")),
+        }
+    }
+}
+
+fn candidate(path: PathBuf, source: ClaudeDirSource) -> ClaudeDirCandidate {
+    ClaudeDirCandidate {
+        exists: path.is_dir(),
+        has_projects: path.join("projects").is_dir(),
+        path: path.to_string_lossy().to_string(),
+        source,
+    }
+}
+
+/// `.claude` folders of Windows profiles, sorted by profile name
+fn windows_claude_dirs(users: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(users) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            !SKIPPED_WINDOWS_PROFILES.contains(&name.to_string_lossy().as_ref())
+        })
+        .map(|entry| entry.path().join(".claude"))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Every location to check, highest priority first
+fn candidates(env: &Environment) -> Vec<ClaudeDirCandidate> {
+    let mut found = Vec::new();
+    if let Some(dir) = &env.config_dir {
+        found.push(candidate(dir.clone(), ClaudeDirSource::ConfigDirEnv));
+    }
+    if let Some(home) = &env.home {
+        found.push(candidate(home.join(".claude"), ClaudeDirSource::Home));
+    }
+    let xdg = env
+        .xdg_config_home
+        .clone()
+        .or_else(|| env.home.as_ref().map(|home| home.join(".config")));
+    if let Some(xdg) = xdg {
+        found.push(candidate(xdg.join("claude"), ClaudeDirSource::XdgConfig));
+    }
+    if let Some(users) = &env.windows_users {
+        found.extend(
+            windows_claude_dirs(users)
+                .into_iter()
+                .map(|dir| candidate(dir, ClaudeDirSource::Wsl)),
+        );
+    }
+    found
+}
+
+/// `.claude.json` that Claude Code pairs with `dir`
+fn global_config_path(dir: &Path, source: ClaudeDirSource, env: &Environment) -> PathBuf {
+    let inside = dir.join(GLOBAL_CONFIG_FILE);
+    match source {
+        ClaudeDirSource::ConfigDirEnv => inside,
+        _ if inside.is_file() => inside,
+        ClaudeDirSource::XdgConfig => env
+            .home
+            .as_ref()
+            .map_or(inside, |home| home.join(GLOBAL_CONFIG_FILE)),
+        ClaudeDirSource::Home | ClaudeDirSource::Wsl => dir
+            .parent()
+            .map_or(inside.clone(), |parent| parent.join(GLOBAL_CONFIG_FILE)),
+    }
+}
+
+/// Pick a folder: an existing `CLAUDE_CONFIG_DIR` always wins, then the first
+/// folder with history, then the first that exists, then the first checked
+fn resolve(env: &Environment) -> Option<ClaudeDirResolution> {
+    let candidates = candidates(env);
+    let chosen = candidates
+        .iter()
+        .find(|c| c.source == ClaudeDirSource::ConfigDirEnv && c.exists)
+        .or_else(|| candidates.iter().find(|c| c.has_projects))
+        .or_else(|| candidates.iter().find(|c| c.exists))
+        .or_else(|| candidates.first())?
+        .clone();
+
+    Some(ClaudeDirResolution {
+        global_config_path: global_config_path(Path::new(&chosen.path), chosen.source, env)
+            .to_string_lossy()
+            .to_string(),
+        path: chosen.path,
+        source: chosen.source,
+        candidates,
+    })
+}
+
+/// Resolve the Claude folder for the current process
+pub(crate) fn resolve_claude_dir() -> Result<ClaudeDirResolution, String> {
+    resolve(&Environment::current())
+        .ok_or_else(|| "HOME_DIRECTORY_NOT_FOUND:Could not determine home directory".to_string())
+}
+
+/// The Claude folder (`~/.claude` unless configured otherwise); it may not exist
+pub(crate) fn claude_dir() -> Result<PathBuf, String> {
+    resolve_claude_dir().map(|resolution| PathBuf::from(resolution.path))
+}
+
+/// The global config file (`~/.claude.json` unless configured otherwise)
+pub(crate) fn claude_json_path() -> Result<PathBuf, String> {
+    resolve_claude_dir().map(|resolution| PathBuf::from(resolution.global_config_path))
+}
+
+/// Report the chosen Claude folder, why it was chosen and every location checked
+#[tauri::command]
+pub async fn get_claude_dir_resolution() -> Result<ClaudeDirResolution, String> {
+    resolve_claude_dir()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn home_env(temp: &TempDir) -> Environment {
+        Environment {
+            home: Some(temp.path().join("home")),
+            ..Environment::default()
+        }
+    }
+
+    #[test]
+    fn test_defaults_to_home_claude() {
+        let temp = TempDir::new().unwrap();
+        let env = home_env(&temp);
+
+        // Nothing exists yet: fall back to ~/.claude so errors name the usual place
+        let resolution = resolve(&env).unwrap();
+        assert_eq!(resolution.source, ClaudeDirSource::Home);
+        assert_eq!(
+            PathBuf::from(&resolution.path),
+            temp.path().join("home").join(".claude")
+        );
+        assert_eq!(
+            PathBuf::from(&resolution.global_config_path),
+            temp.path().join("home").join(".claude.json")
+        );
+        let sources: Vec<ClaudeDirSource> =
+            resolution.candidates.iter().map(|c| c.source).collect();
+        assert_eq!(
+            sources,
+            vec![ClaudeDirSource::Home, ClaudeDirSource::XdgConfig]
+        );
+        assert!(resolve(&Environment::default()).is_none());
+    }
+
+    #[test]
+    fn test_config_dir_env_wins_when_present() {
+        let temp = TempDir::new().unwrap();
+        let mut env = home_env(&temp);
+        fs::create_dir_all(temp.path().join("home/.claude/projects")).unwrap();
+        let custom = temp.path().join("custom");
+        env.config_dir = Some(custom.clone());
+
+        // Missing CLAUDE_CONFIG_DIR falls through to the folder with history
+        assert_eq!(resolve(&env).unwrap().source, ClaudeDirSource::Home);
+
+        fs::create_dir_all(&custom).unwrap();
+        let resolution = resolve(&env).unwrap();
+        assert_eq!(resolution.source, ClaudeDirSource::ConfigDirEnv);
+        assert_eq!(PathBuf::from(&resolution.path), custom);
+        assert_eq!(
+            PathBuf::from(&resolution.global_config_path),
+            custom.join(".claude.json")
+        );
+    }
+
+    #[test]
+    fn test_xdg_and_wsl_fallbacks() {
+        let temp = TempDir::new().unwrap();
+        let mut env = home_env(&temp);
+        let users = temp.path().join("Users");
+        fs::create_dir_all(users.join("alex/.claude/projects")).unwrap();
+        fs::create_dir_all(users.join("Public/.claude/projects")).unwrap();
+        env.windows_users = Some(users.clone());
+
+        let resolution = resolve(&env).unwrap();
+        assert_eq!(resolution.source, ClaudeDirSource::Wsl);
+        assert_eq!(
+            PathBuf::from(&resolution.path),
+            users.join("alex").join(".claude")
+        );
+        assert_eq!(
+            PathBuf::from(&resolution.global_config_path),
+            users.join("alex").join(".claude.json")
+        );
+        assert_eq!(resolution.candidates.len(), 3);
+
+        // A local XDG install with history is preferred over the Windows side
+        let xdg = temp.path().join("xdg");
+        fs::create_dir_all(xdg.join("claude/projects")).unwrap();
+        env.xdg_config_home = Some(xdg.clone());
+        let resolution = resolve(&env).unwrap();
+        assert_eq!(resolution.source, ClaudeDirSource::XdgConfig);
+        assert_eq!(PathBuf::from(&resolution.path), xdg.join("claude"));
+        assert_eq!(
+            PathBuf::from(&resolution.global_config_path),
+            temp.path().join("home").join(".claude.json")
+        );
+    }
+}
@@ -3,7 +3,10 @@
 //! This module provides commands for reading and writing Claude Code settings
 //! across different scopes (user, project, local, managed) and MCP server configurations.
 
+use super::claude_dir::{claude_dir, claude_json_path};
+use super::fs_utils::sha256_hex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -15,6 +18,9 @@ pub struct AllSettings {
     pub project: Option<String>,
     pub local: Option<String>,
     pub managed: Option<String>,
+    /// Content hash of each scope that was read, to pass back when saving
+    #[serde(default)]
+    pub hashes: HashMap<String, String>,
 }
 
 /// MCP servers from both settings.json and .mcp.json
@@ -37,24 +43,24 @@ pub struct AllMCPServers {
     pub user_claude_json: Option<serde_json::Value>,
     /// Local/Project-scoped MCP from `~/.claude.json` → `projects.<path>.mcpServers` (official)
     pub local_claude_json: Option<serde_json::Value>,
+    /// Content hash of the file behind each source, keyed like `save_mcp_servers` sources
+    #[serde(default)]
+    pub hashes: HashMap<String, String>,
 }
 
 /// Get the user settings path (~/.claude/settings.json)
-fn get_user_settings_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    Ok(home.join(".claude").join("settings.json"))
+pub(crate) fn get_user_settings_path() -> Result<PathBuf, String> {
+    Ok(claude_dir()?.join("settings.json"))
 }
 
 /// Get the user MCP settings path (~/.claude/.mcp.json)
-fn get_user_mcp_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    Ok(home.join(".claude").join(".mcp.json"))
+pub(crate) fn get_user_mcp_path() -> Result<PathBuf, String> {
+    Ok(claude_dir()?.join(".mcp.json"))
 }
 
 /// Get the main Claude config path (~/.claude.json) - the official config file
-fn get_claude_json_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    Ok(home.join(".claude.json"))
+pub(crate) fn get_claude_json_path() -> Result<PathBuf, String> {
+    claude_json_path()
 }
 
 /// Validate project path to prevent path traversal attacks
@@ -94,7 +100,7 @@ fn validate_project_path(path: &str) -> Result<PathBuf, String> {
 }
 
 /// Get the project MCP settings path (`<project>/.mcp.json`)
-fn get_project_mcp_path(project_path: &str) -> Result<PathBuf, String> {
+pub(crate) fn get_project_mcp_path(project_path: &str) -> Result<PathBuf, String> {
     let validated = validate_project_path(project_path)?;
     Ok(validated.join(".mcp.json"))
 }
@@ -114,7 +120,10 @@ fn get_managed_settings_path() -> Result<PathBuf, String> {
 }
 
 /// Get settings path for a specific scope
-fn get_settings_path(scope: &str, project_path: Option<&str>) -> Result<PathBuf, String> {
+pub(crate) fn get_settings_path(
+    scope: &str,
+    project_path: Option<&str>,
+) -> Result<PathBuf, String> {
     match scope {
         "user" => get_user_settings_path(),
         "project" => {
@@ -141,6 +150,29 @@ fn read_settings_file(path: &Path) -> Result<String, String> {
     fs::read_to_string(path).map_err(|e| format!("Failed to read settings file: {e}"))
 }
 
+/// Hash of a settings file as `read_settings_file` returns it
+pub(crate) fn settings_hash(path: &Path) -> Result<String, String> {
+    read_settings_file(path).map(|content| sha256_hex(content.as_bytes()))
+}
+
+/// Reject a save when the file changed since the caller read it
+///
+/// `expected_hash` is the hash returned with the content the caller edited;
+/// `None` skips the check.
+fn ensure_unchanged(path: &Path, expected_hash: Option<&str>) -> Result<(), String> {
+    let Some(expected) = expected_hash else {
+        return Ok(());
+    };
+    if settings_hash(path)? == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "Conflict: {} was changed by another program since it was loaded",
+            path.display()
+        ))
+    }
+}
+
 /// Write settings file with atomic write pattern
 fn write_settings_file(path: &Path, content: &str) -> Result<(), String> {
     // Validate JSON before writing
@@ -194,14 +226,16 @@ pub async fn get_settings_by_scope(
 /// * `scope` - One of: "user", "project", "local" (NOT "managed" - read-only)
 /// * `content` - JSON string to save
 /// * `project_path` - Required for "project" and "local" scopes (must be absolute path)
+/// * `expected_hash` - Hash from `get_all_settings`; the save is rejected if the file no longer matches
 ///
 /// # Errors
-/// Returns error if scope is "managed" or if JSON is invalid
+/// Returns error if scope is "managed", if JSON is invalid or if the file changed since it was read
 #[tauri::command]
 pub async fn save_settings(
     scope: String,
     content: String,
     project_path: Option<String>,
+    expected_hash: Option<String>,
 ) -> Result<(), String> {
     // Managed settings are read-only
     if scope == "managed" {
@@ -210,6 +244,7 @@ pub async fn save_settings(
 
     tauri::async_runtime::spawn_blocking(move || {
         let path = get_settings_path(&scope, project_path.as_deref())?;
+        ensure_unchanged(&path, expected_hash.as_deref())?;
         write_settings_file(&path, &content)
     })
     .await
@@ -226,29 +261,34 @@ pub async fn save_settings(
 #[tauri::command]
 pub async fn get_all_settings(project_path: Option<String>) -> Result<AllSettings, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        let user = get_user_settings_path()
-            .ok()
-            .and_then(|p| read_settings_file(&p).ok());
-
-        let project = project_path
-            .as_deref()
-            .and_then(|pp| get_settings_path("project", Some(pp)).ok())
-            .and_then(|p| read_settings_file(&p).ok());
-
-        let local = project_path
-            .as_deref()
-            .and_then(|pp| get_settings_path("local", Some(pp)).ok())
-            .and_then(|p| read_settings_file(&p).ok());
-
-        let managed = get_managed_settings_path()
-            .ok()
-            .and_then(|p| read_settings_file(&p).ok());
+        let mut hashes = HashMap::new();
+        let mut read_scope = |scope: &str, path: Option<PathBuf>| {
+            let content = read_settings_file(&path?).ok()?;
+            hashes.insert(scope.to_string(), sha256_hex(content.as_bytes()));
+            Some(content)
+        };
+
+        let user = read_scope("user", get_user_settings_path().ok());
+        let project = read_scope(
+            "project",
+            project_path
+                .as_deref()
+                .and_then(|pp| get_settings_path("project", Some(pp)).ok()),
+        );
+        let local = read_scope(
+            "local",
+            project_path
+                .as_deref()
+                .and_then(|pp| get_settings_path("local", Some(pp)).ok()),
+        );
+        let managed = read_scope("managed", get_managed_settings_path().ok());
 
         Ok(AllSettings {
             user,
             project,
             local,
             managed,
+            hashes,
         })
     })
     .await
@@ -312,63 +352,44 @@ pub async fn get_mcp_servers() -> Result<MCPServers, String> {
 #[tauri::command]
 pub async fn get_all_mcp_servers(project_path: Option<String>) -> Result<AllMCPServers, String> {
     tauri::async_runtime::spawn_blocking(move || {
+        let mut hashes = HashMap::new();
+        // Hash the same bytes that get parsed, so a save catches any later change
+        let mut load = |source: &str, path: Result<PathBuf, String>| {
+            let path = path.ok()?;
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => Some(content),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(_) => return None,
+            };
+            let hash = sha256_hex(content.as_deref().unwrap_or("{}").as_bytes());
+            hashes.insert(source.to_string(), hash);
+            content.and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        };
+        // Check if it has mcpServers key or is servers directly
+        let servers = |json: serde_json::Value| match json.get("mcpServers") {
+            Some(servers) => servers.clone(),
+            None => json,
+        };
+
         // User settings.json mcpServers (legacy)
-        let user_settings = get_user_settings_path().ok().and_then(|p| {
-            read_settings_file(&p).ok().and_then(|content| {
-                serde_json::from_str::<serde_json::Value>(&content)
-                    .ok()
-                    .and_then(|json| json.get("mcpServers").cloned())
-            })
-        });
+        let user_settings = load("user_settings", get_user_settings_path())
+            .and_then(|json| json.get("mcpServers").cloned());
 
         // User .mcp.json (legacy)
-        let user_mcp_file = get_user_mcp_path().ok().and_then(|p| {
-            if !p.exists() {
-                return None;
-            }
-            read_settings_file(&p).ok().and_then(|content| {
-                serde_json::from_str::<serde_json::Value>(&content)
-                    .ok()
-                    .map(|json| {
-                        // Check if it has mcpServers key or is servers directly
-                        if let Some(servers) = json.get("mcpServers") {
-                            servers.clone()
-                        } else {
-                            json
-                        }
-                    })
-            })
-        });
+        let user_mcp_file = load("user_mcp", get_user_mcp_path()).map(servers);
 
         // Project .mcp.json
-        let project_mcp_file = project_path.as_deref().and_then(|pp| {
-            let p = get_project_mcp_path(pp).ok()?;
-            if !p.exists() {
-                return None;
-            }
-            read_settings_file(&p).ok().and_then(|content| {
-                serde_json::from_str::<serde_json::Value>(&content)
-                    .ok()
-                    .map(|json| {
-                        // Check if it has mcpServers key or is servers directly
-                        if let Some(servers) = json.get("mcpServers") {
-                            servers.clone()
-                        } else {
-                            json
-                        }
-                    })
-            })
-        });
+        let project_mcp_file = project_path
+            .as_deref()
+            .and_then(|pp| load("project_mcp", get_project_mcp_path(pp)).map(servers));
 
         // Read ~/.claude.json (official config file)
-        let claude_json = get_claude_json_path().ok().and_then(|p| {
-            if !p.exists() {
-                return None;
+        let claude_json = load("user_claude_json", get_claude_json_path());
+        if project_path.is_some() {
+            if let Some(hash) = hashes.get("user_claude_json").cloned() {
+                hashes.insert("local_claude_json".to_string(), hash);
             }
-            read_settings_file(&p)
-                .ok()
-                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
-        });
+        }
 
         // User-scoped MCP from ~/.claude.json → mcpServers
         let user_claude_json = claude_json
@@ -390,6 +411,7 @@ pub async fn get_all_mcp_servers(project_path: Option<String>) -> Result<AllMCPS
             project_mcp_file,
             user_claude_json,
             local_claude_json,
+            hashes,
         })
     })
     .await
@@ -402,11 +424,13 @@ pub async fn get_all_mcp_servers(project_path: Option<String>) -> Result<AllMCPS
 /// * `source` - One of: `user_settings`, `user_mcp`, `project_mcp`, `user_claude_json`, `local_claude_json`
 /// * `servers` - JSON string of MCP servers object
 /// * `project_path` - Required for `project_mcp` and `local_claude_json` sources
+/// * `expected_hash` - Hash from `get_all_mcp_servers`; the save is rejected if the file no longer matches
 #[tauri::command]
 pub async fn save_mcp_servers(
     source: String,
     servers: String,
     project_path: Option<String>,
+    expected_hash: Option<String>,
 ) -> Result<(), String> {
     // Validate servers JSON
     let servers_value: serde_json::Value =
@@ -417,6 +441,7 @@ pub async fn save_mcp_servers(
             "user_settings" => {
                 // Update mcpServers field in ~/.claude/settings.json (legacy)
                 let path = get_user_settings_path()?;
+                ensure_unchanged(&path, expected_hash.as_deref())?;
                 let mut settings: serde_json::Value = if path.exists() {
                     let content = read_settings_file(&path)?;
                     serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
@@ -432,6 +457,7 @@ pub async fn save_mcp_servers(
             "user_mcp" => {
                 // Write to ~/.claude/.mcp.json (legacy)
                 let path = get_user_mcp_path()?;
+                ensure_unchanged(&path, expected_hash.as_deref())?;
                 // Store with mcpServers wrapper for consistency
                 let mcp_json = serde_json::json!({ "mcpServers": servers_value });
                 let content = serde_json::to_string_pretty(&mcp_json)
@@ -442,6 +468,7 @@ pub async fn save_mcp_servers(
                 // Write to <project>/.mcp.json
                 let pp = project_path.ok_or("project_path required for project_mcp source")?;
                 let path = get_project_mcp_path(&pp)?;
+                ensure_unchanged(&path, expected_hash.as_deref())?;
                 // Store with mcpServers wrapper for consistency
                 let mcp_json = serde_json::json!({ "mcpServers": servers_value });
                 let content = serde_json::to_string_pretty(&mcp_json)
@@ -451,6 +478,7 @@ pub async fn save_mcp_servers(
             "user_claude_json" => {
                 // Update mcpServers field in ~/.claude.json (official)
                 let path = get_claude_json_path()?;
+                ensure_unchanged(&path, expected_hash.as_deref())?;
                 let mut claude_json: serde_json::Value = if path.exists() {
                     let content = read_settings_file(&path)?;
                     serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
@@ -468,6 +496,7 @@ pub async fn save_mcp_servers(
                 let pp =
                     project_path.ok_or("project_path required for local_claude_json source")?;
                 let path = get_claude_json_path()?;
+                ensure_unchanged(&path, expected_hash.as_deref())?;
                 let mut claude_json: serde_json::Value = if path.exists() {
                     let content = read_settings_file(&path)?;
                     serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
@@ -510,6 +539,8 @@ pub struct ClaudeJsonConfig {
     pub project_settings: Option<serde_json::Value>,
     /// File path for reference
     pub file_path: String,
+    /// Content hash, to pass back when saving MCP servers to `~/.claude.json`
+    pub content_hash: String,
 }
 
 /// Get the full ~/.claude.json configuration
@@ -527,16 +558,18 @@ pub async fn get_claude_json_config(
         let path = get_claude_json_path()?;
         let file_path = path.to_string_lossy().to_string();
 
+        let content = read_settings_file(&path)?;
+        let content_hash = sha256_hex(content.as_bytes());
         if !path.exists() {
             return Ok(ClaudeJsonConfig {
                 raw: serde_json::json!({}),
                 mcp_servers: None,
                 project_settings: None,
                 file_path,
+                content_hash,
             });
         }
 
-        let content = read_settings_file(&path)?;
         let raw: serde_json::Value = serde_json::from_str(&content)
             .map_err(|e| format!("Failed to parse claude.json: {e}"))?;
 
@@ -552,6 +585,7 @@ pub async fn get_claude_json_config(
             mcp_servers,
             project_settings,
             file_path,
+            content_hash,
         })
     })
     .await
@@ -568,7 +602,7 @@ pub async fn get_claude_json_config(
 ///
 /// # Returns
 /// Ok(()) if path is safe, error message if not
-fn is_safe_path(path: &Path) -> Result<(), String> {
+pub(crate) fn is_safe_path(path: &Path) -> Result<(), String> {
     let home = dirs::home_dir().ok_or("Could not find home directory")?;
     let allowed_dirs = [
         home.join(".claude-history-viewer").join("exports"),
@@ -766,7 +800,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_save_settings_managed_readonly() {
-        let result = save_settings("managed".to_string(), "{}".to_string(), None).await;
+        let result = save_settings("managed".to_string(), "{}".to_string(), None, None).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("read-only"));
     }
@@ -863,7 +897,7 @@ mod tests {
         let content = r#"{"theme":"dark","fontSize":14}"#;
 
         // Save
-        let save_result = save_settings("user".to_string(), content.to_string(), None).await;
+        let save_result = save_settings("user".to_string(), content.to_string(), None, None).await;
         assert!(save_result.is_ok());
 
         // Retrieve
@@ -877,4 +911,57 @@ mod tests {
 
         drop(temp);
     }
+
+    #[tokio::test]
+    async fn test_save_settings_rejects_stale_hash() {
+        let temp = setup_test_env();
+        let loaded = get_all_settings(None).await.unwrap();
+        let hash = loaded.hashes["user"].clone();
+
+        // Saving against the hash that was read succeeds once
+        save_settings(
+            "user".to_string(),
+            r#"{"theme":"dark"}"#.to_string(),
+            None,
+            Some(hash.clone()),
+        )
+        .await
+        .unwrap();
+
+        // The file no longer matches, so a second save from the same read is rejected
+        let err = save_settings(
+            "user".to_string(),
+            r#"{"theme":"light"}"#.to_string(),
+            None,
+            Some(hash),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("Conflict"));
+        let current = get_settings_by_scope("user".to_string(), None)
+            .await
+            .unwrap();
+        assert!(current.contains("dark"));
+
+        let servers = get_all_mcp_servers(None).await.unwrap();
+        let err = save_mcp_servers(
+            "user_settings".to_string(),
+            "{}".to_string(),
+            None,
+            Some(loaded.hashes["user"].clone()),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("Conflict"));
+        save_mcp_servers(
+            "user_settings".to_string(),
+            "{}".to_string(),
+            None,
+            Some(servers.hashes["user_settings"].clone()),
+        )
+        .await
+        .unwrap();
+
+        drop(temp);
+    }
 }
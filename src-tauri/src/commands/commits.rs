@@ -0,0 +1,429 @@
+//! Session ↔ commit correlation
+//!
+//! Matches a project's sessions against the commits of its repository: by
+//! commit time relative to the session window, by files the session edited,
+//! and by `Co-Authored-By: Claude` trailers.
+
+use super::git::{changed_files, log_commits, open_repository, to_git_commit, LogQuery};
+use super::session::{load_project_sessions, session_edited_files};
+use crate::models::{
+    CommitCorrelation, CommitMatchReason, CorrelatedCommit, CorrelationOptions, GitCommit,
+    SessionCommitLink,
+};
+use chrono::{DateTime, Duration, Utc};
+use git2::Repository;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A session's active window and the files it edited, relative to the repository
+struct SessionWindow {
+    path: String,
+    session_id: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    edited_files: HashSet<String>,
+}
+
+/// A commit with its full message and changed files
+struct RepoCommit {
+    commit: GitCommit,
+    body: String,
+    files: Vec<String>,
+}
+
+/// Commits on any ref authored since `since` (Unix seconds), newest first
+fn read_commits(repo: &Repository, since: i64) -> Vec<RepoCommit> {
+    let query = LogQuery {
+        since: Some(since),
+        all_refs: true,
+        ..LogQuery::default()
+    };
+    let commits = log_commits(repo, &query).unwrap_or_default();
+    commits
+        .iter()
+        .map(|commit| RepoCommit {
+            commit: to_git_commit(commit),
+            body: commit.body().unwrap_or_default().to_string(),
+            // Like `git log`, merges are not attributed any files
+            files: if commit.parent_count() > 1 {
+                Vec::new()
+            } else {
+                changed_files(repo, commit, false)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|change| change.path)
+                    .collect()
+            },
+        })
+        .collect()
+}
+
+/// Whether a commit message has a `Co-Authored-By:` trailer naming Claude
+fn is_claude_co_authored(message: &str) -> bool {
+    message.lines().any(|line| {
+        let line = line.trim().to_lowercase();
+        line.starts_with("co-authored-by:") && line.contains("claude")
+    })
+}
+
+/// `path` relative to the first of `roots` that contains it, with `/` separators
+fn relative_to_repo(path: &str, roots: &[PathBuf]) -> Option<String> {
+    let path = Path::new(path);
+    roots.iter().find_map(|root| {
+        let relative = path.strip_prefix(root).ok()?;
+        Some(
+            relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    })
+}
+
+/// Spellings of the repository's working directory, which git paths are relative to
+fn workdir_roots(repo: &Repository) -> Vec<PathBuf> {
+    let Some(workdir) = repo.workdir() else {
+        return Vec::new();
+    };
+    let mut roots = vec![workdir.to_path_buf()];
+    if let Ok(canonical) = workdir.canonicalize() {
+        if canonical != workdir {
+            roots.push(canonical);
+        }
+    }
+    roots
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Match every commit against every session window
+fn correlate(
+    sessions: &[SessionWindow],
+    commits: Vec<RepoCommit>,
+    options: &CorrelationOptions,
+) -> CommitCorrelation {
+    let after_session = Duration::minutes(i64::from(options.after_session_minutes));
+    let file_match = Duration::days(i64::from(options.file_match_days));
+    let mut correlation = CommitCorrelation::default();
+
+    for repo_commit in commits {
+        let Some(committed) = DateTime::<Utc>::from_timestamp(repo_commit.commit.timestamp, 0)
+        else {
+            continue;
+        };
+        let co_authored = is_claude_co_authored(&repo_commit.body);
+        let mut links: Vec<SessionCommitLink> = Vec::new();
+
+        for session in sessions {
+            let mut reasons = Vec::new();
+            if committed >= session.start && committed <= session.end {
+                reasons.push(CommitMatchReason::DuringSession);
+            } else if committed > session.end && committed <= session.end + after_session {
+                reasons.push(CommitMatchReason::AfterSession);
+            }
+
+            let mut shared_files: Vec<String> = repo_commit
+                .files
+                .iter()
+                .filter(|file| session.edited_files.contains(*file))
+                .cloned()
+                .collect();
+            shared_files.sort();
+            if !shared_files.is_empty()
+                && committed >= session.start
+                && committed <= session.end + file_match
+            {
+                reasons.push(CommitMatchReason::EditedFiles);
+            } else {
+                shared_files.clear();
+            }
+
+            if !reasons.is_empty() {
+                if co_authored {
+                    reasons.push(CommitMatchReason::ClaudeCoAuthor);
+                }
+                links.push(SessionCommitLink {
+                    session_path: session.path.clone(),
+                    session_id: session.session_id.clone(),
+                    commit_hash: repo_commit.commit.hash.clone(),
+                    reasons,
+                    shared_files,
+                });
+            }
+        }
+
+        // A co-authored commit nothing else explains goes to the latest session before it
+        if links.is_empty() && co_authored {
+            let nearest = sessions
+                .iter()
+                .filter(|s| committed >= s.start && committed <= s.end + file_match)
+                .max_by_key(|s| s.end);
+            if let Some(session) = nearest {
+                links.push(SessionCommitLink {
+                    session_path: session.path.clone(),
+                    session_id: session.session_id.clone(),
+                    commit_hash: repo_commit.commit.hash.clone(),
+                    reasons: vec![CommitMatchReason::ClaudeCoAuthor],
+                    shared_files: Vec::new(),
+                });
+            }
+        }
+
+        for link in &links {
+            correlation
+                .session_commits
+                .entry(link.session_path.clone())
+                .or_default()
+                .push(link.commit_hash.clone());
+            correlation
+                .commit_sessions
+                .entry(link.commit_hash.clone())
+                .or_default()
+                .push(link.session_path.clone());
+        }
+        correlation.links.extend(links);
+        correlation.commits.push(CorrelatedCommit {
+            commit: repo_commit.commit,
+            files: repo_commit.files,
+            claude_co_authored: co_authored,
+        });
+    }
+    correlation
+}
+
+/// Link a project's sessions to commits made during or after them
+///
+/// `project_path` is the Claude project folder and `repo_path` the project's
+/// directory on disk, which may be a subfolder of the repository. Returns no
+/// commits when it is not in a git repository.
+#[tauri::command]
+pub async fn correlate_session_commits(
+    project_path: String,
+    repo_path: String,
+    options: Option<CorrelationOptions>,
+) -> Result<CommitCorrelation, String> {
+    let repo = PathBuf::from(&repo_path);
+    if !repo.is_absolute() {
+        return Err("Path must be absolute".to_string());
+    }
+    if !repo.is_dir() {
+        return Err("Path does not exist or is not a directory".to_string());
+    }
+    let options = options.unwrap_or_default();
+    let sessions = load_project_sessions(project_path, None).await?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let Ok(repo) = open_repository(&repo) else {
+            return CommitCorrelation::default();
+        };
+        // Edits may be recorded against either spelling of the working directory
+        let roots = workdir_roots(&repo);
+
+        let windows: Vec<SessionWindow> = sessions
+            .iter()
+            .filter_map(|session| {
+                let start = parse_time(&session.first_message_time)?;
+                let end = parse_time(&session.last_message_time)?.max(start);
+                let edited_files = session_edited_files(&PathBuf::from(&session.file_path))
+                    .iter()
+                    .filter_map(|file| relative_to_repo(file, &roots))
+                    .collect();
+                Some(SessionWindow {
+                    path: session.file_path.clone(),
+                    session_id: session.actual_session_id.clone(),
+                    start,
+                    end,
+                    edited_files,
+                })
+            })
+            .collect();
+
+        let Some(since) = windows.iter().map(|w| w.start.timestamp()).min() else {
+            return CommitCorrelation::default();
+        };
+        correlate(&windows, read_commits(&repo, since), &options)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_time(value).unwrap()
+    }
+
+    fn window(path: &str, start: &str, end: &str, files: &[&str]) -> SessionWindow {
+        SessionWindow {
+            path: path.to_string(),
+            session_id: path.to_string(),
+            start: at(start),
+            end: at(end),
+            edited_files: files.iter().map(|f| (*f).to_string()).collect(),
+        }
+    }
+
+    fn commit(hash: &str, time: &str, body: &str, files: &[&str]) -> RepoCommit {
+        RepoCommit {
+            commit: GitCommit {
+                hash: hash.to_string(),
+                author: "dev".to_string(),
+                date: time.to_string(),
+                message: hash.to_string(),
+                timestamp: at(time).timestamp(),
+            },
+            body: body.to_string(),
+            files: files.iter().map(|f| (*f).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_read_commits() {
+        use crate::commands::git::tests::commit_files;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        commit_files(&repo, 1750928400, "Old commit", &[("old.txt", "old\n")]);
+        commit_files(
+            &repo,
+            1750932000,
+            "Add parser\n\nLonger body\n\nCo-Authored-By: Claude <noreply@anthropic.com>\n",
+            &[
+                ("src/parser.rs", "fn parse() {}\n"),
+                ("README.md", "docs\n"),
+            ],
+        );
+
+        let commits = read_commits(&repo, 1750930000);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].commit.message, "Add parser");
+        let mut files = commits[0].files.clone();
+        files.sort();
+        assert_eq!(files, vec!["README.md", "src/parser.rs"]);
+        assert!(is_claude_co_authored(&commits[0].body));
+
+        let all = read_commits(&repo, 0);
+        assert_eq!(all.len(), 2);
+        assert!(!is_claude_co_authored(&all[1].body));
+    }
+
+    #[tokio::test]
+    async fn test_correlate_project_in_repo_subfolder() {
+        use crate::commands::git::tests::commit_files;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let repo = git2::Repository::init(&root).unwrap();
+        let app = root.join("app");
+        std::fs::create_dir_all(&app).unwrap();
+
+        let project_dir = temp.path().join("project");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let cwd = app.to_string_lossy();
+        let edited = app.join("src/lib.rs").to_string_lossy().to_string();
+        let lines = [
+            serde_json::json!({"uuid": "u1", "sessionId": "s1", "timestamp": "2025-06-26T10:00:00Z", "type": "user", "cwd": cwd, "message": {"role": "user", "content": "Add lib"}}),
+            serde_json::json!({"uuid": "u2", "sessionId": "s1", "timestamp": "2025-06-26T10:05:00Z", "type": "user", "cwd": cwd, "toolUseResult": {"type": "create", "filePath": edited, "content": "pub fn f() {}"}}),
+        ];
+        std::fs::write(
+            project_dir.join("s1.jsonl"),
+            format!("{}\n{}\n", lines[0], lines[1]),
+        )
+        .unwrap();
+
+        // A day later: only the edited file links it to the session
+        commit_files(
+            &repo,
+            1751018400,
+            "Add lib",
+            &[("app/src/lib.rs", "pub fn f() {}\n")],
+        );
+
+        let correlation = correlate_session_commits(
+            project_dir.to_string_lossy().to_string(),
+            app.to_string_lossy().to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(correlation.commits.len(), 1);
+        assert_eq!(correlation.links.len(), 1);
+        assert_eq!(
+            correlation.links[0].reasons,
+            vec![CommitMatchReason::EditedFiles]
+        );
+        assert_eq!(correlation.links[0].shared_files, vec!["app/src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_relative_to_repo() {
+        let roots = [PathBuf::from("/work/app")];
+        assert_eq!(
+            relative_to_repo("/work/app/src/main.rs", &roots).as_deref(),
+            Some("src/main.rs")
+        );
+        assert_eq!(relative_to_repo("/work/other/main.rs", &roots), None);
+    }
+
+    #[test]
+    fn test_correlate() {
+        let sessions = [
+            window(
+                "s1",
+                "2025-06-26T10:00:00Z",
+                "2025-06-26T11:00:00Z",
+                &["src/lib.rs"],
+            ),
+            window("s2", "2025-06-27T09:00:00Z", "2025-06-27T09:30:00Z", &[]),
+        ];
+        let co_author = "Co-Authored-By: Claude <noreply@anthropic.com>";
+        let commits = vec![
+            // Two days later, but touches a file s1 edited
+            commit("c4", "2025-06-28T12:00:00Z", "", &["src/lib.rs"]),
+            // Co-authored, outside every window: attributed to the latest session before it
+            commit("c3", "2025-06-27T15:00:00Z", co_author, &["docs.md"]),
+            // Shortly after s1 ended
+            commit("c2", "2025-06-26T11:30:00Z", "", &["other.rs"]),
+            // During s1, co-authored
+            commit("c1", "2025-06-26T10:30:00Z", co_author, &["src/lib.rs"]),
+            // Before any session
+            commit("c0", "2025-06-25T10:00:00Z", "", &["src/lib.rs"]),
+        ];
+
+        let correlation = correlate(&sessions, commits, &CorrelationOptions::default());
+        assert_eq!(correlation.commits.len(), 5);
+
+        let reasons = |hash: &str| -> Vec<CommitMatchReason> {
+            correlation
+                .links
+                .iter()
+                .find(|link| link.commit_hash == hash)
+                .map(|link| link.reasons.clone())
+                .unwrap_or_default()
+        };
+        assert_eq!(
+            reasons("c1"),
+            vec![
+                CommitMatchReason::DuringSession,
+                CommitMatchReason::EditedFiles,
+                CommitMatchReason::ClaudeCoAuthor
+            ]
+        );
+        assert_eq!(reasons("c2"), vec![CommitMatchReason::AfterSession]);
+        assert_eq!(reasons("c3"), vec![CommitMatchReason::ClaudeCoAuthor]);
+        assert_eq!(reasons("c4"), vec![CommitMatchReason::EditedFiles]);
+        assert!(reasons("c0").is_empty());
+
+        assert_eq!(correlation.session_commits["s1"], vec!["c4", "c2", "c1"]);
+        assert_eq!(correlation.session_commits["s2"], vec!["c3"]);
+        assert_eq!(correlation.commit_sessions["c1"], vec!["s1"]);
+        assert!(!correlation.commit_sessions.contains_key("c0"));
+    }
+}
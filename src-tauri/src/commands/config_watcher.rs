@@ -0,0 +1,256 @@
+//! Watching Claude Code's config files
+//!
+//! Settings, MCP config and `~/.claude.json` are edited by Claude Code and by
+//! hand while the app is open. Their parent directories are watched
+//! non-recursively, since editors replace files rather than write them in
+//! place, and a `config-file-changed` event carries each file's new content
+//! hash so the settings screens can tell stale data from their own saves.
+
+use super::claude_settings::{
+    get_claude_json_path, get_project_mcp_path, get_settings_path, get_user_mcp_path,
+    get_user_settings_path, settings_hash,
+};
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebouncedEvent, Debouncer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Which config file changed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileKind {
+    /// `~/.claude/settings.json`
+    UserSettings,
+    /// `~/.claude/.mcp.json`
+    UserMcp,
+    /// `~/.claude.json`
+    ClaudeJson,
+    /// `<project>/.claude/settings.json`
+    ProjectSettings,
+    /// `<project>/.claude/settings.local.json`
+    LocalSettings,
+    /// `<project>/.mcp.json`
+    ProjectMcp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFileChange {
+    pub kind: ConfigFileKind,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    pub exists: bool,
+    /// Hash as the settings commands report it, for their `expected_hash`
+    pub content_hash: String,
+}
+
+/// The running config watcher, if any
+#[derive(Default)]
+pub struct ConfigWatcherState(Mutex<Option<Debouncer<RecommendedWatcher>>>);
+
+/// `path` with its parent directory resolved, as the watcher reports it
+fn watched_path(path: &Path) -> Option<PathBuf> {
+    let parent = path.parent()?.canonicalize().ok()?;
+    Some(parent.join(path.file_name()?))
+}
+
+/// Config files to report and the last hash seen for each
+#[derive(Debug, Default)]
+struct ConfigTargets {
+    files: HashMap<PathBuf, ConfigFileKind>,
+    project_path: Option<String>,
+    hashes: HashMap<PathBuf, String>,
+}
+
+impl ConfigTargets {
+    fn new(files: Vec<(PathBuf, ConfigFileKind)>, project_path: Option<String>) -> Self {
+        let files: HashMap<PathBuf, ConfigFileKind> = files
+            .into_iter()
+            .filter_map(|(path, kind)| Some((watched_path(&path)?, kind)))
+            .collect();
+        let hashes = files
+            .keys()
+            .filter_map(|path| Some((path.clone(), settings_hash(path).ok()?)))
+            .collect();
+        Self {
+            files,
+            project_path,
+            hashes,
+        }
+    }
+
+    /// The user's config files, plus a project's when `project_path` is given
+    fn for_project(project_path: Option<&str>) -> Result<Self, String> {
+        let mut files = vec![
+            (get_user_settings_path()?, ConfigFileKind::UserSettings),
+            (get_user_mcp_path()?, ConfigFileKind::UserMcp),
+            (get_claude_json_path()?, ConfigFileKind::ClaudeJson),
+        ];
+        if let Some(pp) = project_path {
+            files.push((
+                get_settings_path("project", Some(pp))?,
+                ConfigFileKind::ProjectSettings,
+            ));
+            files.push((
+                get_settings_path("local", Some(pp))?,
+                ConfigFileKind::LocalSettings,
+            ));
+            files.push((get_project_mcp_path(pp)?, ConfigFileKind::ProjectMcp));
+        }
+        Ok(Self::new(files, project_path.map(str::to_string)))
+    }
+
+    /// Directories to watch
+    fn directories(&self) -> BTreeSet<PathBuf> {
+        self.files
+            .keys()
+            .filter_map(|path| path.parent().map(Path::to_path_buf))
+            .collect()
+    }
+
+    /// Config files among `paths` whose content changed since last seen
+    fn changes(&mut self, paths: &[PathBuf]) -> Vec<ConfigFileChange> {
+        let mut changes = Vec::new();
+        for path in paths {
+            let Some(&kind) = self.files.get(path) else {
+                continue;
+            };
+            let Ok(content_hash) = settings_hash(path) else {
+                continue;
+            };
+            if self.hashes.get(path) == Some(&content_hash) {
+                continue;
+            }
+            self.hashes.insert(path.clone(), content_hash.clone());
+            let project_path = match kind {
+                ConfigFileKind::UserSettings
+                | ConfigFileKind::UserMcp
+                | ConfigFileKind::ClaudeJson => None,
+                ConfigFileKind::ProjectSettings
+                | ConfigFileKind::LocalSettings
+                | ConfigFileKind::ProjectMcp => self.project_path.clone(),
+            };
+            changes.push(ConfigFileChange {
+                kind,
+                path: path.to_string_lossy().to_string(),
+                project_path,
+                exists: path.exists(),
+                content_hash,
+            });
+        }
+        changes
+    }
+}
+
+/// Watch the user's config files, and a project's when `project_path` is given
+///
+/// Replaces any config watcher already running, so switching projects only
+/// needs another call.
+#[tauri::command]
+pub async fn start_config_watcher(
+    app_handle: AppHandle,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let mut targets = ConfigTargets::for_project(project_path.as_deref())?;
+    let directories = targets.directories();
+
+    let app_handle_clone = app_handle.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(300),
+        move |result: Result<Vec<DebouncedEvent>, notify::Error>| match result {
+            Ok(events) => {
+                let paths: Vec<PathBuf> = events.into_iter().map(|event| event.path).collect();
+                for change in targets.changes(&paths) {
+                    if let Err(e) = app_handle_clone.emit("config-file-changed", &change) {
+                        log::error!("Failed to emit config change: {e}");
+                    }
+                }
+            }
+            Err(error) => {
+                log::error!("Config watcher error: {error:?}");
+            }
+        },
+    )
+    .map_err(|e| format!("Failed to create config watcher: {e}"))?;
+
+    // Directories that do not exist yet cannot be watched
+    for directory in &directories {
+        if let Err(e) = debouncer
+            .watcher()
+            .watch(directory, RecursiveMode::NonRecursive)
+        {
+            log::warn!("Cannot watch {}: {e}", directory.display());
+        }
+    }
+
+    let state: tauri::State<ConfigWatcherState> = app_handle.state();
+    let mut watcher = state.0.lock().map_err(|e| format!("Lock error: {e}"))?;
+    *watcher = Some(debouncer);
+    log::info!(
+        "Config watcher started for {} directories",
+        directories.len()
+    );
+    Ok(())
+}
+
+/// Stop watching config files
+#[tauri::command]
+pub async fn stop_config_watcher(app_handle: AppHandle) -> Result<(), String> {
+    let state: tauri::State<ConfigWatcherState> = app_handle.state();
+    let mut watcher = state.0.lock().map_err(|e| format!("Lock error: {e}"))?;
+    *watcher = None;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_changes() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("app");
+        fs::create_dir_all(&project).unwrap();
+        let settings = temp.path().join("settings.json");
+        let mcp = project.join(".mcp.json");
+        fs::write(&settings, "{}").unwrap();
+        let mut targets = ConfigTargets::new(
+            vec![
+                (settings.clone(), ConfigFileKind::UserSettings),
+                (mcp.clone(), ConfigFileKind::ProjectMcp),
+            ],
+            Some(project.to_string_lossy().to_string()),
+        );
+        let settings = watched_path(&settings).unwrap();
+        let mcp = watched_path(&mcp).unwrap();
+        assert_eq!(targets.directories().len(), 2);
+
+        // Unchanged content and unrelated files are not reported
+        let stray = settings.with_file_name("settings.json.tmp");
+        fs::write(&stray, "{}").unwrap();
+        assert!(targets.changes(&[settings.clone(), stray]).is_empty());
+
+        fs::write(&settings, r#"{"theme":"dark"}"#).unwrap();
+        fs::write(&mcp, r#"{"mcpServers":{}}"#).unwrap();
+        let changes = targets.changes(&[settings.clone(), mcp.clone()]);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, ConfigFileKind::UserSettings);
+        assert_eq!(changes[0].project_path, None);
+        assert_eq!(changes[0].content_hash, settings_hash(&settings).unwrap(),);
+        assert_eq!(changes[1].kind, ConfigFileKind::ProjectMcp);
+        assert!(changes[1].project_path.is_some());
+        assert!(targets.changes(std::slice::from_ref(&settings)).is_empty());
+
+        fs::remove_file(&mcp).unwrap();
+        let changes = targets.changes(std::slice::from_ref(&mcp));
+        assert_eq!(changes.len(), 1);
+        assert!(!changes[0].exists);
+    }
+}
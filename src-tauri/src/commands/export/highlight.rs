@@ -0,0 +1,323 @@
+//! Lightweight syntax highlighting for HTML export
+//!
+//! A small lexer that recognizes comments, strings, numbers and keywords for
+//! the languages that show up most in sessions. Output is escaped HTML with
+//! `tok-*` classes styled by the export stylesheet.
+
+/// Comment, string and keyword rules for a language family
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    /// Characters that open a string literal
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+    /// Keywords match in any case (SQL)
+    case_insensitive: bool,
+}
+
+impl Syntax {
+    fn is_keyword(&self, word: &str) -> bool {
+        if self.case_insensitive {
+            self.keywords.iter().any(|k| k.eq_ignore_ascii_case(word))
+        } else {
+            self.keywords.contains(&word)
+        }
+    }
+}
+
+const C_LIKE_KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "from",
+    "func",
+    "function",
+    "go",
+    "if",
+    "impl",
+    "implements",
+    "import",
+    "in",
+    "interface",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "new",
+    "nil",
+    "null",
+    "package",
+    "private",
+    "pub",
+    "public",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "undefined",
+    "use",
+    "var",
+    "void",
+    "where",
+    "while",
+    "yield",
+];
+
+const SCRIPT_KEYWORDS: &[&str] = &[
+    "and", "as", "case", "class", "def", "do", "done", "elif", "else", "esac", "except", "export",
+    "False", "fi", "finally", "for", "from", "function", "if", "import", "in", "is", "lambda",
+    "local", "None", "not", "or", "pass", "raise", "return", "then", "True", "try", "while",
+    "with", "yield",
+];
+
+const SQL_KEYWORDS: &[&str] = &[
+    "and", "as", "by", "create", "delete", "from", "group", "insert", "into", "join", "left",
+    "limit", "not", "null", "on", "or", "order", "select", "set", "table", "update", "values",
+    "where",
+];
+
+const LUA_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+const JSON_KEYWORDS: &[&str] = &["true", "false", "null"];
+
+fn syntax_for(language: &str) -> Syntax {
+    match language.to_ascii_lowercase().as_str() {
+        "py" | "python" | "sh" | "bash" | "zsh" | "shell" | "console" | "rb" | "ruby" | "yaml"
+        | "yml" | "toml" | "dockerfile" | "makefile" => Syntax {
+            line_comments: &["#"],
+            block_comment: None,
+            quotes: &['"', '\''],
+            keywords: SCRIPT_KEYWORDS,
+            case_insensitive: false,
+        },
+        "sql" => Syntax {
+            line_comments: &["--"],
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '\''],
+            keywords: SQL_KEYWORDS,
+            case_insensitive: true,
+        },
+        "lua" => Syntax {
+            line_comments: &["--"],
+            block_comment: Some(("--[[", "]]")),
+            quotes: &['"', '\''],
+            keywords: LUA_KEYWORDS,
+            case_insensitive: false,
+        },
+        "json" | "jsonl" => Syntax {
+            line_comments: &[],
+            block_comment: None,
+            quotes: &['"'],
+            keywords: JSON_KEYWORDS,
+            case_insensitive: false,
+        },
+        // Apostrophes in prose are not quotes
+        "" | "text" | "txt" | "plain" | "diff" | "md" | "markdown" => Syntax {
+            line_comments: &[],
+            block_comment: None,
+            quotes: &[],
+            keywords: &[],
+            case_insensitive: false,
+        },
+        // `'` starts lifetimes and labels, not strings
+        "rs" | "rust" => Syntax {
+            line_comments: &["//"],
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"'],
+            keywords: C_LIKE_KEYWORDS,
+            case_insensitive: false,
+        },
+        _ => Syntax {
+            line_comments: &["//"],
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '\'', '`'],
+            keywords: C_LIKE_KEYWORDS,
+            case_insensitive: false,
+        },
+    }
+}
+
+/// Escape text for HTML element and attribute content
+pub(super) fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn push_token(out: &mut String, class: &str, text: &str) {
+    out.push_str("<span class=\"tok-");
+    out.push_str(class);
+    out.push_str("\">");
+    out.push_str(&escape_html(text));
+    out.push_str("</span>");
+}
+
+/// Highlight `code` as `language`, returning escaped HTML
+pub(super) fn highlight_code(code: &str, language: &str) -> String {
+    let syntax = syntax_for(language);
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+
+    while let Some(c) = rest.chars().next() {
+        // Block comments first: Lua's `--[[` also starts with its line comment
+        if let Some((open, close)) = syntax.block_comment {
+            if rest.starts_with(open) {
+                let end = rest[open.len()..]
+                    .find(close)
+                    .map_or(rest.len(), |i| open.len() + i + close.len());
+                push_token(&mut out, "com", &rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        if let Some(prefix) = syntax.line_comments.iter().find(|p| rest.starts_with(**p)) {
+            // `#` only starts a comment at a word boundary (not in `a#b` or `$#`)
+            let boundary = *prefix != "#" || out.is_empty() || out.ends_with([' ', '\t', '\n']);
+            if boundary {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                push_token(&mut out, "com", &rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        if syntax.quotes.contains(&c) {
+            // Strings run to the matching quote, honoring backslash escapes
+            let mut end = rest.len();
+            let mut escaped = false;
+            for (i, ch) in rest.char_indices().skip(1) {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == c {
+                    end = i + ch.len_utf8();
+                    break;
+                } else if ch == '\n' && c != '`' {
+                    end = i;
+                    break;
+                }
+            }
+            push_token(&mut out, "str", &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let end = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '.' || ch == '_'))
+                .unwrap_or(rest.len());
+            push_token(&mut out, "num", &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if syntax.is_keyword(word) {
+                push_token(&mut out, "kw", word);
+            } else {
+                out.push_str(&escape_html(word));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        out.push_str(&escape_html(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_rust() {
+        let html = highlight_code("let x = \"a<b\"; // note\nfn f() {}", "rust");
+        assert_eq!(
+            html,
+            "<span class=\"tok-kw\">let</span> x = <span class=\"tok-str\">&quot;a&lt;b&quot;</span>; \
+             <span class=\"tok-com\">// note</span>\n<span class=\"tok-kw\">fn</span> f() {}"
+        );
+    }
+
+    #[test]
+    fn test_highlight_rust_lifetimes() {
+        let html = highlight_code("fn f<'a>(x: &'a str) {}", "rust");
+        assert!(!html.contains("tok-str"));
+        assert!(html.contains("&#39;a str"));
+    }
+
+    #[test]
+    fn test_highlight_sql_and_lua() {
+        let html = highlight_code("SELECT id FROM t", "sql");
+        assert_eq!(
+            html,
+            "<span class=\"tok-kw\">SELECT</span> id <span class=\"tok-kw\">FROM</span> t"
+        );
+
+        let html = highlight_code("local x = nil --[[ a\nb ]] select", "lua");
+        assert!(html.starts_with("<span class=\"tok-kw\">local</span>"));
+        assert!(html.contains("<span class=\"tok-kw\">nil</span>"));
+        assert!(html.contains("<span class=\"tok-com\">--[[ a\nb ]]</span>"));
+        assert!(html.ends_with(" select"));
+    }
+
+    #[test]
+    fn test_highlight_shell_and_plain_text() {
+        let html = highlight_code("echo $# # done", "bash");
+        assert!(html.ends_with("<span class=\"tok-com\"># done</span>"));
+        assert!(html.contains("$#"));
+
+        assert_eq!(highlight_code("if <x>", "text"), "if &lt;x&gt;");
+        assert_eq!(
+            highlight_code("it's \"fine\"", "markdown"),
+            "it&#39;s &quot;fine&quot;"
+        );
+        assert_eq!(
+            highlight_code("42", "json"),
+            "<span class=\"tok-num\">42</span>"
+        );
+    }
+}
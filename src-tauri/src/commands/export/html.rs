@@ -0,0 +1,454 @@
+//! Self-contained HTML rendering
+//!
+//! Produces a single HTML file with inline CSS: a header per session with its
+//! summary, models and token totals, collapsible tool calls and highlighted
+//! code blocks. Project exports add a table of contents. Everything is built
+//! from typed messages, so it needs no webview.
+
+use super::highlight::{escape_html, highlight_code};
+use super::transcript::{
+    message_turn, one_line, session_summary, tool_call_summary, tool_result_text, truncate_chars,
+    Turn, DEFAULT_MAX_TOOL_RESULT_CHARS,
+};
+use crate::models::ClaudeMessage;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Options for HTML export
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HtmlExportOptions {
+    pub include_thinking: bool,
+    /// Tool results longer than this are truncated; `0` disables the limit
+    pub max_tool_result_chars: usize,
+    pub include_sidechain: bool,
+    /// Replace secrets and PII before rendering
+    pub redact: bool,
+}
+
+impl Default for HtmlExportOptions {
+    fn default() -> Self {
+        Self {
+            include_thinking: false,
+            max_tool_result_chars: DEFAULT_MAX_TOOL_RESULT_CHARS,
+            include_sidechain: false,
+            redact: false,
+        }
+    }
+}
+
+const STYLESHEET: &str = r"
+:root { color-scheme: light dark; --bg: #ffffff; --fg: #1f2328; --muted: #656d76; --border: #d0d7de; --panel: #f6f8fa; --user: #ddf4ff; --accent: #8250df; }
+@media (prefers-color-scheme: dark) { :root { --bg: #0d1117; --fg: #e6edf3; --muted: #8d96a0; --border: #30363d; --panel: #161b22; --user: #0c2d6b; --accent: #d2a8ff; } }
+* { box-sizing: border-box; }
+body { margin: 0 auto; max-width: 960px; padding: 24px; background: var(--bg); color: var(--fg); font: 15px/1.6 -apple-system, BlinkMacSystemFont, 'Segoe UI', Helvetica, Arial, sans-serif; }
+h1 { font-size: 1.8em; margin: 0 0 8px; }
+h2 { font-size: 1.4em; margin: 0 0 8px; }
+h3 { font-size: 1em; margin: 0 0 8px; color: var(--muted); text-transform: uppercase; letter-spacing: .04em; }
+h3 time { font-weight: normal; text-transform: none; margin-left: 8px; }
+a { color: var(--accent); }
+nav ol { padding-left: 24px; }
+.session { border-top: 1px solid var(--border); padding-top: 24px; margin-top: 32px; }
+.meta { color: var(--muted); font-size: .9em; margin: 0 0 16px; }
+.meta span + span::before { content: ' · '; }
+.turn { padding: 12px 16px; border-radius: 8px; margin: 12px 0; border: 1px solid var(--border); }
+.turn.user { background: var(--user); }
+details { margin: 8px 0; border: 1px solid var(--border); border-radius: 6px; background: var(--panel); }
+details > summary { cursor: pointer; padding: 6px 10px; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: .85em; }
+details > :not(summary) { margin: 0 10px 10px; }
+details.error > summary { color: #cf222e; }
+pre { overflow-x: auto; padding: 10px; border-radius: 6px; background: var(--panel); border: 1px solid var(--border); font-size: .85em; line-height: 1.45; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; }
+p code { background: var(--panel); padding: 1px 4px; border-radius: 4px; }
+.tok-kw { color: #cf222e; } .tok-str { color: #0a3069; } .tok-com { color: #6e7781; font-style: italic; } .tok-num { color: #0550ae; }
+@media (prefers-color-scheme: dark) { .tok-kw { color: #ff7b72; } .tok-str { color: #a5d6ff; } .tok-com { color: #8b949e; } .tok-num { color: #79c0ff; } }
+";
+
+/// Header facts for a single session
+#[derive(Debug, Default, PartialEq)]
+struct SessionOverview {
+    session_id: String,
+    title: String,
+    started: String,
+    ended: String,
+    models: BTreeSet<String>,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_tokens: u64,
+    message_count: usize,
+}
+
+impl SessionOverview {
+    fn from_messages(messages: &[ClaudeMessage]) -> Self {
+        let mut overview = Self::default();
+        for message in messages.iter().filter(|m| m.message_type != "summary") {
+            if overview.session_id.is_empty() {
+                overview.session_id.clone_from(&message.session_id);
+                overview.started.clone_from(&message.timestamp);
+            }
+            overview.ended.clone_from(&message.timestamp);
+            overview.message_count += 1;
+            if let Some(model) = &message.model {
+                overview.models.insert(model.clone());
+            }
+            if let Some(usage) = &message.usage {
+                overview.input_tokens += u64::from(usage.input_tokens.unwrap_or(0));
+                overview.output_tokens += u64::from(usage.output_tokens.unwrap_or(0));
+                overview.cache_tokens += u64::from(usage.cache_creation_input_tokens.unwrap_or(0))
+                    + u64::from(usage.cache_read_input_tokens.unwrap_or(0));
+            }
+        }
+        overview.title = session_summary(messages).map_or_else(
+            || format!("Session {}", overview.session_id),
+            str::to_string,
+        );
+        overview
+    }
+
+    fn render_meta(&self) -> String {
+        let mut parts = vec![
+            format!(
+                "<span>Session <code>{}</code></span>",
+                escape_html(&self.session_id)
+            ),
+            format!(
+                "<span><time>{}</time> – <time>{}</time></span>",
+                escape_html(&self.started),
+                escape_html(&self.ended)
+            ),
+            format!("<span>{} messages</span>", self.message_count),
+        ];
+        if !self.models.is_empty() {
+            let models: Vec<String> = self.models.iter().map(|m| escape_html(m)).collect();
+            parts.push(format!("<span>{}</span>", models.join(", ")));
+        }
+        parts.push(format!(
+            "<span>{} input / {} output / {} cache tokens</span>",
+            self.input_tokens, self.output_tokens, self.cache_tokens
+        ));
+        format!("<p class=\"meta\">{}</p>\n", parts.concat())
+    }
+}
+
+/// Escape prose and turn `inline code` spans into `<code>`
+fn render_inline(text: &str) -> String {
+    let ticks = text.matches('`').count();
+    let mut out = String::new();
+    for (i, part) in text.split('`').enumerate() {
+        // Odd segments sit between backticks; an unmatched trailing one stays literal
+        if i % 2 == 0 {
+            out.push_str(&escape_html(part));
+        } else if i < ticks {
+            out.push_str("<code>");
+            out.push_str(&escape_html(part));
+            out.push_str("</code>");
+        } else {
+            out.push('`');
+            out.push_str(&escape_html(part));
+        }
+    }
+    out
+}
+
+fn flush_paragraph(lines: &mut Vec<&str>, out: &mut String) {
+    if lines.is_empty() {
+        return;
+    }
+    let rendered: Vec<String> = lines.iter().map(|line| render_inline(line)).collect();
+    out.push_str("<p>");
+    out.push_str(&rendered.join("<br>\n"));
+    out.push_str("</p>\n");
+    lines.clear();
+}
+
+/// Render message text: fenced code blocks are highlighted, the rest becomes paragraphs
+fn render_text(text: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<(String, String, Vec<&str>)> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some((fence, language, body)) = code.as_mut() {
+            if trimmed.trim_end() == fence.as_str() {
+                out.push_str(&format!(
+                    "<pre><code>{}</code></pre>\n",
+                    highlight_code(&body.join("\n"), language)
+                ));
+                code = None;
+            } else {
+                body.push(line);
+            }
+            continue;
+        }
+
+        let fence_len = trimmed.chars().take_while(|c| *c == '`').count();
+        if fence_len >= 3 {
+            flush_paragraph(&mut paragraph, &mut out);
+            let language = trimmed[fence_len..].trim().to_string();
+            code = Some(("`".repeat(fence_len), language, Vec::new()));
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut out);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush_paragraph(&mut paragraph, &mut out);
+    // An unterminated fence still renders as code
+    if let Some((_, language, body)) = code {
+        out.push_str(&format!(
+            "<pre><code>{}</code></pre>\n",
+            highlight_code(&body.join("\n"), &language)
+        ));
+    }
+    out
+}
+
+fn details(class: &str, summary: &str, body: &str) -> String {
+    format!(
+        "<details class=\"{class}\"><summary>{}</summary>\n{body}</details>\n",
+        escape_html(summary)
+    )
+}
+
+fn render_block(block: &Value, options: &HtmlExportOptions, out: &mut String) {
+    match block.get("type").and_then(Value::as_str) {
+        Some("text") => {
+            if let Some(text) = block.get("text").and_then(Value::as_str) {
+                out.push_str(&render_text(text));
+            }
+        }
+        Some("thinking") if options.include_thinking => {
+            if let Some(thinking) = block.get("thinking").and_then(Value::as_str) {
+                out.push_str(&details("thinking", "Thinking", &render_text(thinking)));
+            }
+        }
+        Some("tool_use") => {
+            let name = block.get("name").and_then(Value::as_str).unwrap_or("tool");
+            let input = block.get("input").cloned().unwrap_or(Value::Null);
+            let json = serde_json::to_string_pretty(&input).unwrap_or_default();
+            let body = format!(
+                "<pre><code>{}</code></pre>\n",
+                highlight_code(&json, "json")
+            );
+            out.push_str(&details("tool", &tool_call_summary(name, &input), &body));
+        }
+        Some("tool_result") => {
+            let content = block.get("content").cloned().unwrap_or(Value::Null);
+            let text = truncate_chars(&tool_result_text(&content), options.max_tool_result_chars);
+            let is_error = block.get("is_error").and_then(Value::as_bool) == Some(true);
+            let (class, summary) = if is_error {
+                ("tool-result error", "Tool error".to_string())
+            } else {
+                (
+                    "tool-result",
+                    format!("Tool result — {}", one_line(&text, 80)),
+                )
+            };
+            let body = format!("<pre><code>{}</code></pre>\n", escape_html(&text));
+            out.push_str(&details(class, &summary, &body));
+        }
+        Some("image") => out.push_str("<p><em>[image]</em></p>\n"),
+        _ => {}
+    }
+}
+
+/// Render one session as a `<section>` (without the surrounding document)
+fn render_session_section(
+    messages: &[ClaudeMessage],
+    anchor: &str,
+    heading_level: u8,
+    options: &HtmlExportOptions,
+) -> String {
+    let overview = SessionOverview::from_messages(messages);
+    let mut out = format!(
+        "<section class=\"session\" id=\"{anchor}\">\n<h{heading_level}>{}</h{heading_level}>\n",
+        escape_html(&overview.title)
+    );
+    out.push_str(&overview.render_meta());
+
+    let mut current: Option<(Turn, String)> = None;
+    let close_turn = |turn: Option<(Turn, String)>, out: &mut String| {
+        if let Some((_, body)) = turn {
+            out.push_str(&body);
+            out.push_str("</div>\n");
+        }
+    };
+
+    for message in messages {
+        let Some((turn, content)) = message_turn(message, options.include_sidechain) else {
+            continue;
+        };
+        let mut body = String::new();
+        match content {
+            Value::String(text) => body.push_str(&render_text(text)),
+            Value::Array(blocks) => {
+                for block in blocks {
+                    render_block(block, options, &mut body);
+                }
+            }
+            _ => {}
+        }
+        if body.is_empty() {
+            continue;
+        }
+
+        match current.as_mut() {
+            Some((current_turn, current_body)) if *current_turn == turn => {
+                current_body.push_str(&body);
+            }
+            _ => {
+                close_turn(current.take(), &mut out);
+                let class = match turn {
+                    Turn::User => "user",
+                    Turn::Assistant => "assistant",
+                };
+                let header = format!(
+                    "<div class=\"turn {class}\">\n<h3>{}<time>{}</time></h3>\n{body}",
+                    turn.label(),
+                    escape_html(&message.timestamp)
+                );
+                current = Some((turn, header));
+            }
+        }
+    }
+    close_turn(current, &mut out);
+
+    out.push_str("</section>\n");
+    out
+}
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{STYLESHEET}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_html(title)
+    )
+}
+
+/// Render a single session as a standalone HTML document
+pub fn render_session_html(messages: &[ClaudeMessage], options: &HtmlExportOptions) -> String {
+    let title = SessionOverview::from_messages(messages).title;
+    document(
+        &title,
+        &render_session_section(messages, "session", 1, options),
+    )
+}
+
+/// Render several sessions of a project as one HTML document with a table of contents
+pub fn render_project_html(
+    project_name: &str,
+    sessions: &[Vec<ClaudeMessage>],
+    options: &HtmlExportOptions,
+) -> String {
+    let mut toc = String::from("<nav>\n<h2>Sessions</h2>\n<ol>\n");
+    let mut sections = String::new();
+
+    for (idx, messages) in sessions.iter().enumerate() {
+        let anchor = format!("session-{}", idx + 1);
+        let overview = SessionOverview::from_messages(messages);
+        toc.push_str(&format!(
+            "<li><a href=\"#{anchor}\">{}</a> <span class=\"meta\">{} · {} messages</span></li>\n",
+            escape_html(&overview.title),
+            escape_html(&overview.started),
+            overview.message_count
+        ));
+        sections.push_str(&render_session_section(messages, &anchor, 2, options));
+    }
+    toc.push_str("</ol>\n</nav>\n");
+
+    let body = format!(
+        "<h1>{}</h1>\n<p class=\"meta\"><span>{} sessions</span></p>\n{toc}{sections}",
+        escape_html(project_name),
+        sessions.len()
+    );
+    document(project_name, &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(message_type: &str, content: Value, extra: Value) -> ClaudeMessage {
+        let mut value = json!({
+            "uuid": "u",
+            "parentUuid": null,
+            "sessionId": "session-1",
+            "timestamp": "2025-06-26T10:00:00Z",
+            "type": message_type,
+            "content": content,
+            "toolUse": null,
+            "toolUseResult": null,
+            "isSidechain": false
+        });
+        if let (Some(obj), Some(extra)) = (value.as_object_mut(), extra.as_object()) {
+            obj.extend(extra.clone());
+        }
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn conversation() -> Vec<ClaudeMessage> {
+        vec![
+            message("summary", json!("Fix <build>"), json!({})),
+            message("user", json!("Why does `cargo build` fail?"), json!({})),
+            message(
+                "assistant",
+                json!([
+                    {"type": "text", "text": "Try:\n\n```rust\nfn main() {}\n```"},
+                    {"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "cargo build"}}
+                ]),
+                json!({
+                    "model": "claude-opus-4-20250514",
+                    "usage": {"input_tokens": 100, "output_tokens": 50, "cache_read_input_tokens": 10}
+                }),
+            ),
+            message(
+                "user",
+                json!([{"type": "tool_result", "tool_use_id": "t1", "content": "error", "is_error": true}]),
+                json!({}),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_render_session_html() {
+        let html = render_session_html(&conversation(), &HtmlExportOptions::default());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<title>Fix &lt;build&gt;</title>"));
+        assert!(html.contains("claude-opus-4-20250514"));
+        assert!(html.contains("100 input / 50 output / 10 cache tokens"));
+        assert!(html.contains("<code>cargo build</code>"));
+        assert!(html.contains("<span class=\"tok-kw\">fn</span> main() {}"));
+        assert!(
+            html.contains("<details class=\"tool\"><summary>Tool: Bash — cargo build</summary>")
+        );
+        assert!(html.contains("<details class=\"tool-result error\"><summary>Tool error</summary>"));
+        // The tool result is folded into the assistant turn
+        assert_eq!(html.matches("class=\"turn assistant\"").count(), 1);
+    }
+
+    #[test]
+    fn test_render_project_html_has_toc() {
+        let sessions = vec![conversation(), conversation()];
+        let html = render_project_html("my-project", &sessions, &HtmlExportOptions::default());
+
+        assert!(html.contains("<a href=\"#session-1\">Fix &lt;build&gt;</a>"));
+        assert!(html.contains("<a href=\"#session-2\">"));
+        assert!(html.contains("<section class=\"session\" id=\"session-2\">"));
+        assert_eq!(html.matches("<!DOCTYPE html>").count(), 1);
+    }
+
+    #[test]
+    fn test_render_text_inline_code_and_unclosed_fence() {
+        assert_eq!(render_text("a `b` c `d"), "<p>a <code>b</code> c `d</p>\n");
+        assert_eq!(
+            render_text("```\nx < y"),
+            "<pre><code>x &lt; y</code></pre>\n"
+        );
+    }
+}
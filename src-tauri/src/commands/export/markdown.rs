@@ -0,0 +1,273 @@
+//! Markdown transcript rendering
+//!
+//! Turns the messages from `load_session_messages` into a Markdown document:
+//! one heading per user/assistant turn, tool calls and results folded into
+//! `<details>` blocks and thinking included only on request.
+
+use super::transcript::{
+    message_turn, one_line, session_summary, tool_call_summary, tool_result_text, truncate_chars,
+    Turn, DEFAULT_MAX_TOOL_RESULT_CHARS,
+};
+use crate::models::ClaudeMessage;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Options for Markdown export
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MarkdownExportOptions {
+    pub include_thinking: bool,
+    /// Tool results longer than this are truncated; `0` disables the limit
+    pub max_tool_result_chars: usize,
+    pub include_sidechain: bool,
+    pub include_timestamps: bool,
+    /// Replace secrets and PII before rendering
+    pub redact: bool,
+}
+
+impl Default for MarkdownExportOptions {
+    fn default() -> Self {
+        Self {
+            include_thinking: false,
+            max_tool_result_chars: DEFAULT_MAX_TOOL_RESULT_CHARS,
+            include_sidechain: false,
+            include_timestamps: true,
+            redact: false,
+        }
+    }
+}
+
+/// A code fence that cannot be closed by backticks inside `content`
+fn fence_for(content: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in content.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn fenced(content: &str, language: &str) -> String {
+    let fence = fence_for(content);
+    let body = content.strip_suffix('\n').unwrap_or(content);
+    format!("{fence}{language}\n{body}\n{fence}\n")
+}
+
+/// Keep `<summary>` on a single line and free of markup
+fn summary_text(text: &str) -> String {
+    one_line(text, 120)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn details(summary: &str, body: &str) -> String {
+    format!("<details>\n<summary>{summary}</summary>\n\n{body}\n</details>\n")
+}
+
+fn render_block(block: &Value, options: &MarkdownExportOptions, out: &mut String) {
+    match block.get("type").and_then(Value::as_str) {
+        Some("text") => {
+            if let Some(text) = block.get("text").and_then(Value::as_str) {
+                let text = text.trim();
+                if !text.is_empty() {
+                    out.push_str(text);
+                    out.push_str("\n\n");
+                }
+            }
+        }
+        Some("thinking") if options.include_thinking => {
+            if let Some(thinking) = block.get("thinking").and_then(Value::as_str) {
+                let quoted: Vec<String> = thinking
+                    .trim()
+                    .lines()
+                    .map(|line| format!("> {line}").trim_end().to_string())
+                    .collect();
+                out.push_str(&details("Thinking", &quoted.join("\n")));
+                out.push('\n');
+            }
+        }
+        Some("tool_use") => {
+            let name = block.get("name").and_then(Value::as_str).unwrap_or("tool");
+            let input = block.get("input").cloned().unwrap_or(Value::Null);
+            let body = serde_json::to_string_pretty(&input).unwrap_or_default();
+            out.push_str(&details(
+                &summary_text(&tool_call_summary(name, &input)),
+                &fenced(&body, "json"),
+            ));
+            out.push('\n');
+        }
+        Some("tool_result") => {
+            let content = block.get("content").cloned().unwrap_or(Value::Null);
+            let text = truncate_chars(&tool_result_text(&content), options.max_tool_result_chars);
+            let is_error = block.get("is_error").and_then(Value::as_bool) == Some(true);
+            let summary = if is_error {
+                "Tool error"
+            } else {
+                "Tool result"
+            };
+            out.push_str(&details(summary, &fenced(&text, "")));
+            out.push('\n');
+        }
+        Some("image") => out.push_str("*[image]*\n\n"),
+        _ => {}
+    }
+}
+
+/// Render a full session transcript as Markdown
+pub fn render_session_markdown(
+    messages: &[ClaudeMessage],
+    options: &MarkdownExportOptions,
+) -> String {
+    let mut out = String::new();
+
+    // Prefer the session summary as the title
+    let summary = session_summary(messages);
+    if let Some(first) = messages.iter().find(|m| m.message_type != "summary") {
+        match summary {
+            Some(summary) => out.push_str(&format!("# {summary}\n\n")),
+            None => out.push_str(&format!("# Session {}\n\n", first.session_id)),
+        }
+        if options.include_timestamps {
+            out.push_str(&format!(
+                "*Session `{}` · started {}*\n\n",
+                first.session_id, first.timestamp
+            ));
+        }
+    }
+
+    let mut current_turn: Option<Turn> = None;
+
+    for message in messages {
+        let Some((turn, content)) = message_turn(message, options.include_sidechain) else {
+            continue;
+        };
+
+        let mut body = String::new();
+        match content {
+            Value::String(text) => {
+                let text = text.trim();
+                if !text.is_empty() {
+                    body.push_str(text);
+                    body.push_str("\n\n");
+                }
+            }
+            Value::Array(blocks) => {
+                for block in blocks {
+                    render_block(block, options, &mut body);
+                }
+            }
+            _ => {}
+        }
+        if body.is_empty() {
+            continue;
+        }
+
+        if current_turn != Some(turn) {
+            out.push_str(&format!("## {}\n\n", turn.label()));
+            if options.include_timestamps {
+                out.push_str(&format!("*{}*\n\n", message.timestamp));
+            }
+            current_turn = Some(turn);
+        }
+        out.push_str(&body);
+    }
+
+    let trimmed_len = out.trim_end().len();
+    out.truncate(trimmed_len);
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(message_type: &str, content: Value) -> ClaudeMessage {
+        serde_json::from_value(json!({
+            "uuid": "u",
+            "parentUuid": null,
+            "sessionId": "session-1",
+            "timestamp": "2025-06-26T10:00:00Z",
+            "type": message_type,
+            "content": content,
+            "toolUse": null,
+            "toolUseResult": null,
+            "isSidechain": false
+        }))
+        .unwrap()
+    }
+
+    fn conversation() -> Vec<ClaudeMessage> {
+        vec![
+            message("user", json!("Fix the build")),
+            message(
+                "assistant",
+                json!([
+                    {"type": "thinking", "thinking": "Check cargo first"},
+                    {"type": "text", "text": "Running:\n\n```sh\ncargo build\n```"},
+                    {"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "cargo build"}}
+                ]),
+            ),
+            message(
+                "user",
+                json!([{"type": "tool_result", "tool_use_id": "t1", "content": "error: ```oops```"}]),
+            ),
+            message("assistant", json!([{"type": "text", "text": "Fixed."}])),
+        ]
+    }
+
+    #[test]
+    fn test_render_turns_and_tool_details() {
+        let markdown = render_session_markdown(&conversation(), &MarkdownExportOptions::default());
+
+        assert!(markdown.starts_with("# Session session-1\n"));
+        assert!(markdown.contains("*Session `session-1` · started 2025-06-26T10:00:00Z*"));
+        assert_eq!(markdown.matches("## User").count(), 1);
+        // The tool result stays inside the assistant turn
+        assert_eq!(markdown.matches("## Assistant").count(), 1);
+        assert!(markdown.contains("```sh\ncargo build\n```"));
+        assert!(markdown.contains("<summary>Tool: Bash — cargo build</summary>"));
+        assert!(
+            markdown.contains("<summary>Tool result</summary>\n\n````\nerror: ```oops```\n````")
+        );
+        assert!(!markdown.contains("Check cargo first"));
+    }
+
+    #[test]
+    fn test_render_optional_thinking() {
+        let options = MarkdownExportOptions {
+            include_thinking: true,
+            include_timestamps: false,
+            ..Default::default()
+        };
+        let markdown = render_session_markdown(&conversation(), &options);
+        assert!(markdown.contains("<summary>Thinking</summary>\n\n> Check cargo first"));
+        assert!(!markdown.contains("2025-06-26T10:00:00Z"));
+    }
+
+    #[test]
+    fn test_render_uses_summary_as_title() {
+        let mut messages = vec![message("summary", json!("Fix cargo build"))];
+        messages.extend(conversation());
+        let markdown = render_session_markdown(&messages, &MarkdownExportOptions::default());
+        assert!(markdown.starts_with("# Fix cargo build\n"));
+    }
+
+    #[test]
+    fn test_truncate_tool_results() {
+        let options = MarkdownExportOptions {
+            max_tool_result_chars: 5,
+            ..Default::default()
+        };
+        let markdown = render_session_markdown(&conversation(), &options);
+        assert!(markdown.contains("error\n… [truncated"));
+    }
+}
@@ -0,0 +1,179 @@
+//! Session export commands
+//!
+//! Renders sessions into shareable documents. Files are written through
+//! `write_text_file`, so exports are limited to its allowed directories.
+//! - `transcript`: Turn grouping and tool summaries shared by the renderers
+//! - `markdown`: Markdown transcript rendering
+//! - `html`: Self-contained HTML rendering
+//! - `highlight`: Syntax highlighting for HTML code blocks
+//! - `redact`: Secret and PII redaction applied before rendering
+
+mod highlight;
+mod html;
+mod markdown;
+mod redact;
+mod transcript;
+
+pub use html::{render_project_html, render_session_html, HtmlExportOptions};
+pub use markdown::{render_session_markdown, MarkdownExportOptions};
+pub use redact::{
+    builtin_detector_names, RedactionFinding, RedactionReport, Redactor, SecretMatch,
+};
+
+use super::claude_settings::write_text_file;
+use super::metadata::read_user_settings;
+use super::session::{load_project_sessions, load_session_messages};
+use crate::models::ClaudeMessage;
+use serde::Serialize;
+use std::path::Path;
+
+/// Redact transcripts in place with one shared `Redactor`, so a secret gets
+/// the same placeholder in every session
+fn redact_transcripts(
+    redact: bool,
+    transcripts: &mut [Vec<ClaudeMessage>],
+) -> Result<RedactionReport, String> {
+    if !redact {
+        return Ok(RedactionReport::default());
+    }
+    let mut redactor = Redactor::new(&read_user_settings()?)?;
+    for message in transcripts.iter_mut().flatten() {
+        redactor.redact_message(message);
+    }
+    Ok(redactor.into_report())
+}
+
+/// Render a session as Markdown without writing it anywhere
+#[tauri::command]
+pub async fn render_session_as_markdown(
+    session_path: String,
+    options: Option<MarkdownExportOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let mut messages = load_session_messages(session_path).await?;
+    redact_transcripts(options.redact, std::slice::from_mut(&mut messages))?;
+    Ok(render_session_markdown(&messages, &options))
+}
+
+/// Export a session as a Markdown file
+#[tauri::command]
+pub async fn export_session_markdown(
+    session_path: String,
+    output_path: String,
+    options: Option<MarkdownExportOptions>,
+) -> Result<RedactionReport, String> {
+    let options = options.unwrap_or_default();
+    let mut messages = load_session_messages(session_path).await?;
+    let report = redact_transcripts(options.redact, std::slice::from_mut(&mut messages))?;
+    write_text_file(output_path, render_session_markdown(&messages, &options)).await?;
+    Ok(report)
+}
+
+/// Export a session as a single self-contained HTML file
+#[tauri::command]
+pub async fn export_session_html(
+    session_path: String,
+    output_path: String,
+    options: Option<HtmlExportOptions>,
+) -> Result<RedactionReport, String> {
+    let options = options.unwrap_or_default();
+    let mut messages = load_session_messages(session_path).await?;
+    let report = redact_transcripts(options.redact, std::slice::from_mut(&mut messages))?;
+    write_text_file(output_path, render_session_html(&messages, &options)).await?;
+    Ok(report)
+}
+
+/// Export every session of a project as one HTML file with a table of contents
+#[tauri::command]
+pub async fn export_project_html(
+    project_path: String,
+    output_path: String,
+    options: Option<HtmlExportOptions>,
+) -> Result<RedactionReport, String> {
+    let options = options.unwrap_or_default();
+    let mut sessions =
+        load_project_sessions(project_path.clone(), Some(!options.include_sidechain)).await?;
+    // Oldest first so the table of contents reads chronologically
+    sessions.sort_by(|a, b| a.first_message_time.cmp(&b.first_message_time));
+
+    let project_name = sessions.first().map_or_else(
+        || {
+            Path::new(&project_path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        },
+        |session| session.project_name.clone(),
+    );
+
+    let mut transcripts = Vec::with_capacity(sessions.len());
+    for session in sessions {
+        transcripts.push(load_session_messages(session.file_path).await?);
+    }
+
+    let report = redact_transcripts(options.redact, &mut transcripts)?;
+    let html = render_project_html(&project_name, &transcripts, &options);
+    write_text_file(output_path, html).await?;
+    Ok(report)
+}
+
+/// Text with secrets replaced, plus what was replaced
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactedText {
+    pub text: String,
+    pub report: RedactionReport,
+}
+
+/// Preview what redaction would replace in a session, without exporting it
+#[tauri::command]
+pub async fn preview_session_redactions(session_path: String) -> Result<RedactionReport, String> {
+    let mut messages = load_session_messages(session_path).await?;
+    redact_transcripts(true, std::slice::from_mut(&mut messages))
+}
+
+/// Redact a snippet before it is copied or shared
+#[tauri::command]
+pub async fn redact_text_for_sharing(text: String) -> Result<RedactedText, String> {
+    let mut redactor = Redactor::new(&read_user_settings()?)?;
+    let text = redactor.redact_text(&text);
+    Ok(RedactedText {
+        text,
+        report: redactor.into_report(),
+    })
+}
+
+/// Names of the built-in redaction detectors, for the settings UI
+#[tauri::command]
+pub async fn get_redaction_detectors() -> Result<Vec<String>, String> {
+    Ok(builtin_detector_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_export_session_markdown_rejects_disallowed_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_path = temp_dir.path().join("session.jsonl");
+        std::fs::write(
+            &session_path,
+            r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","message":{"role":"user","content":"Hello"}}"#,
+        )
+        .unwrap();
+        let session_path = session_path.to_string_lossy().to_string();
+
+        let markdown = render_session_as_markdown(session_path.clone(), None)
+            .await
+            .unwrap();
+        assert!(markdown.contains("## User\n\n*2025-06-26T10:00:00Z*\n\nHello"));
+
+        let output = temp_dir.path().join("out.md").to_string_lossy().to_string();
+        let result = export_session_markdown(session_path, output, None).await;
+        assert!(result.unwrap_err().contains("allowed directories"));
+    }
+}
@@ -0,0 +1,153 @@
+//! Transcript helpers shared by the export renderers
+
+use crate::models::ClaudeMessage;
+use serde_json::Value;
+
+/// Default cap for a single tool result, in characters
+pub const DEFAULT_MAX_TOOL_RESULT_CHARS: usize = 4000;
+
+/// Role a message is rendered under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Turn {
+    User,
+    Assistant,
+}
+
+impl Turn {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::User => "User",
+            Self::Assistant => "Assistant",
+        }
+    }
+}
+
+/// Whether a user message only carries tool results
+fn is_tool_result_only(content: &Value) -> bool {
+    content.as_array().is_some_and(|blocks| {
+        !blocks.is_empty()
+            && blocks
+                .iter()
+                .all(|b| b.get("type").and_then(Value::as_str) == Some("tool_result"))
+    })
+}
+
+/// The turn a message belongs to and its content, or None if it isn't rendered
+///
+/// Tool results belong to the assistant turn that requested them.
+pub(super) fn message_turn(
+    message: &ClaudeMessage,
+    include_sidechain: bool,
+) -> Option<(Turn, &Value)> {
+    if message.is_sidechain == Some(true) && !include_sidechain {
+        return None;
+    }
+    let content = message.content.as_ref()?;
+    let turn = match message.message_type.as_str() {
+        "user" if is_tool_result_only(content) => Turn::Assistant,
+        "user" => Turn::User,
+        "assistant" => Turn::Assistant,
+        _ => return None,
+    };
+    Some((turn, content))
+}
+
+/// The session summary, if the transcript has one
+pub(super) fn session_summary(messages: &[ClaudeMessage]) -> Option<&str> {
+    messages
+        .iter()
+        .filter(|m| m.message_type == "summary")
+        .find_map(|m| m.content.as_ref().and_then(Value::as_str))
+        .map(str::trim)
+}
+
+/// Cut `text` to `max_chars` characters, noting how much was dropped
+///
+/// A limit of `0` disables truncation.
+pub(super) fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if max_chars == 0 {
+        return text.to_string();
+    }
+    match text.char_indices().nth(max_chars) {
+        Some((byte_idx, _)) => {
+            let dropped = text[byte_idx..].chars().count();
+            format!("{}\n… [truncated {dropped} characters]", &text[..byte_idx])
+        }
+        None => text.to_string(),
+    }
+}
+
+/// First line of `text`, shortened to `max_chars` characters
+pub(super) fn one_line(text: &str, max_chars: usize) -> String {
+    let first_line = text.lines().next().unwrap_or_default();
+    let mut line: String = first_line.chars().take(max_chars).collect();
+    if line.len() < first_line.len() {
+        line.push('…');
+    }
+    line
+}
+
+/// Short description of a tool call, e.g. `Tool: Bash — cargo build`
+pub(super) fn tool_call_summary(name: &str, input: &Value) -> String {
+    let hint = [
+        "command",
+        "file_path",
+        "notebook_path",
+        "pattern",
+        "url",
+        "description",
+    ]
+    .iter()
+    .find_map(|key| input.get(*key).and_then(Value::as_str));
+    match hint {
+        Some(hint) => format!("Tool: {name} — {}", one_line(hint, 80)),
+        None => format!("Tool: {name}"),
+    }
+}
+
+/// Plain text of a `tool_result` content field (string or block array)
+pub(super) fn tool_result_text(content: &Value) -> String {
+    match content {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item.get("type").and_then(Value::as_str) {
+                Some("text") => item.get("text").and_then(Value::as_str).map(str::to_string),
+                Some("image") => Some("[image]".to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(
+            truncate_chars("héllo world", 5),
+            "héllo\n… [truncated 6 characters]"
+        );
+        assert_eq!(truncate_chars("short", 10), "short");
+        assert_eq!(truncate_chars("unlimited", 0), "unlimited");
+    }
+
+    #[test]
+    fn test_tool_call_summary() {
+        let input = json!({"command": "cargo build\ncargo test"});
+        assert_eq!(
+            tool_call_summary("Bash", &input),
+            "Tool: Bash — cargo build"
+        );
+        assert_eq!(
+            tool_call_summary("TodoWrite", &json!({})),
+            "Tool: TodoWrite"
+        );
+    }
+}
@@ -0,0 +1,613 @@
+//! Git repository access
+//!
+//! Reads history, changed files, `HEAD` and worktrees through libgit2, so
+//! nothing depends on a `git` binary being on the app's `PATH`.
+
+use crate::models::{GitChangeStatus, GitCommit, GitFileChange, GitHead, GitLogPage, GitWorktree};
+use chrono::{DateTime, Utc};
+use git2::{Commit, Delta, DiffFindOptions, Oid, Repository, Sort};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Which commits a log walk yields
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LogQuery {
+    pub offset: usize,
+    /// No limit when `None`
+    pub limit: Option<usize>,
+    /// Inclusive bounds on author time, in Unix seconds
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// Walk every ref like `git log --all` instead of only `HEAD`
+    pub all_refs: bool,
+}
+
+/// Validate a project directory passed in from the frontend
+pub(crate) fn validate_repo_path(path: &str) -> Result<PathBuf, String> {
+    let path_buf = PathBuf::from(path);
+    if !path_buf.is_absolute() {
+        return Err("Path must be absolute".to_string());
+    }
+    if !path_buf.is_dir() {
+        return Err("Path does not exist or is not a directory".to_string());
+    }
+    path_buf
+        .canonicalize()
+        .map_err(|e| format!("Invalid path: {e}"))
+}
+
+/// Repository containing `path`, searching parent directories like `git` does
+pub(crate) fn open_repository(path: &Path) -> Result<Repository, String> {
+    Repository::discover(path).map_err(|e| format!("Not a git repository: {}", e.message()))
+}
+
+fn rfc3339(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn parse_bound(value: Option<&str>, name: &str) -> Result<Option<i64>, String> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|dt| dt.timestamp())
+                .map_err(|e| format!("Invalid {name} '{value}': {e}"))
+        })
+        .transpose()
+}
+
+pub(crate) fn to_git_commit(commit: &Commit) -> GitCommit {
+    let author = commit.author();
+    let timestamp = author.when().seconds();
+    GitCommit {
+        hash: commit.id().to_string(),
+        author: String::from_utf8_lossy(author.name_bytes()).to_string(),
+        date: rfc3339(timestamp),
+        message: commit.summary().unwrap_or_default().to_string(),
+        timestamp,
+    }
+}
+
+/// Commits matching `query`, newest first
+pub(crate) fn log_commits<'r>(
+    repo: &'r Repository,
+    query: &LogQuery,
+) -> Result<Vec<Commit<'r>>, String> {
+    let mut walk = repo.revwalk().map_err(|e| e.message().to_string())?;
+    walk.set_sorting(Sort::TIME)
+        .map_err(|e| e.message().to_string())?;
+    // An unborn HEAD simply has no history
+    if walk.push_head().is_err() && !query.all_refs {
+        return Ok(Vec::new());
+    }
+    if query.all_refs {
+        walk.push_glob("refs/*")
+            .map_err(|e| e.message().to_string())?;
+    }
+
+    let mut commits = Vec::new();
+    let mut skipped = 0;
+    for oid in walk {
+        if query.limit.is_some_and(|limit| commits.len() >= limit) {
+            break;
+        }
+        let commit = oid
+            .and_then(|oid| repo.find_commit(oid))
+            .map_err(|e| e.message().to_string())?;
+        // Walked by commit time, so nothing older can follow
+        if query
+            .since
+            .is_some_and(|since| commit.time().seconds() < since)
+        {
+            break;
+        }
+        let authored = commit.author().when().seconds();
+        if query.since.is_some_and(|since| authored < since)
+            || query.until.is_some_and(|until| authored > until)
+        {
+            continue;
+        }
+        if skipped < query.offset {
+            skipped += 1;
+            continue;
+        }
+        commits.push(commit);
+    }
+    Ok(commits)
+}
+
+fn change_status(delta: Delta) -> GitChangeStatus {
+    match delta {
+        Delta::Added | Delta::Untracked => GitChangeStatus::Added,
+        Delta::Modified => GitChangeStatus::Modified,
+        Delta::Deleted => GitChangeStatus::Deleted,
+        Delta::Renamed => GitChangeStatus::Renamed,
+        Delta::Copied => GitChangeStatus::Copied,
+        Delta::Typechange => GitChangeStatus::TypeChange,
+        _ => GitChangeStatus::Other,
+    }
+}
+
+fn path_string(path: Option<&Path>) -> Option<String> {
+    path.map(|path| {
+        path.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    })
+}
+
+/// Files `commit` changed relative to its first parent
+///
+/// With `detailed`, renames are detected and line counts filled in; without
+/// it only paths and statuses are read, which is much cheaper.
+pub(crate) fn changed_files(
+    repo: &Repository,
+    commit: &Commit,
+    detailed: bool,
+) -> Result<Vec<GitFileChange>, String> {
+    let tree = commit.tree().map_err(|e| e.message().to_string())?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().map_err(|e| e.message().to_string())?),
+        Err(_) => None,
+    };
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+        .map_err(|e| e.message().to_string())?;
+    if detailed {
+        diff.find_similar(Some(DiffFindOptions::new().renames(true)))
+            .map_err(|e| e.message().to_string())?;
+    }
+
+    let mut files = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let status = change_status(delta.status());
+        let new_path = path_string(delta.new_file().path());
+        let old_path = path_string(delta.old_file().path());
+        let Some(path) = new_path.clone().or_else(|| old_path.clone()) else {
+            continue;
+        };
+        let mut change = GitFileChange {
+            path,
+            old_path: matches!(status, GitChangeStatus::Renamed | GitChangeStatus::Copied)
+                .then_some(old_path)
+                .flatten(),
+            status,
+            additions: 0,
+            deletions: 0,
+            binary: delta.flags().is_binary(),
+        };
+        if detailed {
+            if let Some(patch) =
+                git2::Patch::from_diff(&diff, idx).map_err(|e| e.message().to_string())?
+            {
+                let (_, additions, deletions) =
+                    patch.line_stats().map_err(|e| e.message().to_string())?;
+                change.additions = additions;
+                change.deletions = deletions;
+                change.binary = patch.delta().flags().is_binary();
+            }
+        }
+        files.push(change);
+    }
+    Ok(files)
+}
+
+/// Branch and commit `HEAD` points at
+pub(crate) fn head_info(repo: &Repository) -> GitHead {
+    let detached = repo.head_detached().unwrap_or(false);
+    let commit = repo
+        .head()
+        .ok()
+        .and_then(|head| head.target())
+        .map(|oid| oid.to_string());
+    // Read the symbolic ref directly so a branch with no commits still has a name
+    let branch = repo
+        .find_reference("HEAD")
+        .ok()
+        .and_then(|head| head.symbolic_target().map(str::to_string))
+        .and_then(|target| target.strip_prefix("refs/heads/").map(str::to_string))
+        .filter(|_| !detached);
+    GitHead {
+        branch,
+        commit,
+        detached,
+    }
+}
+
+/// `HEAD` of a linked worktree as the main repository recorded it
+///
+/// Used when the worktree's directory is gone and it can no longer be opened.
+fn recorded_head(main: &Repository, name: &str) -> GitHead {
+    let Ok(content) = fs::read_to_string(main.path().join("worktrees").join(name).join("HEAD"))
+    else {
+        return GitHead::default();
+    };
+    let content = content.trim();
+    match content.strip_prefix("ref: ") {
+        Some(target) => GitHead {
+            branch: target.strip_prefix("refs/heads/").map(str::to_string),
+            commit: main.refname_to_id(target).ok().map(|oid| oid.to_string()),
+            detached: false,
+        },
+        None => GitHead {
+            branch: None,
+            commit: Some(content.to_string()).filter(|hash| !hash.is_empty()),
+            detached: true,
+        },
+    }
+}
+
+/// The main checkout followed by every linked worktree, sorted by name
+pub(crate) fn list_worktrees(repo: &Repository) -> Result<Vec<GitWorktree>, String> {
+    // A linked worktree sees the others only through the main repository
+    let main = if repo.is_worktree() {
+        Repository::open(repo.commondir()).map_err(|e| e.message().to_string())?
+    } else {
+        Repository::open(repo.path()).map_err(|e| e.message().to_string())?
+    };
+
+    let mut worktrees = Vec::new();
+    if let Some(workdir) = main.workdir() {
+        worktrees.push(GitWorktree {
+            name: None,
+            path: workdir.to_string_lossy().trim_end_matches('/').to_string(),
+            is_main: true,
+            head: head_info(&main),
+            locked: false,
+            prunable: false,
+        });
+    }
+
+    let names = main.worktrees().map_err(|e| e.message().to_string())?;
+    let mut names: Vec<&str> = names.iter().flatten().collect();
+    names.sort_unstable();
+    for name in names {
+        let Ok(worktree) = main.find_worktree(name) else {
+            continue;
+        };
+        let head = Repository::open_from_worktree(&worktree)
+            .map(|repo| head_info(&repo))
+            .unwrap_or_else(|_| recorded_head(&main, name));
+        worktrees.push(GitWorktree {
+            name: Some(name.to_string()),
+            path: worktree
+                .path()
+                .to_string_lossy()
+                .trim_end_matches('/')
+                .to_string(),
+            is_main: false,
+            head,
+            locked: !matches!(worktree.is_locked(), Ok(git2::WorktreeLockStatus::Unlocked)),
+            prunable: worktree.is_prunable(None).unwrap_or(false),
+        });
+    }
+    Ok(worktrees)
+}
+
+/// The oldest root commit reachable from `HEAD`
+pub(crate) fn root_commit(repo: &Repository) -> Option<String> {
+    let mut walk = repo.revwalk().ok()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME | Sort::REVERSE)
+        .ok()?;
+    walk.push_head().ok()?;
+    let first = walk.next()?.ok()?;
+    Some(first.to_string())
+}
+
+/// URL of the `origin` remote, or of the first remote with a URL
+pub(crate) fn remote_url(repo: &Repository) -> Option<String> {
+    if let Some(url) = repo
+        .find_remote("origin")
+        .ok()
+        .and_then(|remote| remote.url().map(str::to_string))
+    {
+        return Some(url);
+    }
+    let names = repo.remotes().ok()?;
+    let url = names
+        .iter()
+        .flatten()
+        .find_map(|name| repo.find_remote(name).ok()?.url().map(str::to_string));
+    url
+}
+
+/// A page of commits reachable from `HEAD` (or every ref), newest first
+///
+/// `since` and `until` are RFC 3339 bounds on the author date. Returns an
+/// empty page when the directory is not inside a git repository.
+#[tauri::command]
+pub async fn get_git_log_page(
+    actual_path: String,
+    offset: usize,
+    limit: usize,
+    since: Option<String>,
+    until: Option<String>,
+    all_branches: Option<bool>,
+) -> Result<GitLogPage, String> {
+    if limit == 0 {
+        return Err("limit must be greater than zero".to_string());
+    }
+    let path = validate_repo_path(&actual_path)?;
+    let query = LogQuery {
+        offset,
+        // One extra to tell whether another page exists
+        limit: Some(limit.saturating_add(1)),
+        since: parse_bound(since.as_deref(), "since")?,
+        until: parse_bound(until.as_deref(), "until")?,
+        all_refs: all_branches.unwrap_or(false),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let Ok(repo) = open_repository(&path) else {
+            return Ok(GitLogPage {
+                commits: Vec::new(),
+                offset,
+                has_more: false,
+            });
+        };
+        let mut commits: Vec<GitCommit> = log_commits(&repo, &query)?
+            .iter()
+            .map(to_git_commit)
+            .collect();
+        let has_more = commits.len() > limit;
+        commits.truncate(limit);
+        Ok(GitLogPage {
+            commits,
+            offset,
+            has_more,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Files changed by one commit, with line counts and rename detection
+#[tauri::command]
+pub async fn get_git_commit_files(
+    actual_path: String,
+    commit_hash: String,
+) -> Result<Vec<GitFileChange>, String> {
+    let path = validate_repo_path(&actual_path)?;
+    let oid = Oid::from_str(&commit_hash).map_err(|e| format!("Invalid commit hash: {e}"))?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let repo = open_repository(&path)?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("Commit not found: {}", e.message()))?;
+        changed_files(&repo, &commit, true)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Current branch and `HEAD` commit of the repository containing `actual_path`
+#[tauri::command]
+pub async fn get_git_head(actual_path: String) -> Result<GitHead, String> {
+    let path = validate_repo_path(&actual_path)?;
+    tauri::async_runtime::spawn_blocking(move || Ok(head_info(&open_repository(&path)?)))
+        .await
+        .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Every worktree of the repository containing `actual_path`
+#[tauri::command]
+pub async fn get_git_worktrees(actual_path: String) -> Result<Vec<GitWorktree>, String> {
+    let path = validate_repo_path(&actual_path)?;
+    tauri::async_runtime::spawn_blocking(move || list_worktrees(&open_repository(&path)?))
+        .await
+        .map_err(|e| format!("Task join error: {e}"))?
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use git2::{Signature, Time};
+    use tempfile::TempDir;
+
+    /// Commit `files` (path, content) on `HEAD` with author and committer time `at`
+    pub(crate) fn commit_files(
+        repo: &Repository,
+        at: i64,
+        message: &str,
+        files: &[(&str, &str)],
+    ) -> Oid {
+        let workdir = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            let full = workdir.join(path);
+            fs::create_dir_all(full.parent().unwrap()).unwrap();
+            fs::write(&full, content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::new("Test User", "test@example.com", &Time::new(at, 0)).unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    fn sample_repo(temp: &TempDir) -> Repository {
+        let repo = Repository::init(temp.path().join("repo")).unwrap();
+        commit_files(
+            &repo,
+            1_700_000_000,
+            "Initial commit",
+            &[("README.md", "hello\n")],
+        );
+        commit_files(
+            &repo,
+            1_700_000_100,
+            "Add parser | with a pipe",
+            &[
+                ("src/parser.rs", "fn parse() {}\n"),
+                ("README.md", "hello\nworld\n"),
+            ],
+        );
+        commit_files(
+            &repo,
+            1_700_000_200,
+            "Tweak parser",
+            &[("src/parser.rs", "fn parse() {}\nfn more() {}\n")],
+        );
+        repo
+    }
+
+    #[test]
+    fn test_log_pagination_and_date_range() {
+        let temp = TempDir::new().unwrap();
+        let repo = sample_repo(&temp);
+
+        let all = log_commits(&repo, &LogQuery::default()).unwrap();
+        let messages: Vec<String> = all.iter().map(|c| to_git_commit(c).message).collect();
+        assert_eq!(
+            messages,
+            vec!["Tweak parser", "Add parser | with a pipe", "Initial commit"]
+        );
+        assert_eq!(to_git_commit(&all[0]).author, "Test User");
+        assert_eq!(to_git_commit(&all[0]).timestamp, 1_700_000_200);
+
+        let page = LogQuery {
+            offset: 1,
+            limit: Some(1),
+            ..LogQuery::default()
+        };
+        let page = log_commits(&repo, &page).unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].summary(), Some("Add parser | with a pipe"));
+
+        let range = LogQuery {
+            since: Some(1_700_000_050),
+            until: Some(1_700_000_150),
+            ..LogQuery::default()
+        };
+        let range = log_commits(&repo, &range).unwrap();
+        assert_eq!(range.len(), 1);
+        assert_eq!(range[0].summary(), Some("Add parser | with a pipe"));
+
+        let root = all.last().unwrap().id().to_string();
+        assert_eq!(root_commit(&repo), Some(root));
+    }
+
+    #[tokio::test]
+    async fn test_get_git_log_page_limits() {
+        let temp = TempDir::new().unwrap();
+        sample_repo(&temp);
+        let path = temp.path().join("repo").to_string_lossy().to_string();
+
+        let page = get_git_log_page(path.clone(), 0, 2, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.commits.len(), 2);
+        assert!(page.has_more);
+
+        let page = get_git_log_page(path.clone(), 0, usize::MAX, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.commits.len(), 3);
+        assert!(!page.has_more);
+
+        assert!(get_git_log_page(path, 0, 0, None, None, None)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_changed_files_with_stats() {
+        let temp = TempDir::new().unwrap();
+        let repo = sample_repo(&temp);
+        let commits = log_commits(&repo, &LogQuery::default()).unwrap();
+
+        let mut files = changed_files(&repo, &commits[1], true).unwrap();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "README.md");
+        assert_eq!(files[0].status, GitChangeStatus::Modified);
+        assert_eq!((files[0].additions, files[0].deletions), (1, 0));
+        assert_eq!(files[1].path, "src/parser.rs");
+        assert_eq!(files[1].status, GitChangeStatus::Added);
+
+        // The root commit is compared with an empty tree
+        let root = changed_files(&repo, &commits[2], false).unwrap();
+        assert_eq!(root.len(), 1);
+        assert_eq!(root[0].status, GitChangeStatus::Added);
+    }
+
+    #[test]
+    fn test_head_worktrees_and_remote() {
+        let temp = TempDir::new().unwrap();
+        let repo = sample_repo(&temp);
+        let head = head_info(&repo);
+        assert!(!head.detached);
+        assert!(head.branch.is_some());
+        assert_eq!(
+            head.commit,
+            repo.head().unwrap().target().map(|oid| oid.to_string())
+        );
+
+        assert_eq!(remote_url(&repo), None);
+        repo.remote("upstream", "https://example.com/me/tool.git")
+            .unwrap();
+        repo.remote("origin", "git@github.com:me/tool.git").unwrap();
+        assert_eq!(
+            remote_url(&repo).as_deref(),
+            Some("git@github.com:me/tool.git")
+        );
+
+        let feature_path = temp.path().join("feature");
+        repo.worktree("feature", &feature_path, None).unwrap();
+        let linked = Repository::open(&feature_path).unwrap();
+        assert_eq!(head_info(&linked).branch.as_deref(), Some("feature"));
+        commit_files(
+            &linked,
+            1_700_000_300,
+            "Feature work",
+            &[("feature.txt", "x\n")],
+        );
+        assert_eq!(log_commits(&repo, &LogQuery::default()).unwrap().len(), 3);
+        let all_refs = LogQuery {
+            all_refs: true,
+            ..LogQuery::default()
+        };
+        let all = log_commits(&repo, &all_refs).unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].summary(), Some("Feature work"));
+
+        // Listing from either checkout sees both
+        for from in [&repo, &linked] {
+            let worktrees = list_worktrees(from).unwrap();
+            assert_eq!(worktrees.len(), 2);
+            assert!(worktrees[0].is_main);
+            assert_eq!(worktrees[1].name.as_deref(), Some("feature"));
+            assert!(!worktrees[1].prunable);
+            assert_eq!(
+                fs::canonicalize(&worktrees[1].path).unwrap(),
+                fs::canonicalize(&feature_path).unwrap()
+            );
+        }
+
+        // A deleted worktree keeps its branch
+        fs::remove_dir_all(&feature_path).unwrap();
+        let worktrees = list_worktrees(&repo).unwrap();
+        assert!(worktrees[1].prunable);
+        assert_eq!(worktrees[1].head.branch.as_deref(), Some("feature"));
+        assert!(worktrees[1].head.commit.is_some());
+
+        let empty = Repository::init(temp.path().join("empty")).unwrap();
+        let head = head_info(&empty);
+        assert!(head.branch.is_some());
+        assert_eq!(head.commit, None);
+        assert!(log_commits(&empty, &LogQuery::default())
+            .unwrap()
+            .is_empty());
+    }
+}
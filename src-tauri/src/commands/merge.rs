@@ -0,0 +1,545 @@
+//! Moved and renamed projects
+//!
+//! Moving or renaming a repository makes Claude Code start a new project
+//! folder, splitting its history in two. This module finds folders that
+//! belong together and serves sessions, stats and edits across them as one
+//! virtual project, without touching the files.
+
+use super::git::{open_repository, remote_url, root_commit};
+use super::project::scan_projects;
+use super::session::{
+    collect_recent_edits, load_project_sessions, PaginatedRecentEdits, SessionScanCache,
+};
+use super::stats::summarize_project_dirs;
+use crate::models::{
+    ClaudeProject, ClaudeSession, GitWorktreeType, MergeSignal, ProjectMergeSuggestion,
+    ProjectStatsSummary, RecentEditsFilter, SessionContextFilter,
+};
+use rayon::prelude::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What a project folder reveals about the project it belongs to
+#[derive(Debug, Default)]
+struct ProjectIdentity {
+    path: String,
+    actual_path: String,
+    last_modified: String,
+    exists: bool,
+    remote: Option<String>,
+    root_commit: Option<String>,
+    cwds: HashSet<String>,
+    session_ids: HashSet<String>,
+}
+
+/// Reduce a remote URL to `host/owner/repo` so SSH and HTTPS forms compare equal
+fn normalize_remote(url: &str) -> String {
+    let url = url.trim();
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let without_user = without_scheme
+        .split_once('@')
+        .map_or(without_scheme, |(_, rest)| rest);
+    // scp-like `host:owner/repo`
+    let (host, path) = match without_user.split_once(['/', ':']) {
+        Some((host, path)) => (host, path),
+        None => (without_user, ""),
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    format!("{}/{path}", host.to_lowercase())
+}
+
+fn identify(project: &ClaudeProject) -> ProjectIdentity {
+    let actual_path = Path::new(&project.actual_path);
+    let is_main_checkout = project
+        .git_info
+        .as_ref()
+        .is_some_and(|info| info.worktree_type == GitWorktreeType::Main);
+
+    let sessions: Vec<PathBuf> = fs::read_dir(&project.path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+                .collect()
+        })
+        .unwrap_or_default();
+    let cache = SessionScanCache::load(Path::new(&project.path));
+    let cwds = sessions
+        .par_iter()
+        .filter_map(|path| cache.scan(path))
+        .flat_map_iter(|scan| scan.cwd_counts.into_keys())
+        .collect();
+    cache.save();
+    let session_ids = sessions
+        .iter()
+        .filter_map(|path| path.file_stem().and_then(|s| s.to_str()))
+        .map(str::to_string)
+        .collect();
+
+    let repo = is_main_checkout
+        .then(|| open_repository(actual_path).ok())
+        .flatten();
+
+    ProjectIdentity {
+        path: project.path.clone(),
+        actual_path: project.actual_path.clone(),
+        last_modified: project.last_modified.clone(),
+        exists: actual_path.is_dir(),
+        remote: repo
+            .as_ref()
+            .and_then(remote_url)
+            .map(|url| normalize_remote(&url)),
+        root_commit: repo.as_ref().and_then(root_commit),
+        cwds,
+        session_ids,
+    }
+}
+
+/// A project and a subfolder of it are separate projects of one repository
+fn is_nested(a: &ProjectIdentity, b: &ProjectIdentity) -> bool {
+    let (a, b) = (Path::new(&a.actual_path), Path::new(&b.actual_path));
+    a.starts_with(b) || b.starts_with(a)
+}
+
+fn folder_name(path: &str) -> Option<&str> {
+    Path::new(path).file_name().and_then(|n| n.to_str())
+}
+
+/// Subdirectories of the project that sessions ran in, relative to it
+fn relative_cwds(identity: &ProjectIdentity) -> HashSet<&Path> {
+    identity
+        .cwds
+        .iter()
+        .filter_map(|cwd| Path::new(cwd).strip_prefix(&identity.actual_path).ok())
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .collect()
+}
+
+/// Signals that only say two checkouts are of one repository
+fn is_repository_signal(signal: &MergeSignal) -> bool {
+    matches!(
+        signal,
+        MergeSignal::GitRemote { .. } | MergeSignal::GitRootCommit { .. }
+    )
+}
+
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[index] = root;
+    root
+}
+
+/// Group identities that share a signal, returning each group's members and signals
+fn group_identities(identities: &[ProjectIdentity]) -> Vec<(Vec<usize>, BTreeSet<MergeSignal>)> {
+    let mut links: Vec<(usize, usize, MergeSignal)> = Vec::new();
+    let mut link_shared = |key_of: &dyn Fn(&ProjectIdentity) -> Vec<String>,
+                           signal: &dyn Fn(String) -> MergeSignal| {
+        let mut by_key: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, identity) in identities.iter().enumerate() {
+            for key in key_of(identity) {
+                by_key.entry(key).or_default().push(index);
+            }
+        }
+        for (key, indices) in by_key {
+            for (n, &a) in indices.iter().enumerate() {
+                for &b in &indices[n + 1..] {
+                    if !is_nested(&identities[a], &identities[b]) {
+                        links.push((a, b, signal(key.clone())));
+                    }
+                }
+            }
+        }
+    };
+
+    link_shared(&|i| i.remote.iter().cloned().collect(), &|url| {
+        MergeSignal::GitRemote { url }
+    });
+    link_shared(&|i| i.root_commit.iter().cloned().collect(), &|commit| {
+        MergeSignal::GitRootCommit { commit }
+    });
+    link_shared(
+        &|i| i.session_ids.iter().cloned().collect(),
+        &|session_id| MergeSignal::SharedSession { session_id },
+    );
+
+    // Sessions of one project that ran in another project's directory
+    let by_actual_path: HashMap<&str, usize> = identities
+        .iter()
+        .enumerate()
+        .map(|(index, identity)| (identity.actual_path.as_str(), index))
+        .collect();
+    for (a, identity) in identities.iter().enumerate() {
+        for cwd in &identity.cwds {
+            if let Some(&b) = by_actual_path.get(cwd.as_str()) {
+                if a != b && !is_nested(identity, &identities[b]) {
+                    links.push((a, b, MergeSignal::CwdLineage { cwd: cwd.clone() }));
+                }
+            }
+        }
+    }
+
+    // A vanished directory whose name now exists exactly once elsewhere. Names
+    // like `app` or `web` are too common to go on, so the two must also share
+    // another signal or have had sessions in the same subdirectory.
+    let mut existing_by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, identity) in identities.iter().enumerate() {
+        if identity.exists {
+            if let Some(name) = folder_name(&identity.actual_path) {
+                existing_by_name.entry(name).or_default().push(index);
+            }
+        }
+    }
+    for (a, identity) in identities.iter().enumerate() {
+        let Some(name) = folder_name(&identity.actual_path).filter(|_| !identity.exists) else {
+            continue;
+        };
+        let Some(&[b]) = existing_by_name.get(name).map(Vec::as_slice) else {
+            continue;
+        };
+        let linked = links
+            .iter()
+            .any(|(x, y, _)| (*x, *y) == (a, b) || (*x, *y) == (b, a));
+        let same_layout = !relative_cwds(identity).is_disjoint(&relative_cwds(&identities[b]));
+        if linked || same_layout {
+            links.push((
+                a,
+                b,
+                MergeSignal::MovedFolder {
+                    folder_name: name.to_string(),
+                },
+            ));
+        }
+    }
+
+    // Forks share a root commit and side-by-side clones share a remote too, so
+    // those only count for a vanished directory or alongside another signal
+    let pair = |a: usize, b: usize| (a.min(b), a.max(b));
+    let corroborated: HashSet<(usize, usize)> = links
+        .iter()
+        .filter(|(_, _, signal)| !is_repository_signal(signal))
+        .map(|&(a, b, _)| pair(a, b))
+        .collect();
+    links.retain(|&(a, b, ref signal)| {
+        !is_repository_signal(signal)
+            || !identities[a].exists
+            || !identities[b].exists
+            || corroborated.contains(&pair(a, b))
+    });
+
+    let mut parents: Vec<usize> = (0..identities.len()).collect();
+    for (a, b, _) in &links {
+        let (root_a, root_b) = (find(&mut parents, *a), find(&mut parents, *b));
+        parents[root_a] = root_b;
+    }
+
+    let mut groups: HashMap<usize, (Vec<usize>, BTreeSet<MergeSignal>)> = HashMap::new();
+    for index in 0..identities.len() {
+        let root = find(&mut parents, index);
+        groups.entry(root).or_default().0.push(index);
+    }
+    for (a, _, signal) in links {
+        let root = find(&mut parents, a);
+        if let Some(group) = groups.get_mut(&root) {
+            group.1.insert(signal);
+        }
+    }
+    groups
+        .into_values()
+        .filter(|(members, _)| members.len() > 1)
+        .collect()
+}
+
+fn suggest_merges(projects: &[ClaudeProject]) -> Vec<ProjectMergeSuggestion> {
+    let identities: Vec<ProjectIdentity> = projects.par_iter().map(identify).collect();
+
+    let mut suggestions: Vec<ProjectMergeSuggestion> = group_identities(&identities)
+        .into_iter()
+        .map(|(mut members, signals)| {
+            members.sort_by(|&a, &b| {
+                identities[b]
+                    .last_modified
+                    .cmp(&identities[a].last_modified)
+                    .then_with(|| identities[a].path.cmp(&identities[b].path))
+            });
+            ProjectMergeSuggestion {
+                primary_path: identities[members[0]].path.clone(),
+                project_paths: members
+                    .iter()
+                    .map(|&i| identities[i].path.clone())
+                    .collect(),
+                actual_paths: members
+                    .iter()
+                    .map(|&i| identities[i].actual_path.clone())
+                    .collect(),
+                signals: signals.into_iter().collect(),
+            }
+        })
+        .collect();
+    suggestions.sort_by(|a, b| a.primary_path.cmp(&b.primary_path));
+    suggestions
+}
+
+fn validate_project_paths(project_paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    if project_paths.is_empty() {
+        return Err("project_paths is required".to_string());
+    }
+    project_paths
+        .iter()
+        .map(|path| {
+            let path = PathBuf::from(path);
+            if path.is_absolute() {
+                Ok(path)
+            } else {
+                Err("project_paths must be absolute".to_string())
+            }
+        })
+        .collect()
+}
+
+/// Find project folders that are likely one project split by a move or rename
+#[tauri::command]
+pub async fn detect_moved_projects(
+    claude_path: String,
+) -> Result<Vec<ProjectMergeSuggestion>, String> {
+    let projects = scan_projects(claude_path, None).await?;
+    tauri::async_runtime::spawn_blocking(move || suggest_merges(&projects))
+        .await
+        .map_err(|e| format!("Task join error: {e}"))
+}
+
+/// Sessions of several project folders as one list, newest first
+#[tauri::command]
+pub async fn load_merged_project_sessions(
+    project_paths: Vec<String>,
+    exclude_sidechain: Option<bool>,
+) -> Result<Vec<ClaudeSession>, String> {
+    validate_project_paths(&project_paths)?;
+    let mut sessions = Vec::new();
+    for project_path in project_paths {
+        sessions.extend(load_project_sessions(project_path, exclude_sidechain).await?);
+    }
+    sessions.sort_by(|a, b| b.last_message_time.cmp(&a.last_message_time));
+    Ok(sessions)
+}
+
+/// Stats summary over several project folders; named after the first one
+#[tauri::command]
+pub async fn get_merged_project_stats_summary(
+    project_paths: Vec<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    filter: Option<SessionContextFilter>,
+) -> Result<ProjectStatsSummary, String> {
+    let dirs = validate_project_paths(&project_paths)?;
+    let filter = filter.unwrap_or_default();
+    let project_name = dirs[0]
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown")
+        .to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        summarize_project_dirs(&dirs, project_name, start_date, end_date, &filter)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))
+}
+
+/// Recent edits over several project folders
+#[tauri::command]
+pub async fn get_merged_recent_edits(
+    project_paths: Vec<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    filter: Option<RecentEditsFilter>,
+) -> Result<PaginatedRecentEdits, String> {
+    let dirs = validate_project_paths(&project_paths)?;
+    tauri::async_runtime::spawn_blocking(move || collect_recent_edits(&dirs, offset, limit, filter))
+        .await
+        .map_err(|e| format!("Task join error: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_session(project_dir: &Path, session_id: &str, cwd: &Path) {
+        fs::create_dir_all(project_dir).unwrap();
+        let line = serde_json::json!({
+            "uuid": "u1",
+            "sessionId": session_id,
+            "timestamp": "2025-06-26T10:00:00Z",
+            "type": "user",
+            "cwd": cwd,
+            "message": {"role": "user", "content": "Hello"},
+        });
+        fs::write(
+            project_dir.join(format!("{session_id}.jsonl")),
+            format!("{line}\n"),
+        )
+        .unwrap();
+    }
+
+    fn git_checkout(dir: &Path, remote: &str) {
+        let repo = git2::Repository::init(dir).unwrap();
+        repo.remote("origin", remote).unwrap();
+    }
+
+    fn project_folder(projects: &Path, actual: &Path) -> PathBuf {
+        projects.join(crate::utils::encode_project_path(&actual.to_string_lossy()))
+    }
+
+    #[test]
+    fn test_normalize_remote() {
+        let expected = "github.com/me/tool";
+        assert_eq!(normalize_remote("git@github.com:me/tool.git"), expected);
+        assert_eq!(normalize_remote("https://github.com/me/tool"), expected);
+        assert_eq!(
+            normalize_remote("ssh://git@GitHub.com/me/tool.git/"),
+            expected
+        );
+        assert_ne!(normalize_remote("https://github.com/me/other"), expected);
+    }
+
+    #[tokio::test]
+    async fn test_detect_moved_projects() {
+        let temp = TempDir::new().unwrap();
+        let claude = temp.path().join(".claude");
+        let projects = claude.join("projects");
+        let work = temp.path().join("work");
+
+        // Same remote in two checkouts, backed by a session of one that ran in
+        // the other, but not a subfolder project of one of them
+        let old_clone = work.join("tool");
+        let new_clone = work.join("tool-renamed");
+        git_checkout(&old_clone, "git@github.com:me/tool.git");
+        git_checkout(&new_clone, "https://github.com/me/tool");
+        fs::create_dir_all(old_clone.join("docs")).unwrap();
+        write_session(&project_folder(&projects, &old_clone), "a1", &old_clone);
+        write_session(&project_folder(&projects, &new_clone), "a2", &new_clone);
+        write_session(&project_folder(&projects, &new_clone), "a4", &old_clone);
+        let docs = old_clone.join("docs");
+        write_session(&project_folder(&projects, &docs), "a3", &docs);
+
+        // Side-by-side clones of one remote, with nothing else in common
+        let clone = work.join("lib");
+        let fork = work.join("lib-fork");
+        git_checkout(&clone, "git@github.com:me/lib.git");
+        git_checkout(&fork, "git@github.com:me/lib.git");
+        write_session(&project_folder(&projects, &clone), "e1", &clone);
+        write_session(&project_folder(&projects, &fork), "e2", &fork);
+
+        // A directory that no longer exists, moved under another parent, with
+        // sessions in the same subdirectory before and after
+        let gone = work.join("old-place").join("site");
+        let moved = work.join("new-place").join("site");
+        fs::create_dir_all(moved.join("src")).unwrap();
+        write_session(&project_folder(&projects, &gone), "b1", &gone);
+        write_session(&project_folder(&projects, &gone), "b2", &gone.join("src"));
+        write_session(&project_folder(&projects, &moved), "b3", &moved);
+        write_session(&project_folder(&projects, &moved), "b4", &moved.join("src"));
+
+        // Only the folder name matches
+        let gone_web = work.join("old-place").join("web");
+        let other_web = work.join("elsewhere").join("web");
+        fs::create_dir_all(&other_web).unwrap();
+        write_session(&project_folder(&projects, &gone_web), "d1", &gone_web);
+        write_session(&project_folder(&projects, &other_web), "d2", &other_web);
+
+        // Unrelated project
+        let other = work.join("other");
+        fs::create_dir_all(&other).unwrap();
+        write_session(&project_folder(&projects, &other), "c1", &other);
+
+        let suggestions = detect_moved_projects(claude.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 2);
+
+        let tool = suggestions
+            .iter()
+            .find(|s| {
+                s.signals
+                    .iter()
+                    .any(|signal| matches!(signal, MergeSignal::GitRemote { .. }))
+            })
+            .unwrap();
+        let mut actual_paths = tool.actual_paths.clone();
+        actual_paths.sort();
+        assert_eq!(
+            actual_paths,
+            vec![
+                old_clone.to_string_lossy().to_string(),
+                new_clone.to_string_lossy().to_string()
+            ]
+        );
+        assert_eq!(
+            tool.signals,
+            vec![
+                MergeSignal::GitRemote {
+                    url: "github.com/me/tool".to_string()
+                },
+                MergeSignal::CwdLineage {
+                    cwd: old_clone.to_string_lossy().to_string()
+                }
+            ]
+        );
+
+        let site = suggestions
+            .iter()
+            .find(|s| {
+                s.actual_paths
+                    .contains(&moved.to_string_lossy().to_string())
+            })
+            .unwrap();
+        assert_eq!(site.project_paths.len(), 2);
+        assert_eq!(
+            site.signals,
+            vec![MergeSignal::MovedFolder {
+                folder_name: "site".to_string()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merged_views_span_folders() {
+        let temp = TempDir::new().unwrap();
+        let projects = temp.path().join(".claude").join("projects");
+        let old_dir = temp.path().join("old").join("app");
+        let new_dir = temp.path().join("new").join("app");
+        let old_folder = project_folder(&projects, &old_dir);
+        let new_folder = project_folder(&projects, &new_dir);
+        write_session(&old_folder, "s1", &old_dir);
+        write_session(&new_folder, "s2", &new_dir);
+        let paths = vec![
+            new_folder.to_string_lossy().to_string(),
+            old_folder.to_string_lossy().to_string(),
+        ];
+
+        let sessions = load_merged_project_sessions(paths.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), 2);
+
+        let summary = get_merged_project_stats_summary(paths.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(summary.total_sessions, 2);
+        assert_eq!(summary.total_messages, 2);
+
+        assert!(
+            load_merged_project_sessions(vec!["relative".to_string()], None)
+                .await
+                .is_err()
+        );
+        assert!(get_merged_recent_edits(vec![], None, None, None)
+            .await
+            .is_err());
+    }
+}
@@ -25,12 +25,15 @@ fn validate_absolute_path(project_path: &str) -> Result<(), String> {
 pub struct MetadataState {
     /// Cached metadata with mutex for thread-safe access
     pub metadata: Mutex<Option<UserMetadata>>,
+    /// Held across a read-modify-write so concurrent updates are not lost
+    updates: tauri::async_runtime::Mutex<()>,
 }
 
 impl Default for MetadataState {
     fn default() -> Self {
         Self {
             metadata: Mutex::new(None),
+            updates: tauri::async_runtime::Mutex::new(()),
         }
     }
 }
@@ -56,6 +59,24 @@ fn ensure_metadata_folder() -> Result<PathBuf, String> {
     Ok(folder)
 }
 
+/// Read the persisted user metadata directly from disk
+///
+/// For callers without access to `MetadataState`, such as exports.
+pub(crate) fn read_user_metadata() -> Result<UserMetadata, String> {
+    let path = get_user_data_path()?;
+    if !path.exists() {
+        return Ok(UserMetadata::new());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read metadata file: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse metadata: {e}"))
+}
+
+/// Read the persisted user settings directly from disk
+pub(crate) fn read_user_settings() -> Result<UserSettings, String> {
+    Ok(read_user_metadata()?.settings)
+}
+
 /// Get the metadata folder path
 #[tauri::command]
 pub async fn get_metadata_folder_path() -> Result<String, String> {
@@ -119,26 +140,68 @@ fn save_metadata_to_disk(metadata: &UserMetadata) -> Result<(), String> {
     Ok(())
 }
 
-/// Save user metadata to disk with atomic write
-#[tauri::command]
-pub async fn save_user_metadata(
-    metadata: UserMetadata,
-    state: State<'_, MetadataState>,
-) -> Result<(), String> {
-    let metadata_clone = metadata.clone();
+/// Apply `change` to the cached metadata and persist it
+///
+/// Falls back to the file on disk when the cache has not been loaded yet,
+/// so commands outside the metadata UI never clobber saved metadata.
+pub(crate) async fn modify_user_metadata(
+    state: &MetadataState,
+    change: impl FnOnce(&mut UserMetadata),
+) -> Result<UserMetadata, String> {
+    try_modify_user_metadata(state, |metadata| {
+        change(metadata);
+        Ok(())
+    })
+    .await
+}
 
-    // Perform blocking file I/O off the async runtime
+/// Like `modify_user_metadata`, but nothing is saved when `change` fails
+///
+/// Updates are applied one at a time, so `change` always sees the result of
+/// the previous one.
+pub(crate) async fn try_modify_user_metadata(
+    state: &MetadataState,
+    change: impl FnOnce(&mut UserMetadata) -> Result<(), String>,
+) -> Result<UserMetadata, String> {
+    let _update = state.updates.lock().await;
+    let cached = state
+        .metadata
+        .lock()
+        .map_err(|e| format!("Failed to lock metadata: {e}"))?
+        .clone();
+    let mut metadata = match cached {
+        Some(metadata) => metadata,
+        None => tauri::async_runtime::spawn_blocking(read_user_metadata)
+            .await
+            .map_err(|e| format!("Task join error: {e}"))??,
+    };
+    change(&mut metadata)?;
+
+    let metadata_clone = metadata.clone();
     tauri::async_runtime::spawn_blocking(move || save_metadata_to_disk(&metadata_clone))
         .await
         .map_err(|e| format!("Task join error: {e}"))??;
 
-    // Update cache
-    let mut cached = state
+    *state
         .metadata
         .lock()
-        .map_err(|e| format!("Failed to lock metadata: {e}"))?;
-    *cached = Some(metadata);
+        .map_err(|e| format!("Failed to lock metadata: {e}"))? = Some(metadata.clone());
+    Ok(metadata)
+}
 
+/// Save user metadata to disk with atomic write
+///
+/// Registered data roots are kept; they change only through the roots commands.
+#[tauri::command]
+pub async fn save_user_metadata(
+    metadata: UserMetadata,
+    state: State<'_, MetadataState>,
+) -> Result<(), String> {
+    modify_user_metadata(&state, |current| {
+        let previous = std::mem::replace(current, metadata);
+        current.settings.data_roots = previous.settings.data_roots;
+    })
+    .await?;
     Ok(())
 }
 
@@ -149,32 +212,15 @@ pub async fn update_session_metadata(
     update: SessionMetadata,
     state: State<'_, MetadataState>,
 ) -> Result<UserMetadata, String> {
-    // Perform quick in-memory mutation while holding lock, then release
-    let metadata_to_save = {
-        let mut cached = state
-            .metadata
-            .lock()
-            .map_err(|e| format!("Failed to lock metadata: {e}"))?;
-
-        let metadata = cached.get_or_insert_with(UserMetadata::new);
-
+    modify_user_metadata(&state, |metadata| {
         // Update or insert session metadata
         if update.is_empty() {
             metadata.sessions.remove(&session_id);
         } else {
             metadata.sessions.insert(session_id, update);
         }
-
-        metadata.clone()
-    }; // Lock released here
-
-    // Perform blocking file I/O off the async runtime
-    let metadata_clone = metadata_to_save.clone();
-    tauri::async_runtime::spawn_blocking(move || save_metadata_to_disk(&metadata_clone))
-        .await
-        .map_err(|e| format!("Task join error: {e}"))??;
-
-    Ok(metadata_to_save)
+    })
+    .await
 }
 
 /// Update metadata for a specific project
@@ -187,60 +233,30 @@ pub async fn update_project_metadata(
     // Validate that project path is absolute
     validate_absolute_path(&project_path)?;
 
-    // Perform quick in-memory mutation while holding lock, then release
-    let metadata_to_save = {
-        let mut cached = state
-            .metadata
-            .lock()
-            .map_err(|e| format!("Failed to lock metadata: {e}"))?;
-
-        let metadata = cached.get_or_insert_with(UserMetadata::new);
-
+    modify_user_metadata(&state, |metadata| {
         // Update or insert project metadata
         if update.is_empty() {
             metadata.projects.remove(&project_path);
         } else {
             metadata.projects.insert(project_path, update);
         }
-
-        metadata.clone()
-    }; // Lock released here
-
-    // Perform blocking file I/O off the async runtime
-    let metadata_clone = metadata_to_save.clone();
-    tauri::async_runtime::spawn_blocking(move || save_metadata_to_disk(&metadata_clone))
-        .await
-        .map_err(|e| format!("Task join error: {e}"))??;
-
-    Ok(metadata_to_save)
+    })
+    .await
 }
 
 /// Update global user settings
+///
+/// Registered data roots are kept; they change only through the roots commands.
 #[tauri::command]
 pub async fn update_user_settings(
     settings: UserSettings,
     state: State<'_, MetadataState>,
 ) -> Result<UserMetadata, String> {
-    // Perform quick in-memory mutation while holding lock, then release
-    let metadata_to_save = {
-        let mut cached = state
-            .metadata
-            .lock()
-            .map_err(|e| format!("Failed to lock metadata: {e}"))?;
-
-        let metadata = cached.get_or_insert_with(UserMetadata::new);
-        metadata.settings = settings;
-
-        metadata.clone()
-    }; // Lock released here
-
-    // Perform blocking file I/O off the async runtime
-    let metadata_clone = metadata_to_save.clone();
-    tauri::async_runtime::spawn_blocking(move || save_metadata_to_disk(&metadata_clone))
-        .await
-        .map_err(|e| format!("Task join error: {e}"))??;
-
-    Ok(metadata_to_save)
+    modify_user_metadata(&state, |metadata| {
+        let previous = std::mem::replace(&mut metadata.settings, settings);
+        metadata.settings.data_roots = previous.data_roots;
+    })
+    .await
 }
 
 /// Check if a project should be hidden based on metadata
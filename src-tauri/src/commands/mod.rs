@@ -1,14 +1,25 @@
+pub mod archive;
+pub mod bundle;
+pub mod claude_dir;
 pub mod claude_settings;
+pub mod commits;
+pub mod config_watcher;
+pub mod export;
 pub mod feedback;
 pub mod fs_utils;
+pub mod git;
 pub mod mcp_presets;
+pub mod merge;
 pub mod metadata;
 pub mod project;
+pub mod roots;
 pub mod session;
 pub mod settings;
 pub mod stats;
+pub mod tail;
 pub mod unified_presets;
 pub mod watcher;
+pub mod worktree;
 
 #[cfg(test)]
 mod proptest_examples;
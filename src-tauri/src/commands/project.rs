@@ -1,70 +1,37 @@
-use crate::models::{ClaudeProject, GitCommit};
+use super::archive::{archive_projects_dir, merge_archived_projects};
+use super::claude_dir::claude_dir;
+use super::git::{log_commits, open_repository, to_git_commit, validate_repo_path, LogQuery};
+use super::roots::{selected_roots, ResolvedRoot};
+use super::session::{SessionScan, SessionScanCache};
+use crate::models::{ClaudeProject, GitCommit, RootSelection};
 use crate::utils::{
     detect_git_worktree_info, estimate_message_count_from_size, extract_project_name,
+    resolve_project_path,
 };
 use chrono::{DateTime, Utc};
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::process::Command;
 use walkdir::WalkDir;
 
 #[tauri::command]
 pub async fn get_git_log(actual_path: String, limit: usize) -> Result<Vec<GitCommit>, String> {
-    // Validate path is absolute and exists
-    let path_buf = PathBuf::from(&actual_path);
-    if !path_buf.is_absolute() {
-        return Err("Path must be absolute".to_string());
-    }
-    if !path_buf.exists() || !path_buf.is_dir() {
-        return Err("Path does not exist or is not a directory".to_string());
-    }
-
-    // Canonicalize to ensure we are using the real path
-    let safe_path = path_buf
-        .canonicalize()
-        .map_err(|e| format!("Invalid path: {e}"))?;
-
-    let output = Command::new("git")
-        .args(["log", "-n"])
-        .arg(limit.to_string())
-        .args(["--pretty=format:%H|%an|%at|%s"])
-        .current_dir(&safe_path)
-        .output()
-        .map_err(|e| format!("Failed to execute git log: {e}"))?;
-
-    if !output.status.success() {
+    let safe_path = validate_repo_path(&actual_path)?;
+    let Ok(repo) = open_repository(&safe_path) else {
         return Ok(vec![]);
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut commits = Vec::new();
-
-    for line in stdout.lines() {
-        let parts: Vec<&str> = line.splitn(4, '|').collect();
-        if parts.len() == 4 {
-            let timestamp = parts[2].parse::<i64>().unwrap_or(0);
-            let date = DateTime::<Utc>::from_timestamp(timestamp, 0)
-                .map(|dt| dt.to_rfc3339())
-                .unwrap_or_else(|| "unknown".to_string());
-
-            commits.push(GitCommit {
-                hash: parts[0].to_string(),
-                author: parts[1].to_string(),
-                timestamp,
-                date,
-                message: parts[3].to_string(),
-            });
-        }
-    }
-
-    Ok(commits)
+    };
+    let query = LogQuery {
+        limit: Some(limit),
+        ..LogQuery::default()
+    };
+    let commits = log_commits(&repo, &query)?;
+    Ok(commits.iter().map(to_git_commit).collect())
 }
 
 #[tauri::command]
 pub async fn get_claude_folder_path() -> Result<String, String> {
-    let home_dir =
-        dirs::home_dir().ok_or("HOME_DIRECTORY_NOT_FOUND:Could not determine home directory")?;
-    let claude_path = home_dir.join(".claude");
+    let claude_path = claude_dir()?;
 
     if !claude_path.exists() {
         return Err(format!(
@@ -104,84 +71,127 @@ pub async fn validate_claude_folder(path: String) -> Result<bool, String> {
     Ok(false)
 }
 
-#[tauri::command]
-pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, String> {
-    #[cfg(debug_assertions)]
-    let start_time = std::time::Instant::now();
-    let projects_path = PathBuf::from(&claude_path).join("projects");
+/// Summarize one project folder
+///
+/// Message counts and `cwd`s come from the session metadata cache when it is
+/// warm for a file, and from fast line classification otherwise, which is then
+/// cached. Only unreadable files fall back to a size-based estimate.
+fn scan_project(entry: &walkdir::DirEntry) -> Option<ClaudeProject> {
+    let raw_project_name = entry.file_name().to_string_lossy().to_string();
+    let project_path = entry.path().to_string_lossy().to_string();
+
+    // Validate that project_path is absolute before processing
+    if !entry.path().is_absolute() {
+        #[cfg(debug_assertions)]
+        eprintln!("⚠️ Skipping non-absolute project path: {project_path}");
+        return None;
+    }
 
-    if !projects_path.exists() {
-        return Ok(vec![]);
+    let files: Vec<(PathBuf, fs::Metadata)> = WalkDir::new(entry.path())
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter_map(|e| Some((e.path().to_path_buf(), e.metadata().ok()?)))
+        .collect();
+
+    let last_modified = files
+        .iter()
+        .filter_map(|(_, metadata)| metadata.modified().ok())
+        .max();
+
+    let cache = SessionScanCache::load(entry.path());
+    let scans: Vec<(SessionScan, bool)> = files
+        .par_iter()
+        .map(|(path, metadata)| {
+            cache.scan(path).map_or_else(
+                || {
+                    let estimate = SessionScan {
+                        message_count: estimate_message_count_from_size(metadata.len()),
+                        ..SessionScan::default()
+                    };
+                    (estimate, true)
+                },
+                |scan| (scan, false),
+            )
+        })
+        .collect();
+    cache.save();
+
+    let mut cwd_counts: HashMap<String, usize> = HashMap::new();
+    for (scan, _) in &scans {
+        for (cwd, count) in &scan.cwd_counts {
+            *cwd_counts.entry(cwd.clone()).or_insert(0) += count;
+        }
     }
 
-    let mut projects = Vec::new();
+    let last_modified_str = last_modified
+        .map(|lm| {
+            let dt: DateTime<Utc> = lm.into();
+            dt.to_rfc3339()
+        })
+        .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+    // Decode the actual filesystem path FIRST
+    let (actual_path, path_resolution) = resolve_project_path(&project_path, &cwd_counts);
+
+    // Detect git worktree information using the actual filesystem path
+    let git_info = detect_git_worktree_info(&actual_path);
+
+    Some(ClaudeProject {
+        name: extract_project_name(&raw_project_name),
+        path: project_path,
+        actual_path,
+        session_count: files.len(),
+        message_count: scans.iter().map(|(scan, _)| scan.message_count).sum(),
+        last_modified: last_modified_str,
+        git_info,
+        archived_session_count: 0,
+        is_estimate: scans.iter().any(|(_, estimated)| *estimated),
+        root: None,
+        path_resolution: Some(path_resolution),
+    })
+}
+
+/// Scan one data root, tagging each project with the root's name
+fn scan_root(root: &ResolvedRoot) -> Vec<ClaudeProject> {
+    let projects_path = root.projects_path();
+    if !projects_path.exists() {
+        return vec![];
+    }
 
-    for entry in WalkDir::new(&projects_path)
+    let project_dirs: Vec<walkdir::DirEntry> = WalkDir::new(&projects_path)
         .min_depth(1)
         .max_depth(1)
         .into_iter()
         .filter_map(std::result::Result::ok)
         .filter(|e| e.file_type().is_dir())
-    {
-        let raw_project_name = entry.file_name().to_string_lossy().to_string();
-        let project_path = entry.path().to_string_lossy().to_string();
-        let project_name = extract_project_name(&raw_project_name);
-
-        let mut session_count = 0;
-        let mut message_count = 0;
-        let mut last_modified = None;
-
-        for jsonl_entry in WalkDir::new(entry.path())
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-            .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
-        {
-            session_count += 1;
-
-            if let Ok(metadata) = jsonl_entry.metadata() {
-                if let Ok(modified) = metadata.modified() {
-                    if last_modified.is_none() || modified > last_modified.unwrap() {
-                        last_modified = Some(modified);
-                    }
-                }
-
-                // Estimate message count from file size - much faster
-                let estimated_messages = estimate_message_count_from_size(metadata.len());
-                message_count += estimated_messages;
-            }
-        }
+        .collect();
 
-        let last_modified_str = last_modified
-            .map(|lm| {
-                let dt: DateTime<Utc> = lm.into();
-                dt.to_rfc3339()
-            })
-            .unwrap_or_else(|| Utc::now().to_rfc3339());
-
-        // Validate that project_path is absolute before processing
-        let path_buf = PathBuf::from(&project_path);
-        if !path_buf.is_absolute() {
-            #[cfg(debug_assertions)]
-            eprintln!("⚠️ Skipping non-absolute project path: {project_path}");
-            continue;
-        }
+    let mut projects: Vec<ClaudeProject> =
+        project_dirs.par_iter().filter_map(scan_project).collect();
 
-        // Decode the actual filesystem path FIRST
-        let actual_path = crate::utils::decode_project_path(&project_path);
-
-        // Detect git worktree information using the actual filesystem path
-        let git_info = detect_git_worktree_info(&actual_path);
+    // Archived projects still appear, with their archived sessions counted
+    if let Ok(archive) = archive_projects_dir(&root.path) {
+        merge_archived_projects(&mut projects, &projects_path, &archive);
+    }
 
-        projects.push(ClaudeProject {
-            name: project_name,
-            path: project_path,
-            actual_path,
-            session_count,
-            message_count,
-            last_modified: last_modified_str,
-            git_info,
-        });
+    for project in &mut projects {
+        project.root = Some(root.name.clone());
     }
+    projects
+}
+
+/// Scan `claude_path`, or the selected data roots when `roots` is given
+#[tauri::command]
+pub async fn scan_projects(
+    claude_path: String,
+    roots: Option<RootSelection>,
+) -> Result<Vec<ClaudeProject>, String> {
+    #[cfg(debug_assertions)]
+    let start_time = std::time::Instant::now();
+
+    let roots = selected_roots(&claude_path, roots.as_ref())?;
+    let mut projects: Vec<ClaudeProject> = roots.iter().flat_map(scan_root).collect();
 
     projects.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
 
@@ -201,8 +211,10 @@ pub async fn scan_projects(claude_path: String) -> Result<Vec<ClaudeProject>, St
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{PathConfidence, PathDecodeMethod, PathResolution};
     use std::fs::File;
     use std::io::Write;
+    use std::process::Command;
     use tempfile::TempDir;
 
     fn create_test_jsonl_file(dir: &PathBuf, filename: &str, content: &str) {
@@ -265,7 +277,7 @@ mod tests {
         let projects_dir = claude_dir.join("projects");
         fs::create_dir_all(&projects_dir).unwrap();
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
@@ -274,7 +286,7 @@ mod tests {
     async fn test_scan_projects_no_projects_dir() {
         let temp_dir = TempDir::new().unwrap();
 
-        let result = scan_projects(temp_dir.path().to_string_lossy().to_string()).await;
+        let result = scan_projects(temp_dir.path().to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
@@ -294,7 +306,7 @@ mod tests {
             r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","message":{"role":"user","content":"Hello"}}"#,
         );
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -304,6 +316,126 @@ mod tests {
         assert!(projects[0].message_count > 0);
     }
 
+    #[tokio::test]
+    async fn test_scan_projects_counts_messages_exactly() {
+        let temp_dir = TempDir::new().unwrap();
+        let claude_dir = temp_dir.path().join(".claude");
+        let project_dir = claude_dir.join("projects").join("my-project");
+        fs::create_dir_all(&project_dir).unwrap();
+
+        let big_result = "x".repeat(20_000);
+        let content = [
+            r#"{"type":"summary","summary":"Test"}"#.to_string(),
+            r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-26T10:00:00Z","type":"user","message":{"role":"user","content":"Hello"}}"#.to_string(),
+            format!(r#"{{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-26T10:00:01Z","type":"user","message":{{"role":"user","content":"{big_result}"}}}}"#),
+            r#"{"uuid":"u3","sessionId":"s1","timestamp":"2025-06-26T10:00:02Z","type":"progress"}"#.to_string(),
+        ]
+        .join("\n");
+        create_test_jsonl_file(&project_dir, "session.jsonl", &content);
+        let claude_path = claude_dir.to_string_lossy().to_string();
+
+        // Cold: counted line by line rather than estimated from the 20 KB file size
+        let projects = scan_projects(claude_path.clone(), None).await.unwrap();
+        assert_eq!(projects[0].message_count, 2);
+        assert!(!projects[0].is_estimate);
+
+        // Warm: the count comes from the session metadata cache
+        crate::commands::session::load_project_sessions(
+            project_dir.to_string_lossy().to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        let cache_path = project_dir.join(".session_cache.json");
+        let mut cache: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
+        for entry in cache["entries"].as_object_mut().unwrap().values_mut() {
+            entry["session"]["message_count"] = serde_json::json!(7);
+        }
+        fs::write(&cache_path, cache.to_string()).unwrap();
+
+        let projects = scan_projects(claude_path, None).await.unwrap();
+        assert_eq!(projects[0].message_count, 7);
+        assert!(!projects[0].is_estimate);
+    }
+
+    #[tokio::test]
+    async fn test_scan_projects_decodes_path_from_cwd() {
+        let temp_dir = TempDir::new().unwrap();
+        let claude_dir = temp_dir.path().join(".claude");
+        let project_dir = claude_dir
+            .join("projects")
+            .join("-work-claude-code-history-viewer");
+        fs::create_dir_all(&project_dir).unwrap();
+
+        let content = [
+            r#"{"uuid":"u1","sessionId":"s1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/work/claude-code-history-viewer","message":{"role":"user","content":"Hello"}}"#,
+            r#"{"uuid":"u2","sessionId":"s1","timestamp":"2025-06-26T10:00:01Z","type":"assistant","cwd":"/work/claude-code-history-viewer/src-tauri","message":{"role":"assistant","content":"Hi"}}"#,
+        ]
+        .join("\n");
+        create_test_jsonl_file(&project_dir, "session.jsonl", &content);
+        let claude_path = claude_dir.to_string_lossy().to_string();
+
+        let expected = Some(PathResolution {
+            method: PathDecodeMethod::SessionCwd,
+            confidence: PathConfidence::High,
+        });
+        let projects = scan_projects(claude_path.clone(), None).await.unwrap();
+        assert_eq!(projects[0].actual_path, "/work/claude-code-history-viewer");
+        assert_eq!(projects[0].path_resolution, expected);
+
+        // The cwds are cached with the session metadata and reused on the next scan
+        crate::commands::session::load_project_sessions(
+            project_dir.to_string_lossy().to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        let cache = fs::read_to_string(project_dir.join(".session_cache.json")).unwrap();
+        assert!(cache.contains("cwd_counts"));
+        let projects = scan_projects(claude_path, None).await.unwrap();
+        assert_eq!(projects[0].actual_path, "/work/claude-code-history-viewer");
+        assert_eq!(projects[0].path_resolution, expected);
+    }
+
+    #[test]
+    fn test_scan_roots_tags_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut registered = Vec::new();
+        for root in ["home", "work"] {
+            let project_dir = temp_dir
+                .path()
+                .join(root)
+                .join("projects")
+                .join(format!("{root}-project"));
+            fs::create_dir_all(&project_dir).unwrap();
+            create_test_jsonl_file(&project_dir, "session.jsonl", "{}");
+            registered.push(crate::models::DataRoot {
+                name: root.to_string(),
+                path: temp_dir.path().join(root).to_string_lossy().to_string(),
+            });
+        }
+        let claude_path = temp_dir.path().join("home").to_string_lossy().to_string();
+
+        // `home` is also the default folder, so it is scanned once
+        let roots =
+            crate::commands::roots::resolve_roots(&claude_path, &RootSelection::All, &registered)
+                .unwrap();
+        let mut tagged: Vec<(String, String)> = roots
+            .iter()
+            .flat_map(scan_root)
+            .map(|p| (p.root.unwrap(), p.name))
+            .collect();
+        tagged.sort();
+        assert_eq!(
+            tagged,
+            vec![
+                ("default".to_string(), "home-project".to_string()),
+                ("work".to_string(), "work-project".to_string()),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_scan_projects_multiple_projects() {
         let temp_dir = TempDir::new().unwrap();
@@ -321,7 +453,7 @@ mod tests {
         fs::create_dir_all(&project2_dir).unwrap();
         create_test_jsonl_file(&project2_dir, "session.jsonl", "{}");
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -345,7 +477,7 @@ mod tests {
         fs::create_dir_all(&project_dir).unwrap();
         create_test_jsonl_file(&project_dir, "session.jsonl", "{}");
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -374,7 +506,7 @@ mod tests {
         fs::create_dir_all(&newer_dir).unwrap();
         create_test_jsonl_file(&newer_dir, "session.jsonl", "{}");
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -397,7 +529,7 @@ mod tests {
         create_test_jsonl_file(&project_dir, "config.json", "{}");
         create_test_jsonl_file(&project_dir, "readme.txt", "readme");
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
@@ -419,7 +551,7 @@ mod tests {
         create_test_jsonl_file(&project_dir, "session1.jsonl", "{}");
         create_test_jsonl_file(&nested_dir, "session2.jsonl", "{}");
 
-        let result = scan_projects(claude_dir.to_string_lossy().to_string()).await;
+        let result = scan_projects(claude_dir.to_string_lossy().to_string(), None).await;
         assert!(result.is_ok());
 
         let projects = result.unwrap();
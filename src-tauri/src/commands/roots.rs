@@ -0,0 +1,368 @@
+//! Named Claude data roots
+//!
+//! The default root is the Claude folder the app was opened with. Users can
+//! register more in settings (other `CLAUDE_CONFIG_DIR`s, copies pulled from
+//! a remote machine) and scan, search and aggregate stats over any of them.
+
+use super::archive::{archive_root, is_session_file};
+use super::claude_dir::claude_dir;
+use super::metadata::{read_user_settings, try_modify_user_metadata, MetadataState};
+use crate::models::{DataRoot, DataRootInfo, RootSelection};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// Name of the root for the folder the app was opened with
+pub(crate) const DEFAULT_ROOT: &str = "default";
+
+/// A data root resolved to a folder on disk
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ResolvedRoot {
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+}
+
+impl ResolvedRoot {
+    fn new(name: &str, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.to_string(),
+            path: path.into(),
+        }
+    }
+
+    pub(crate) fn projects_path(&self) -> PathBuf {
+        self.path.join("projects")
+    }
+}
+
+pub(crate) fn same_folder(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Resolve `selection` against the registered roots, skipping duplicate folders
+pub(crate) fn resolve_roots(
+    claude_path: &str,
+    selection: &RootSelection,
+    registered: &[DataRoot],
+) -> Result<Vec<ResolvedRoot>, String> {
+    let default = ResolvedRoot::new(DEFAULT_ROOT, claude_path);
+    let candidates: Vec<ResolvedRoot> = match selection {
+        RootSelection::All => std::iter::once(default)
+            .chain(
+                registered
+                    .iter()
+                    .map(|root| ResolvedRoot::new(&root.name, &root.path)),
+            )
+            .collect(),
+        RootSelection::Only(names) => names
+            .iter()
+            .map(|name| {
+                if name == DEFAULT_ROOT {
+                    return Ok(default.clone());
+                }
+                registered
+                    .iter()
+                    .find(|root| &root.name == name)
+                    .map(|root| ResolvedRoot::new(&root.name, &root.path))
+                    .ok_or_else(|| format!("Unknown data root: {name}"))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    let mut roots: Vec<ResolvedRoot> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if !roots
+            .iter()
+            .any(|root| same_folder(&root.path, &candidate.path))
+        {
+            roots.push(candidate);
+        }
+    }
+    Ok(roots)
+}
+
+/// Roots for a command's optional `roots` argument; without one only `claude_path` is used
+pub(crate) fn selected_roots(
+    claude_path: &str,
+    selection: Option<&RootSelection>,
+) -> Result<Vec<ResolvedRoot>, String> {
+    match selection {
+        None => Ok(vec![ResolvedRoot::new(DEFAULT_ROOT, claude_path)]),
+        Some(selection) => resolve_roots(claude_path, selection, &read_user_settings()?.data_roots),
+    }
+}
+
+/// The configured Claude folder and every registered root
+fn known_claude_folders() -> Vec<PathBuf> {
+    let mut folders: Vec<PathBuf> = claude_dir().into_iter().collect();
+    // Unreadable settings only cost the registered roots
+    folders.extend(
+        read_user_settings()
+            .map(|settings| settings.data_roots)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|root| PathBuf::from(root.path)),
+    );
+    folders
+}
+
+/// Folders session files may be read from: the projects folder of the
+/// configured Claude folder and of every registered root, plus the archive
+pub(crate) fn session_read_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = known_claude_folders()
+        .iter()
+        .map(|folder| folder.join("projects"))
+        .collect();
+    roots.extend(archive_root());
+    roots
+}
+
+/// Reject a folder that is neither the configured Claude folder nor a registered root
+pub(crate) fn ensure_known_claude_folder(path: &str) -> Result<(), String> {
+    if is_known_folder(Path::new(path), &known_claude_folders()) {
+        Ok(())
+    } else {
+        Err(format!("Not a known Claude folder: {path}"))
+    }
+}
+
+fn is_known_folder(path: &Path, folders: &[PathBuf]) -> bool {
+    path.is_absolute() && folders.iter().any(|folder| same_folder(path, folder))
+}
+
+/// Reject anything but a `.jsonl`/`.jsonl.gz` session file inside one of `roots`
+pub(crate) fn validate_session_path(path: &str, roots: &[PathBuf]) -> Result<(), String> {
+    let requested = Path::new(path);
+    if !requested.is_absolute() {
+        return Err("Session path must be absolute".to_string());
+    }
+    let canonical = requested
+        .canonicalize()
+        .map_err(|e| format!("Session file not found: {e}"))?;
+    if !canonical.is_file() || !is_session_file(&canonical) {
+        return Err(format!("Not a session file: {path}"));
+    }
+    let inside = roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| canonical.starts_with(root));
+    if inside {
+        Ok(())
+    } else {
+        Err(format!(
+            "Session file is outside the known Claude folders: {path}"
+        ))
+    }
+}
+
+/// The Claude folder at or directly inside `path`
+fn claude_folder(path: &Path) -> Option<PathBuf> {
+    if path.join("projects").is_dir() {
+        Some(path.to_path_buf())
+    } else if path.join(".claude").join("projects").is_dir() {
+        Some(path.join(".claude"))
+    } else {
+        None
+    }
+}
+
+/// Add a root to `roots` after validating its name and folder
+fn register_root(roots: &mut Vec<DataRoot>, name: &str, path: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Data root name cannot be empty".to_string());
+    }
+    if name == DEFAULT_ROOT || roots.iter().any(|root| root.name == name) {
+        return Err(format!("A data root named '{name}' already exists"));
+    }
+    let requested = PathBuf::from(path);
+    if !requested.is_absolute() {
+        return Err("Data root path must be absolute".to_string());
+    }
+    let folder = claude_folder(&requested)
+        .ok_or_else(|| format!("No Claude projects folder found in {path}"))?;
+
+    roots.push(DataRoot {
+        name: name.to_string(),
+        path: folder.to_string_lossy().to_string(),
+    });
+    Ok(())
+}
+
+/// List the default root and every registered root
+#[tauri::command]
+pub async fn list_data_roots(claude_path: String) -> Result<Vec<DataRootInfo>, String> {
+    let registered = read_user_settings()?.data_roots;
+    let default = DataRoot {
+        name: DEFAULT_ROOT.to_string(),
+        path: claude_path,
+    };
+
+    Ok(std::iter::once(default)
+        .chain(registered)
+        .map(|root| DataRootInfo {
+            is_default: root.name == DEFAULT_ROOT,
+            available: Path::new(&root.path).join("projects").is_dir(),
+            name: root.name,
+            path: root.path,
+        })
+        .collect())
+}
+
+/// Register a named Claude folder
+#[tauri::command]
+pub async fn add_data_root(
+    name: String,
+    path: String,
+    state: State<'_, MetadataState>,
+) -> Result<Vec<DataRoot>, String> {
+    let metadata = try_modify_user_metadata(&state, |metadata| {
+        register_root(&mut metadata.settings.data_roots, &name, &path)
+    })
+    .await?;
+    Ok(metadata.settings.data_roots)
+}
+
+/// Forget a registered Claude folder; its files are left untouched
+#[tauri::command]
+pub async fn remove_data_root(
+    name: String,
+    state: State<'_, MetadataState>,
+) -> Result<Vec<DataRoot>, String> {
+    let metadata = try_modify_user_metadata(&state, |metadata| {
+        let roots = &mut metadata.settings.data_roots;
+        if !roots.iter().any(|root| root.name == name) {
+            return Err(format!("Unknown data root: {name}"));
+        }
+        roots.retain(|root| root.name != name);
+        Ok(())
+    })
+    .await?;
+    Ok(metadata.settings.data_roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn claude_dir(temp: &TempDir, name: &str) -> PathBuf {
+        let dir = temp.path().join(name).join(".claude");
+        fs::create_dir_all(dir.join("projects")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_register_root_validates() {
+        let temp = TempDir::new().unwrap();
+        let work = claude_dir(&temp, "work");
+        let mut roots = Vec::new();
+
+        // The parent of a `.claude` folder is accepted and normalized
+        register_root(
+            &mut roots,
+            " work ",
+            &work.parent().unwrap().to_string_lossy(),
+        )
+        .unwrap();
+        assert_eq!(roots[0].name, "work");
+        assert_eq!(PathBuf::from(&roots[0].path), work);
+
+        let path = work.to_string_lossy().to_string();
+        assert!(register_root(&mut roots, "work", &path).is_err());
+        assert!(register_root(&mut roots, DEFAULT_ROOT, &path).is_err());
+        assert!(register_root(&mut roots, "", &path).is_err());
+        assert!(register_root(&mut roots, "rel", "relative/path").is_err());
+        assert!(register_root(
+            &mut roots,
+            "missing",
+            &temp.path().join("missing").to_string_lossy()
+        )
+        .is_err());
+        assert_eq!(roots.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_session_path() {
+        let temp = TempDir::new().unwrap();
+        let projects = claude_dir(&temp, "home").join("projects");
+        let project = projects.join("-work-app");
+        fs::create_dir_all(&project).unwrap();
+        for name in ["s1.jsonl", "s2.jsonl.gz", "notes.txt"] {
+            fs::write(project.join(name), "").unwrap();
+        }
+        let outside = temp.path().join("outside.jsonl");
+        fs::write(&outside, "").unwrap();
+        let roots = vec![projects];
+        let check = |path: &Path| validate_session_path(&path.to_string_lossy(), &roots);
+
+        assert!(check(&project.join("s1.jsonl")).is_ok());
+        assert!(check(&project.join("s2.jsonl.gz")).is_ok());
+        assert!(check(&project.join("notes.txt")).is_err());
+        assert!(check(&project.join("missing.jsonl")).is_err());
+        assert!(check(&outside).is_err());
+        assert!(check(
+            &project
+                .join("..")
+                .join("..")
+                .join("..")
+                .join("outside.jsonl")
+        )
+        .is_err());
+        assert!(validate_session_path("relative.jsonl", &roots).is_err());
+    }
+
+    #[test]
+    fn test_is_known_folder() {
+        let temp = TempDir::new().unwrap();
+        let home = claude_dir(&temp, "home");
+        let other = claude_dir(&temp, "other");
+        let folders = vec![home.clone()];
+
+        assert!(is_known_folder(&home, &folders));
+        assert!(is_known_folder(&home.join("projects").join(".."), &folders));
+        assert!(!is_known_folder(&other, &folders));
+        assert!(!is_known_folder(Path::new("relative"), &folders));
+    }
+
+    #[test]
+    fn test_resolve_roots() {
+        let temp = TempDir::new().unwrap();
+        let default = claude_dir(&temp, "home");
+        let work = claude_dir(&temp, "work");
+        let registered = vec![
+            DataRoot {
+                name: "work".to_string(),
+                path: work.to_string_lossy().to_string(),
+            },
+            // Same folder as the default root
+            DataRoot {
+                name: "alias".to_string(),
+                path: default.to_string_lossy().to_string(),
+            },
+        ];
+        let claude_path = default.to_string_lossy().to_string();
+
+        let all = resolve_roots(&claude_path, &RootSelection::All, &registered).unwrap();
+        let names: Vec<&str> = all.iter().map(|root| root.name.as_str()).collect();
+        assert_eq!(names, vec![DEFAULT_ROOT, "work"]);
+
+        let only = resolve_roots(
+            &claude_path,
+            &RootSelection::Only(vec!["work".to_string()]),
+            &registered,
+        )
+        .unwrap();
+        assert_eq!(only, vec![ResolvedRoot::new("work", &work)]);
+
+        assert!(resolve_roots(
+            &claude_path,
+            &RootSelection::Only(vec!["nope".to_string()]),
+            &registered
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,505 @@
+//! File history checkpoint functions
+//!
+//! Claude Code's checkpoint/rewind feature records `file-history-snapshot`
+//! entries in the session JSONL and keeps the backed up file contents under
+//! `~/.claude/file-history/<session-id>/`. This module decodes those entries
+//! into per-message checkpoints and restores files from them.
+
+use super::edits::{validate_restore_path, write_restored_file};
+use crate::models::{
+    CheckpointFileContent, CheckpointRestoreAction, FileCheckpoint, RawLogEntry,
+    SessionCheckpoints, TrackedFileBackup,
+};
+use crate::utils::find_line_ranges;
+use memchr::memmem;
+use memmap2::Mmap;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Marker used to cheaply skip lines that are not snapshots
+const SNAPSHOT_MARKER: &[u8] = b"\"file-history-snapshot\"";
+
+/// Resolve the Claude folder (`~/.claude`) from a session file path
+///
+/// Session files live under `<claude>/projects/<project>/...`, possibly nested
+/// (e.g. subagent transcripts), so walk up until the `projects` directory.
+fn resolve_claude_dir(session_path: &Path) -> Option<PathBuf> {
+    session_path
+        .ancestors()
+        .find(|p| p.file_name().and_then(|n| n.to_str()) == Some("projects"))
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+}
+
+/// Backup file names are plain `<hash>@v<n>` names; reject anything path-like
+fn is_safe_backup_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains('/')
+        && !name.contains('\\')
+        && !name.contains('\0')
+}
+
+/// Resolve a tracked file key against the session working directory
+fn resolve_tracked_path(key: &str, cwd: Option<&str>) -> String {
+    if Path::new(key).is_absolute() {
+        return key.to_string();
+    }
+    match cwd {
+        Some(cwd) => Path::new(cwd).join(key).to_string_lossy().to_string(),
+        None => key.to_string(),
+    }
+}
+
+/// Parse the `trackedFileBackups` map of a snapshot
+fn parse_tracked_files(
+    snapshot: &serde_json::Value,
+    cwd: Option<&str>,
+    history_dir: Option<&Path>,
+) -> Vec<TrackedFileBackup> {
+    let Some(backups) = snapshot
+        .get("trackedFileBackups")
+        .and_then(serde_json::Value::as_object)
+    else {
+        return Vec::new();
+    };
+
+    backups
+        .iter()
+        .map(|(key, backup)| {
+            // Older builds used `backupPath`; current ones use `backupFileName`
+            let backup_file_name = backup
+                .get("backupFileName")
+                .or_else(|| backup.get("backupPath"))
+                .and_then(|v| v.as_str())
+                .map(|name| {
+                    Path::new(name)
+                        .file_name()
+                        .map_or_else(|| name.to_string(), |n| n.to_string_lossy().to_string())
+                });
+            let backup_exists = match (&backup_file_name, history_dir) {
+                (Some(name), Some(dir)) if is_safe_backup_name(name) => dir.join(name).is_file(),
+                _ => false,
+            };
+
+            TrackedFileBackup {
+                file_path: resolve_tracked_path(key, cwd),
+                backup_file_name,
+                version: backup
+                    .get("version")
+                    .and_then(serde_json::Value::as_u64)
+                    .map(|v| v as u32),
+                backup_time: backup
+                    .get("backupTime")
+                    .and_then(|v| v.as_str())
+                    .map(ToString::to_string),
+                backup_exists,
+            }
+        })
+        .collect()
+}
+
+/// Collect all checkpoints recorded in a session file
+#[allow(unsafe_code)] // Required for mmap performance optimization
+fn collect_session_checkpoints(session_path: &Path) -> Result<SessionCheckpoints, String> {
+    let file =
+        fs::File::open(session_path).map_err(|e| format!("Failed to open session file: {e}"))?;
+
+    // SAFETY: We're only reading the file, and the file handle is kept open
+    // for the duration of the mmap's lifetime. Session files are append-only.
+    let mmap = unsafe { Mmap::map(&file) }
+        .map_err(|e| format!("Failed to memory-map session file: {e}"))?;
+
+    let snapshot_finder = memmem::Finder::new(SNAPSHOT_MARKER);
+    let mut session_id: Option<String> = None;
+    let mut cwd: Option<String> = None;
+    let mut snapshot_entries: Vec<RawLogEntry> = Vec::new();
+
+    for (start, end) in find_line_ranges(&mmap) {
+        let line = &mmap[start..end];
+        let is_snapshot = snapshot_finder.find(line).is_some();
+
+        // Only fully parse non-snapshot lines until session id and cwd are known
+        if !is_snapshot && session_id.is_some() && cwd.is_some() {
+            continue;
+        }
+
+        // simd-json requires mutable slice
+        let mut line_bytes = line.to_vec();
+        let Ok(entry) = simd_json::serde::from_slice::<RawLogEntry>(&mut line_bytes) else {
+            continue;
+        };
+
+        if session_id.is_none() {
+            session_id.clone_from(&entry.session_id);
+        }
+        if cwd.is_none() {
+            cwd.clone_from(&entry.cwd);
+        }
+        if entry.message_type == "file-history-snapshot" {
+            snapshot_entries.push(entry);
+        }
+    }
+
+    // Snapshot lines often lack a sessionId; session files are named after it
+    let session_id = session_id
+        .or_else(|| {
+            session_path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "unknown-session".to_string());
+
+    let history_dir = resolve_claude_dir(session_path)
+        .map(|claude_dir| claude_dir.join("file-history").join(&session_id))
+        .filter(|_| is_safe_backup_name(&session_id));
+
+    let mut checkpoints: Vec<FileCheckpoint> = Vec::new();
+    let mut index_by_message: HashMap<String, usize> = HashMap::new();
+
+    for entry in snapshot_entries {
+        let Some(snapshot) = entry.snapshot.as_ref() else {
+            continue;
+        };
+        let Some(message_id) = entry.message_id.clone().or_else(|| {
+            snapshot
+                .get("messageId")
+                .and_then(|v| v.as_str())
+                .map(ToString::to_string)
+        }) else {
+            continue;
+        };
+        let timestamp = snapshot
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .map(ToString::to_string)
+            .or(entry.timestamp.clone())
+            .unwrap_or_default();
+        let files = parse_tracked_files(snapshot, cwd.as_deref(), history_dir.as_deref());
+
+        match index_by_message.get(&message_id) {
+            // Updates extend the checkpoint of the same message
+            Some(&idx) if entry.is_snapshot_update.unwrap_or(false) => {
+                let checkpoint = &mut checkpoints[idx];
+                for file in files {
+                    if let Some(existing) = checkpoint
+                        .files
+                        .iter_mut()
+                        .find(|f| f.file_path == file.file_path)
+                    {
+                        *existing = file;
+                    } else {
+                        checkpoint.files.push(file);
+                    }
+                }
+                checkpoint.update_count += 1;
+            }
+            Some(&idx) => {
+                checkpoints[idx].files = files;
+                checkpoints[idx].timestamp = timestamp;
+            }
+            None => {
+                index_by_message.insert(message_id.clone(), checkpoints.len());
+                checkpoints.push(FileCheckpoint {
+                    message_id,
+                    timestamp,
+                    update_count: 0,
+                    files,
+                });
+            }
+        }
+    }
+
+    for checkpoint in &mut checkpoints {
+        checkpoint
+            .files
+            .sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    }
+
+    Ok(SessionCheckpoints {
+        session_id,
+        file_history_dir: history_dir.map(|d| d.to_string_lossy().to_string()),
+        checkpoints,
+    })
+}
+
+/// Find the backup of `file_path` recorded in the checkpoint for `message_id`
+///
+/// Returns the path of the backup file, or None if the file did not exist
+/// when the checkpoint was taken.
+fn find_checkpoint_backup(
+    session_path: &Path,
+    message_id: &str,
+    file_path: &str,
+) -> Result<Option<PathBuf>, String> {
+    let session = collect_session_checkpoints(session_path)?;
+
+    let checkpoint = session
+        .checkpoints
+        .iter()
+        .find(|c| c.message_id == message_id)
+        .ok_or_else(|| format!("Checkpoint not found for message: {message_id}"))?;
+
+    let tracked = checkpoint
+        .files
+        .iter()
+        .find(|f| f.file_path == file_path)
+        .ok_or_else(|| format!("File is not tracked by this checkpoint: {file_path}"))?;
+
+    let Some(backup_name) = tracked.backup_file_name.as_deref() else {
+        return Ok(None);
+    };
+    if !is_safe_backup_name(backup_name) {
+        return Err(format!("Invalid backup file name: {backup_name}"));
+    }
+
+    let history_dir = session
+        .file_history_dir
+        .ok_or("Could not locate the file-history folder for this session")?;
+    let backup_path = Path::new(&history_dir).join(backup_name);
+    if !backup_path.is_file() {
+        return Err(format!("Backup file is missing: {}", backup_path.display()));
+    }
+
+    Ok(Some(backup_path))
+}
+
+/// List the file-history checkpoints recorded in a session
+#[tauri::command]
+pub async fn get_session_checkpoints(session_path: String) -> Result<SessionCheckpoints, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        collect_session_checkpoints(Path::new(&session_path))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Read the content of a file as it was at a checkpoint
+///
+/// Returns `None` if the file did not exist at that checkpoint. Binary
+/// backups are returned as lossy text with `is_binary` set.
+#[tauri::command]
+pub async fn get_checkpoint_file_content(
+    session_path: String,
+    message_id: String,
+    file_path: String,
+) -> Result<Option<CheckpointFileContent>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(backup_path) =
+            find_checkpoint_backup(Path::new(&session_path), &message_id, &file_path)?
+        else {
+            return Ok(None);
+        };
+        fs::read(&backup_path)
+            .map(|bytes| Some(CheckpointFileContent::from_bytes(&bytes)))
+            .map_err(|e| format!("Failed to read backup file: {e}"))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Restore a file to its state at a checkpoint
+///
+/// Files that did not exist at the checkpoint are removed.
+#[tauri::command]
+pub async fn restore_checkpoint_file(
+    session_path: String,
+    message_id: String,
+    file_path: String,
+) -> Result<CheckpointRestoreAction, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let target = validate_restore_path(&file_path)?;
+
+        match find_checkpoint_backup(Path::new(&session_path), &message_id, &file_path)? {
+            Some(backup_path) => {
+                let content = fs::read(&backup_path)
+                    .map_err(|e| format!("Failed to read backup file: {e}"))?;
+                write_restored_file(target, &content)?;
+                Ok(CheckpointRestoreAction::Restored)
+            }
+            None if target.is_file() => {
+                fs::remove_file(target).map_err(|e| format!("Failed to remove file: {e}"))?;
+                Ok(CheckpointRestoreAction::Removed)
+            }
+            None => Ok(CheckpointRestoreAction::Unchanged),
+        }
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SESSION_ID: &str = "11111111-2222-3333-4444-555555555555";
+
+    /// Create `<tmp>/.claude/projects/-test-project/<session>.jsonl` and its history dir
+    fn setup_session(temp: &TempDir, lines: &[String]) -> (PathBuf, PathBuf) {
+        let claude_dir = temp.path().join(".claude");
+        let project_dir = claude_dir.join("projects").join("-test-project");
+        let history_dir = claude_dir.join("file-history").join(SESSION_ID);
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::create_dir_all(&history_dir).unwrap();
+
+        let session_path = project_dir.join(format!("{SESSION_ID}.jsonl"));
+        fs::write(&session_path, lines.join("\n")).unwrap();
+        (session_path, history_dir)
+    }
+
+    fn snapshot_line(message_id: &str, backups: &serde_json::Value, is_update: bool) -> String {
+        serde_json::json!({
+            "type": "file-history-snapshot",
+            "messageId": message_id,
+            "snapshot": {
+                "messageId": message_id,
+                "trackedFileBackups": backups,
+                "timestamp": "2025-06-26T10:00:00Z"
+            },
+            "isSnapshotUpdate": is_update
+        })
+        .to_string()
+    }
+
+    fn user_line(cwd: &str) -> String {
+        format!(
+            r#"{{"uuid":"u1","sessionId":"{SESSION_ID}","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"{cwd}","message":{{"role":"user","content":"hi"}}}}"#
+        )
+    }
+
+    #[test]
+    fn test_collect_checkpoints_merges_updates() {
+        let temp = TempDir::new().unwrap();
+        let lines = vec![
+            user_line("/work/app"),
+            snapshot_line(
+                "msg-1",
+                &serde_json::json!({
+                    "/work/app/a.txt": {"backupFileName": "aaa@v1", "version": 1, "backupTime": "2025-06-26T10:00:00Z"}
+                }),
+                false,
+            ),
+            snapshot_line(
+                "msg-1",
+                &serde_json::json!({
+                    "src/b.txt": {"backupFileName": null, "version": 1, "backupTime": "2025-06-26T10:00:01Z"}
+                }),
+                true,
+            ),
+            snapshot_line("msg-2", &serde_json::json!({}), false),
+        ];
+        let (session_path, history_dir) = setup_session(&temp, &lines);
+        fs::write(history_dir.join("aaa@v1"), "v1").unwrap();
+
+        let result = collect_session_checkpoints(&session_path).unwrap();
+
+        assert_eq!(result.session_id, SESSION_ID);
+        assert_eq!(result.checkpoints.len(), 2);
+        let first = &result.checkpoints[0];
+        assert_eq!(first.message_id, "msg-1");
+        assert_eq!(first.update_count, 1);
+        assert_eq!(first.files.len(), 2);
+        assert_eq!(first.files[0].file_path, "/work/app/a.txt");
+        assert!(first.files[0].backup_exists);
+        // Relative keys resolve against the session cwd
+        assert_eq!(first.files[1].file_path, "/work/app/src/b.txt");
+        assert!(first.files[1].backup_file_name.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_restore_checkpoint_file_writes_backup() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("work").join("a.txt");
+        let target_str = target.to_string_lossy().to_string();
+        let lines = vec![snapshot_line(
+            "msg-1",
+            &serde_json::json!({ target_str.clone(): {"backupFileName": "aaa@v1", "version": 1} }),
+            false,
+        )];
+        let (session_path, history_dir) = setup_session(&temp, &lines);
+        fs::write(history_dir.join("aaa@v1"), "checkpoint content").unwrap();
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::write(&target, "changed later").unwrap();
+
+        let session = session_path.to_string_lossy().to_string();
+        let content =
+            get_checkpoint_file_content(session.clone(), "msg-1".to_string(), target_str.clone())
+                .await
+                .unwrap();
+        let content = content.unwrap();
+        assert_eq!(content.content, "checkpoint content");
+        assert!(!content.is_binary);
+
+        let action = restore_checkpoint_file(session, "msg-1".to_string(), target_str)
+            .await
+            .unwrap();
+        assert_eq!(action, CheckpointRestoreAction::Restored);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "checkpoint content");
+    }
+
+    #[tokio::test]
+    async fn test_restore_checkpoint_file_removes_new_file() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("created.txt");
+        let target_str = target.to_string_lossy().to_string();
+        let lines = vec![snapshot_line(
+            "msg-1",
+            &serde_json::json!({ target_str.clone(): {"backupFileName": null, "version": 1} }),
+            false,
+        )];
+        let (session_path, _) = setup_session(&temp, &lines);
+        fs::write(&target, "created after checkpoint").unwrap();
+
+        let action = restore_checkpoint_file(
+            session_path.to_string_lossy().to_string(),
+            "msg-1".to_string(),
+            target_str,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(action, CheckpointRestoreAction::Removed);
+        assert!(!target.exists());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_file_content_binary() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("logo.png");
+        let target_str = target.to_string_lossy().to_string();
+        let lines = vec![snapshot_line(
+            "msg-1",
+            &serde_json::json!({ target_str.clone(): {"backupFileName": "png@v1", "version": 1} }),
+            false,
+        )];
+        let (session_path, history_dir) = setup_session(&temp, &lines);
+        fs::write(
+            history_dir.join("png@v1"),
+            [0x89, b'P', b'N', b'G', 0xff, 0x00],
+        )
+        .unwrap();
+
+        let content = get_checkpoint_file_content(
+            session_path.to_string_lossy().to_string(),
+            "msg-1".to_string(),
+            target_str,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(content.is_binary);
+        assert!(content.content.contains("PNG"));
+    }
+
+    #[test]
+    fn test_is_safe_backup_name() {
+        assert!(is_safe_backup_name("3f2a9c@v2"));
+        assert!(!is_safe_backup_name("../secrets"));
+        assert!(!is_safe_backup_name(".."));
+        assert!(!is_safe_backup_name(""));
+    }
+}
@@ -0,0 +1,342 @@
+//! Disk usage breakdown of the projects folder
+
+use crate::commands::archive::{is_session_file, read_session_bytes};
+use crate::models::{
+    ContentBreakdown, DiskUsageReport, LargeLine, ProjectDiskUsage, SessionDiskUsage,
+};
+use crate::utils::{extract_project_name, find_line_ranges};
+use rayon::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Number of largest lines reported when the caller does not ask for more
+const DEFAULT_TOP_LINES: usize = 20;
+
+#[derive(Clone, Copy)]
+enum Category {
+    Text,
+    Thinking,
+    ToolCalls,
+    ToolResults,
+    Images,
+}
+
+fn bucket(breakdown: &mut ContentBreakdown, category: Category) -> &mut u64 {
+    match category {
+        Category::Text => &mut breakdown.text,
+        Category::Thinking => &mut breakdown.thinking,
+        Category::ToolCalls => &mut breakdown.tool_calls,
+        Category::ToolResults => &mut breakdown.tool_results,
+        Category::Images => &mut breakdown.images,
+    }
+}
+
+/// Add the string bytes under `value` to `category`, switching category at typed content blocks
+fn tally(value: &Value, category: Category, breakdown: &mut ContentBreakdown) {
+    match value {
+        Value::String(s) => *bucket(breakdown, category) += s.len() as u64,
+        Value::Array(items) => {
+            for item in items {
+                tally(item, category, breakdown);
+            }
+        }
+        Value::Object(map) => {
+            let category = match map.get("type").and_then(Value::as_str) {
+                Some("image") => Category::Images,
+                Some("thinking" | "redacted_thinking") => Category::Thinking,
+                Some("tool_use") => Category::ToolCalls,
+                Some("tool_result") => Category::ToolResults,
+                _ => category,
+            };
+            for (key, item) in map {
+                if key != "type" {
+                    tally(item, category, breakdown);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Break one JSONL line down by content; unparseable lines count as `other`
+fn analyze_line(
+    line: &[u8],
+    parse_buffer: &mut Vec<u8>,
+) -> (ContentBreakdown, String, Option<String>) {
+    let len = line.len() as u64;
+    let mut breakdown = ContentBreakdown::default();
+
+    parse_buffer.clear();
+    parse_buffer.extend_from_slice(line);
+    let Ok(entry) = simd_json::serde::from_slice::<Value>(parse_buffer) else {
+        breakdown.other = len;
+        return (breakdown, String::new(), None);
+    };
+
+    let message_type = entry
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let timestamp = entry
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    if message_type == "file-history-snapshot" {
+        breakdown.snapshots = len;
+    } else {
+        if let Some(content) = entry.pointer("/message/content") {
+            tally(content, Category::Text, &mut breakdown);
+        }
+        if let Some(result) = entry.get("toolUseResult") {
+            tally(result, Category::ToolResults, &mut breakdown);
+        }
+        breakdown.other = len.saturating_sub(breakdown.total());
+    }
+    (breakdown, message_type, timestamp)
+}
+
+/// Keep only the `limit` largest lines
+fn keep_largest(lines: &mut Vec<LargeLine>, limit: usize) {
+    lines.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+    lines.truncate(limit);
+}
+
+/// Analyze one session file, returning its usage and its largest lines
+fn analyze_file(
+    path: &Path,
+    project_name: &str,
+    top_lines: usize,
+) -> Option<(SessionDiskUsage, Vec<LargeLine>)> {
+    let disk_bytes = fs::metadata(path).ok()?.len();
+    let contents = read_session_bytes(path).ok()?;
+    let total_bytes = contents.len() as u64;
+    let file_path = path.to_string_lossy().to_string();
+
+    let mut breakdown = ContentBreakdown::default();
+    let mut largest = Vec::new();
+    let mut parse_buffer = Vec::new();
+    let line_ranges = find_line_ranges(&contents);
+
+    for (index, &(start, end)) in line_ranges.iter().enumerate() {
+        let line = &contents[start..end];
+        let (line_breakdown, message_type, timestamp) = analyze_line(line, &mut parse_buffer);
+        breakdown.add(&line_breakdown);
+
+        if top_lines > 0 {
+            largest.push(LargeLine {
+                project_name: project_name.to_string(),
+                file_path: file_path.clone(),
+                line_number: index + 1,
+                bytes: line.len() as u64,
+                message_type,
+                timestamp,
+                dominant_content: line_breakdown.dominant().to_string(),
+            });
+            if largest.len() >= top_lines * 2 {
+                keep_largest(&mut largest, top_lines);
+            }
+        }
+    }
+    keep_largest(&mut largest, top_lines);
+
+    // Newlines go to `other` so the categories add up to the transcript size
+    breakdown.other = (breakdown.other + total_bytes).saturating_sub(breakdown.total());
+
+    Some((
+        SessionDiskUsage {
+            file_path,
+            total_bytes,
+            disk_bytes,
+            line_count: line_ranges.len(),
+            breakdown,
+        },
+        largest,
+    ))
+}
+
+/// Walk every project under `projects_path` and break its disk usage down
+fn analyze_projects(projects_path: &Path, top_lines: usize) -> DiskUsageReport {
+    let mut projects = Vec::new();
+    let mut session_paths: Vec<(usize, PathBuf)> = Vec::new();
+
+    for project in WalkDir::new(projects_path)
+        .min_depth(1)
+        .max_depth(1)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_dir())
+    {
+        let index = projects.len();
+        let mut other_files_bytes = 0;
+        for entry in WalkDir::new(project.path())
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+        {
+            if is_session_file(entry.path()) {
+                session_paths.push((index, entry.path().to_path_buf()));
+            } else {
+                other_files_bytes += entry.metadata().map_or(0, |m| m.len());
+            }
+        }
+        projects.push(ProjectDiskUsage {
+            name: extract_project_name(&project.file_name().to_string_lossy()),
+            path: project.path().to_string_lossy().to_string(),
+            total_bytes: other_files_bytes,
+            disk_bytes: other_files_bytes,
+            other_files_bytes,
+            breakdown: ContentBreakdown::default(),
+            sessions: Vec::new(),
+        });
+    }
+
+    let results: Vec<(usize, SessionDiskUsage, Vec<LargeLine>)> = session_paths
+        .par_iter()
+        .filter_map(|(index, path)| {
+            let (usage, largest) = analyze_file(path, &projects[*index].name, top_lines)?;
+            Some((*index, usage, largest))
+        })
+        .collect();
+
+    let mut report = DiskUsageReport::default();
+    for (index, usage, largest) in results {
+        let project = &mut projects[index];
+        project.total_bytes += usage.total_bytes;
+        project.disk_bytes += usage.disk_bytes;
+        project.breakdown.add(&usage.breakdown);
+        project.sessions.push(usage);
+        report.largest_lines.extend(largest);
+    }
+    keep_largest(&mut report.largest_lines, top_lines);
+
+    for project in &mut projects {
+        project
+            .sessions
+            .sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+        report.total_bytes += project.total_bytes;
+        report.disk_bytes += project.disk_bytes;
+        report.breakdown.add(&project.breakdown);
+    }
+    projects.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+    report.projects = projects;
+    report
+}
+
+/// Bytes per project and session, by content type, plus the largest lines
+#[tauri::command]
+pub async fn get_disk_usage(
+    claude_path: String,
+    top_lines: Option<usize>,
+) -> Result<DiskUsageReport, String> {
+    let projects_path = PathBuf::from(&claude_path).join("projects");
+    if !projects_path.exists() {
+        return Ok(DiskUsageReport::default());
+    }
+    let top_lines = top_lines.unwrap_or(DEFAULT_TOP_LINES);
+
+    tauri::async_runtime::spawn_blocking(move || analyze_projects(&projects_path, top_lines))
+        .await
+        .map_err(|e| format!("Task join error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_project(projects: &Path) -> PathBuf {
+        let project = projects.join("-Users-me-app");
+        fs::create_dir_all(&project).unwrap();
+        let image = "A".repeat(5000);
+        let lines = [
+            r#"{"type":"user","timestamp":"2025-01-01T00:00:00Z","message":{"role":"user","content":"hello there"}}"#.to_string(),
+            r#"{"type":"assistant","timestamp":"2025-01-01T00:00:01Z","message":{"role":"assistant","content":[{"type":"thinking","thinking":"let me think about it"},{"type":"tool_use","id":"t1","name":"Read","input":{"file_path":"/work/app/logo.png"}}]}}"#.to_string(),
+            format!(
+                r#"{{"type":"user","timestamp":"2025-01-01T00:00:02Z","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"t1","content":[{{"type":"image","source":{{"type":"base64","media_type":"image/png","data":"{image}"}}}}]}}]}},"toolUseResult":{{"type":"image","file":{{"base64":"{image}"}}}}}}"#
+            ),
+            r#"{"type":"user","timestamp":"2025-01-01T00:00:03Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t2","content":"file contents here"}]},"toolUseResult":"file contents here"}"#.to_string(),
+            r#"{"type":"file-history-snapshot","messageId":"m1","snapshot":{"trackedFileBackups":{}}}"#.to_string(),
+            "not json".to_string(),
+        ];
+        fs::write(project.join("session-1.jsonl"), lines.join("\n") + "\n").unwrap();
+        fs::write(project.join("sessions-index.json"), "{}").unwrap();
+        project
+    }
+
+    #[test]
+    fn test_breakdown_by_content_type() {
+        let temp = TempDir::new().unwrap();
+        let projects = temp.path().join("projects");
+        write_project(&projects);
+
+        let report = analyze_projects(&projects, 3);
+        assert_eq!(report.projects.len(), 1);
+        let project = &report.projects[0];
+        assert_eq!(project.other_files_bytes, 2);
+        assert_eq!(project.sessions.len(), 1);
+
+        let session = &project.sessions[0];
+        assert_eq!(session.line_count, 6);
+        let b = &session.breakdown;
+        assert_eq!(b.text, "hello there".len() as u64);
+        assert_eq!(b.thinking, "let me think about it".len() as u64);
+        assert_eq!(b.tool_calls, "t1Read/work/app/logo.png".len() as u64);
+        assert_eq!(b.images, 2 * 5000 + "image/png".len() as u64);
+        assert_eq!(b.tool_results, 2 * "file contents here".len() as u64 + 4);
+        assert!(b.snapshots > 0);
+        // Every byte of the file is attributed to exactly one category
+        assert_eq!(b.total(), session.total_bytes);
+        assert_eq!(project.total_bytes, session.total_bytes + 2);
+        assert_eq!(report.total_bytes, project.total_bytes);
+        assert_eq!(session.disk_bytes, session.total_bytes);
+    }
+
+    #[test]
+    fn test_archived_session_sizes() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let temp = TempDir::new().unwrap();
+        let projects = temp.path().join("projects");
+        let session = write_project(&projects).join("session-1.jsonl");
+        let contents = fs::read(&session).unwrap();
+        let mut encoder = GzEncoder::new(
+            fs::File::create(session.with_extension("jsonl.gz")).unwrap(),
+            Compression::default(),
+        );
+        encoder.write_all(&contents).unwrap();
+        encoder.finish().unwrap();
+        fs::remove_file(&session).unwrap();
+
+        let report = analyze_projects(&projects, 3);
+        let session = &report.projects[0].sessions[0];
+        assert_eq!(session.total_bytes, contents.len() as u64);
+        assert_eq!(session.breakdown.total(), session.total_bytes);
+        assert!(session.disk_bytes < session.total_bytes);
+        assert_eq!(report.disk_bytes, session.disk_bytes + 2);
+    }
+
+    #[test]
+    fn test_largest_lines() {
+        let temp = TempDir::new().unwrap();
+        let projects = temp.path().join("projects");
+        write_project(&projects);
+
+        let report = analyze_projects(&projects, 3);
+        assert_eq!(report.largest_lines.len(), 3);
+        let top = &report.largest_lines[0];
+        assert_eq!(top.line_number, 3);
+        assert_eq!(top.dominant_content, "images");
+        assert_eq!(top.project_name, "app");
+        assert_eq!(top.timestamp.as_deref(), Some("2025-01-01T00:00:02Z"));
+        assert!(report
+            .largest_lines
+            .windows(2)
+            .all(|pair| pair[0].bytes >= pair[1].bytes));
+    }
+}
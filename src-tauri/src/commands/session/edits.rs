@@ -1,38 +1,207 @@
 //! File edit and restore functions
 
-use crate::models::{RawLogEntry, RecentFileEdit};
+use super::notebook::{replay_notebook_edit, NotebookEditOperation};
+use super::replay::{replay_edits, replay_status, EditOperation};
+use super::shell::extract_shell_modified_paths;
+use crate::commands::archive::read_session_bytes;
+use crate::commands::roots::{session_read_roots, validate_session_path};
+use crate::models::{
+    CwdScope, EditSource, RawLogEntry, RecentEditsFilter, RecentFileEdit, ReplayStatus,
+    ShellModifiedFile,
+};
 use crate::utils::find_line_ranges;
-use memmap2::Mmap;
+use chrono::{DateTime, FixedOffset};
+use glob::{MatchOptions, Pattern};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 /// Intermediate result from processing a single session file (for parallel processing)
 struct SessionEditsResult {
     edits: Vec<RecentFileEdit>,
+    shell_modified: Vec<ShellModifiedFile>,
     cwd_counts: HashMap<String, usize>,
 }
 
-/// Process a single session file and extract edit information
-#[allow(unsafe_code)] // Required for mmap performance optimization
-fn process_session_file_for_edits(file_path: &PathBuf) -> Option<SessionEditsResult> {
-    let file = fs::File::open(file_path).ok()?;
+/// How much of each edit a session scan keeps
+enum ContentMode<'a> {
+    /// Keep metadata only; file bodies are dropped as soon as they are measured
+    Metadata,
+    /// Keep full bodies, but only for the listed `(line_offset, file_path)` edits
+    Selected(&'a HashSet<(usize, String)>),
+}
+
+impl ContentMode<'_> {
+    fn keeps_bodies(&self) -> bool {
+        matches!(self, Self::Selected(_))
+    }
+}
+
+/// Bash commands issued by this entry, from `message.content` and `toolUse`
+fn bash_commands(log_entry: &RawLogEntry) -> Vec<&str> {
+    let is_bash = |tool_use: &serde_json::Value| {
+        tool_use.get("name").and_then(|v| v.as_str()) == Some("Bash")
+    };
+    fn command(tool_use: &serde_json::Value) -> Option<&str> {
+        tool_use
+            .get("input")
+            .and_then(|input| input.get("command"))
+            .and_then(|v| v.as_str())
+    }
+
+    let mut commands = Vec::new();
+    if let Some(items) = log_entry
+        .message
+        .as_ref()
+        .and_then(|m| m.content.as_array())
+    {
+        commands.extend(
+            items
+                .iter()
+                .filter(|item| item.get("type").and_then(|v| v.as_str()) == Some("tool_use"))
+                .filter(|item| is_bash(item))
+                .filter_map(command),
+        );
+    }
+    if let Some(tool_use) = log_entry.tool_use.as_ref().filter(|t| is_bash(t)) {
+        commands.extend(command(tool_use));
+    }
+    commands
+}
 
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. Session files are append-only.
-    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+/// Reconstructed notebook content for a single `NotebookEdit`
+struct NotebookRecovery {
+    content: String,
+    original: Option<String>,
+    lines_added: usize,
+    lines_removed: usize,
+    status: ReplayStatus,
+}
+
+/// Whether a tool result records a failed call
+fn tool_failed(tool_use_result: &serde_json::Value) -> bool {
+    tool_use_result
+        .get("error")
+        .and_then(|v| v.as_str())
+        .is_some_and(|e| !e.is_empty())
+}
+
+/// Short description of a `NotebookEdit`, e.g. `NotebookEdit insert cell a1`
+fn notebook_edit_summary(tool_use_result: &serde_json::Value) -> String {
+    let mode = tool_use_result
+        .get("edit_mode")
+        .and_then(|v| v.as_str())
+        .unwrap_or("replace");
+    let cell = tool_use_result
+        .get("cell_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| {
+            tool_use_result
+                .get("cell_number")
+                .and_then(serde_json::Value::as_u64)
+                .map(|n| n.to_string())
+        });
+    match cell {
+        Some(cell) => format!("NotebookEdit {mode} cell {cell}"),
+        None => format!("NotebookEdit {mode}"),
+    }
+}
+
+/// Rebuild a notebook from a successful `NotebookEdit` tool result
+///
+/// The edit is replayed on `original_file` when recorded, otherwise on the
+/// notebook content last seen in this session. A recorded `updated_file` is
+/// authoritative and is used to verify the replay.
+fn notebook_edit_from_result(
+    tool_use_result: &serde_json::Value,
+    previous: Option<&String>,
+) -> Option<NotebookRecovery> {
+    let original = tool_use_result
+        .get("original_file")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| previous.cloned());
+    let updated = tool_use_result.get("updated_file").and_then(|v| v.as_str());
+    let outcome = NotebookEditOperation::from_value(tool_use_result)
+        .zip(original.as_deref())
+        .and_then(|(op, original)| replay_notebook_edit(original, &op));
+
+    let (content, lines_added, lines_removed, status) = match (outcome, updated) {
+        (Some(outcome), Some(updated)) => {
+            // Compare parsed JSON so formatting differences don't count
+            let matches = serde_json::from_str::<serde_json::Value>(updated).ok()
+                == serde_json::from_str::<serde_json::Value>(&outcome.content).ok();
+            let status = if matches {
+                ReplayStatus::Verified
+            } else {
+                ReplayStatus::Mismatched
+            };
+            (
+                updated.to_string(),
+                outcome.lines_added,
+                outcome.lines_removed,
+                status,
+            )
+        }
+        (Some(outcome), None) => (
+            outcome.content,
+            outcome.lines_added,
+            outcome.lines_removed,
+            ReplayStatus::Unverified,
+        ),
+        (None, Some(updated)) => (updated.to_string(), 0, 0, ReplayStatus::Verified),
+        (None, None) => return None,
+    };
+
+    Some(NotebookRecovery {
+        content,
+        original,
+        lines_added,
+        lines_removed,
+        status,
+    })
+}
+
+/// Metadata for a notebook edit whose base body the metadata pass did not keep
+///
+/// Only the new cell source is counted; loading the edit replays it in full.
+fn unkept_notebook_edit(tool_use_result: &serde_json::Value) -> Option<NotebookRecovery> {
+    let operation = NotebookEditOperation::from_value(tool_use_result)?;
+    Some(NotebookRecovery {
+        content: String::new(),
+        original: None,
+        lines_added: operation.new_source.lines().count(),
+        lines_removed: 0,
+        status: ReplayStatus::Unverified,
+    })
+}
+
+/// Process a single session file and extract edit information
+///
+/// Every edit is replayed so metadata stays accurate (notebook edits chain on
+/// earlier ones), but bodies are only retained as `mode` allows.
+fn process_session_file_for_edits(
+    file_path: &PathBuf,
+    mode: &ContentMode<'_>,
+) -> Option<SessionEditsResult> {
+    let bytes = read_session_bytes(file_path).ok()?;
 
     let mut edits: Vec<RecentFileEdit> = Vec::with_capacity(16);
+    let mut shell_modified: Vec<ShellModifiedFile> = Vec::new();
     let mut cwd_counts: HashMap<String, usize> = HashMap::new();
+    // Latest known notebook bodies, so consecutive NotebookEdits can chain;
+    // `None` marks a notebook whose body the metadata pass did not keep
+    let mut notebook_contents: HashMap<String, Option<String>> = HashMap::new();
 
     // Use SIMD-accelerated line detection
-    let line_ranges = find_line_ranges(&mmap);
+    let line_ranges = find_line_ranges(&bytes);
 
     for (start, end) in line_ranges {
         // simd-json requires mutable slice
-        let mut line_bytes = mmap[start..end].to_vec();
+        let mut line_bytes = bytes[start..end].to_vec();
 
         let log_entry: RawLogEntry = match simd_json::serde::from_slice(&mut line_bytes) {
             Ok(entry) => entry,
@@ -52,6 +221,8 @@ fn process_session_file_for_edits(file_path: &PathBuf) -> Option<SessionEditsRes
             *cwd_counts.entry(cwd_path.clone()).or_insert(0) += 1;
         }
 
+        let line_edits_start = edits.len();
+
         // Process tool use results for Edit and Write operations
         if let Some(tool_use_result) = &log_entry.tool_use_result {
             // Handle Write/Create tool results (type: "create")
@@ -60,6 +231,15 @@ fn process_session_file_for_edits(file_path: &PathBuf) -> Option<SessionEditsRes
                     tool_use_result.get("filePath").and_then(|v| v.as_str()),
                     tool_use_result.get("content").and_then(|v| v.as_str()),
                 ) {
+                    let is_notebook = Path::new(file_path_str)
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("ipynb"));
+                    if is_notebook {
+                        notebook_contents.insert(
+                            file_path_str.to_string(),
+                            mode.keeps_bodies().then(|| content.to_string()),
+                        );
+                    }
                     edits.push(RecentFileEdit {
                         file_path: file_path_str.to_string(),
                         timestamp: timestamp.clone(),
@@ -70,74 +250,115 @@ fn process_session_file_for_edits(file_path: &PathBuf) -> Option<SessionEditsRes
                         lines_added: content.lines().count(),
                         lines_removed: 0,
                         cwd: cwd.clone(),
+                        replay_status: ReplayStatus::Verified,
+                        failed_replacements: 0,
+                        source: None,
                     });
                 }
             }
 
             // Handle Edit tool results
-            if let Some(file_path_val) = tool_use_result.get("filePath") {
-                if let Some(file_path_str) = file_path_val.as_str() {
+            if let Some(file_path_str) = tool_use_result.get("filePath").and_then(|v| v.as_str()) {
+                // MultiEdit records `originalFileContents` in newer builds
+                let original = tool_use_result
+                    .get("originalFile")
+                    .or_else(|| tool_use_result.get("originalFileContents"))
+                    .and_then(|v| v.as_str());
+
+                let operations: Option<Vec<EditOperation>> =
                     if let Some(edits_arr_val) = tool_use_result.get("edits") {
                         // Multi-edit format
-                        if let Some(original) =
-                            tool_use_result.get("originalFile").and_then(|v| v.as_str())
-                        {
-                            let mut content = original.to_string();
-                            let mut lines_added = 0usize;
-                            let mut lines_removed = 0usize;
-
-                            if let Some(edits_arr) = edits_arr_val.as_array() {
-                                for edit in edits_arr {
-                                    if let (Some(old_str), Some(new_str)) = (
-                                        edit.get("old_string").and_then(|v| v.as_str()),
-                                        edit.get("new_string").and_then(|v| v.as_str()),
-                                    ) {
-                                        content = content.replacen(old_str, new_str, 1);
-                                        lines_removed += old_str.lines().count();
-                                        lines_added += new_str.lines().count();
-                                    }
-                                }
-                            }
-
-                            edits.push(RecentFileEdit {
-                                file_path: file_path_str.to_string(),
-                                timestamp: timestamp.clone(),
-                                session_id: session_id.clone(),
-                                operation_type: "edit".to_string(),
-                                content_after_change: content,
-                                original_content: Some(original.to_string()),
-                                lines_added,
-                                lines_removed,
-                                cwd: cwd.clone(),
-                            });
-                        }
-                    } else if let (Some(old_str), Some(new_str)) = (
-                        tool_use_result.get("oldString").and_then(|v| v.as_str()),
-                        tool_use_result.get("newString").and_then(|v| v.as_str()),
-                    ) {
+                        edits_arr_val.as_array().map(|edits_arr| {
+                            edits_arr
+                                .iter()
+                                .filter_map(EditOperation::from_multi_edit_item)
+                                .collect()
+                        })
+                    } else {
                         // Single edit format
-                        if let Some(original) =
-                            tool_use_result.get("originalFile").and_then(|v| v.as_str())
-                        {
-                            let content = original.replacen(old_str, new_str, 1);
+                        EditOperation::from_edit_result(tool_use_result).map(|op| vec![op])
+                    };
 
-                            edits.push(RecentFileEdit {
-                                file_path: file_path_str.to_string(),
-                                timestamp: timestamp.clone(),
-                                session_id: session_id.clone(),
-                                operation_type: "edit".to_string(),
-                                content_after_change: content,
-                                original_content: Some(original.to_string()),
-                                lines_added: new_str.lines().count(),
-                                lines_removed: old_str.lines().count(),
-                                cwd: cwd.clone(),
-                            });
-                        }
-                    }
+                if let (Some(original), Some(operations)) = (original, operations) {
+                    let outcome = replay_edits(original, &operations);
+                    let status = replay_status(&outcome, tool_use_result.get("structuredPatch"));
+
+                    edits.push(RecentFileEdit {
+                        file_path: file_path_str.to_string(),
+                        timestamp: timestamp.clone(),
+                        session_id: session_id.clone(),
+                        operation_type: "edit".to_string(),
+                        content_after_change: outcome.content,
+                        original_content: Some(original.to_string()),
+                        lines_added: outcome.lines_added,
+                        lines_removed: outcome.lines_removed,
+                        cwd: cwd.clone(),
+                        replay_status: status,
+                        failed_replacements: outcome.failed.len(),
+                        source: None,
+                    });
+                }
+            }
+
+            // Handle NotebookEdit tool results
+            if let Some(notebook_path) = tool_use_result
+                .get("notebook_path")
+                .and_then(|v| v.as_str())
+                .filter(|_| !tool_failed(tool_use_result))
+            {
+                let recovery = match notebook_contents.get(notebook_path) {
+                    Some(None) => notebook_edit_from_result(tool_use_result, None)
+                        .or_else(|| unkept_notebook_edit(tool_use_result)),
+                    previous => notebook_edit_from_result(
+                        tool_use_result,
+                        previous.and_then(Option::as_ref),
+                    ),
+                };
+                if let Some(recovery) = recovery {
+                    notebook_contents.insert(
+                        notebook_path.to_string(),
+                        mode.keeps_bodies().then(|| recovery.content.clone()),
+                    );
+                    edits.push(RecentFileEdit {
+                        file_path: notebook_path.to_string(),
+                        timestamp: timestamp.clone(),
+                        session_id: session_id.clone(),
+                        operation_type: "notebook".to_string(),
+                        content_after_change: recovery.content,
+                        original_content: recovery.original,
+                        lines_added: recovery.lines_added,
+                        lines_removed: recovery.lines_removed,
+                        cwd: cwd.clone(),
+                        replay_status: recovery.status,
+                        failed_replacements: 0,
+                        source: None,
+                    });
+                } else {
+                    // Nothing to replay on: list it with the changes we cannot recover
+                    shell_modified.push(ShellModifiedFile {
+                        file_path: notebook_path.to_string(),
+                        timestamp: timestamp.clone(),
+                        session_id: session_id.clone(),
+                        command: notebook_edit_summary(tool_use_result),
+                        cwd: cwd.clone(),
+                    });
                 }
             }
         }
 
+        // Bash commands can change files we have no content for
+        for command in bash_commands(&log_entry) {
+            for path in extract_shell_modified_paths(command, cwd.as_deref()) {
+                shell_modified.push(ShellModifiedFile {
+                    file_path: path,
+                    timestamp: timestamp.clone(),
+                    session_id: session_id.clone(),
+                    command: command.to_string(),
+                    cwd: cwd.clone(),
+                });
+            }
+        }
+
         // Also check tool_use for Write operations
         if let Some(tool_use) = &log_entry.tool_use {
             if let Some(name) = tool_use.get("name").and_then(|v| v.as_str()) {
@@ -157,15 +378,151 @@ fn process_session_file_for_edits(file_path: &PathBuf) -> Option<SessionEditsRes
                                 lines_added: content.lines().count(),
                                 lines_removed: 0,
                                 cwd: cwd.clone(),
+                                replay_status: ReplayStatus::Verified,
+                                failed_replacements: 0,
+                                source: None,
                             });
                         }
                     }
                 }
             }
         }
+
+        // Tag this line's edits with their source and drop unwanted bodies
+        let line_edits = edits.split_off(line_edits_start);
+        for mut edit in line_edits {
+            match mode {
+                ContentMode::Metadata => {
+                    edit.content_after_change = String::new();
+                    edit.original_content = None;
+                }
+                ContentMode::Selected(wanted) => {
+                    if !wanted.contains(&(start, edit.file_path.clone())) {
+                        continue;
+                    }
+                }
+            }
+            edit.source = Some(EditSource {
+                session_file: file_path.to_string_lossy().to_string(),
+                line_offset: start,
+            });
+            edits.push(edit);
+        }
+    }
+
+    Some(SessionEditsResult {
+        edits,
+        shell_modified,
+        cwd_counts,
+    })
+}
+
+/// `RecentEditsFilter` with timestamps and globs parsed up front
+struct CompiledEditsFilter {
+    session_ids: HashSet<String>,
+    since: Option<DateTime<FixedOffset>>,
+    until: Option<DateTime<FixedOffset>>,
+    include_globs: Vec<Pattern>,
+    exclude_globs: Vec<Pattern>,
+    operation_types: HashSet<String>,
+}
+
+impl CompiledEditsFilter {
+    fn new(filter: &RecentEditsFilter) -> Result<Self, String> {
+        let parse_time = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|v| {
+                    DateTime::parse_from_rfc3339(v)
+                        .map_err(|e| format!("Invalid timestamp '{v}': {e}"))
+                })
+                .transpose()
+        };
+        let parse_globs = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| Pattern::new(p).map_err(|e| format!("Invalid glob '{p}': {e}")))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            session_ids: filter.session_ids.iter().cloned().collect(),
+            since: parse_time(&filter.since)?,
+            until: parse_time(&filter.until)?,
+            include_globs: parse_globs(&filter.include_globs)?,
+            exclude_globs: parse_globs(&filter.exclude_globs)?,
+            operation_types: filter.operation_types.iter().cloned().collect(),
+        })
+    }
+
+    fn matches_time(&self, timestamp: &str) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Ok(time) = DateTime::parse_from_rfc3339(timestamp) else {
+            return false;
+        };
+        self.since.map_or(true, |since| time >= since)
+            && self.until.map_or(true, |until| time <= until)
     }
 
-    Some(SessionEditsResult { edits, cwd_counts })
+    fn matches_path(&self, file_path: &str, cwd: Option<&str>) -> bool {
+        let options = MatchOptions {
+            case_sensitive: !cfg!(target_os = "windows"),
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        let relative = cwd.and_then(|cwd| {
+            Path::new(file_path)
+                .strip_prefix(cwd)
+                .ok()
+                .map(|p| p.to_string_lossy().to_string())
+        });
+        let matches = |pattern: &Pattern| {
+            pattern.matches_with(file_path, options)
+                || relative
+                    .as_deref()
+                    .is_some_and(|rel| pattern.matches_with(rel, options))
+        };
+
+        (self.include_globs.is_empty() || self.include_globs.iter().any(matches))
+            && !self.exclude_globs.iter().any(matches)
+    }
+
+    fn matches_entry(
+        &self,
+        session_id: &str,
+        timestamp: &str,
+        file_path: &str,
+        cwd: Option<&str>,
+    ) -> bool {
+        (self.session_ids.is_empty() || self.session_ids.contains(session_id))
+            && self.matches_time(timestamp)
+            && self.matches_path(file_path, cwd)
+    }
+
+    fn matches_edit(&self, edit: &RecentFileEdit) -> bool {
+        (self.operation_types.is_empty() || self.operation_types.contains(&edit.operation_type))
+            && self.matches_entry(
+                &edit.session_id,
+                &edit.timestamp,
+                &edit.file_path,
+                edit.cwd.as_deref(),
+            )
+    }
+}
+
+/// Whether `file_path` is `dir` or lies beneath it
+///
+/// Uses case-insensitive comparison on Windows for path matching
+fn is_under_dir(file_path: &str, dir: &str) -> bool {
+    #[cfg(target_os = "windows")]
+    let (file_path, dir) = (file_path.to_lowercase(), dir.to_lowercase());
+
+    let dir = dir.trim_end_matches(['/', '\\']);
+    file_path
+        .strip_prefix(dir)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '\\']))
 }
 
 /// Paginated response for recent edits
@@ -175,6 +532,10 @@ pub struct PaginatedRecentEdits {
     pub total_edits_count: usize,
     pub unique_files_count: usize,
     pub project_cwd: Option<String>,
+    /// Latest Bash command per path that may have modified it; not restorable
+    pub shell_modified_files: Vec<ShellModifiedFile>,
+    /// Files that matched the filter but lie outside the `cwd_scope`
+    pub excluded_by_cwd: Vec<String>,
     pub offset: usize,
     pub limit: usize,
     pub has_more: bool,
@@ -182,71 +543,129 @@ pub struct PaginatedRecentEdits {
 
 /// Scan all JSONL files in a project and extract recent file edits/writes
 /// Returns the LATEST content for each unique file path, sorted by timestamp descending
-/// Only includes files that belong to the project's working directory
-/// Supports pagination with offset and limit parameters
+/// Only includes files inside the `cwd_scope` (the most common working directory by default)
+/// Supports pagination with offset and limit parameters and an optional filter
 #[tauri::command]
 pub async fn get_recent_edits(
     project_path: String,
     offset: Option<usize>,
     limit: Option<usize>,
+    filter: Option<RecentEditsFilter>,
+) -> Result<PaginatedRecentEdits, String> {
+    collect_recent_edits(&[PathBuf::from(project_path)], offset, limit, filter)
+}
+
+/// Recent edits across one or more project folders
+///
+/// With the default `cwd_scope`, each folder contributes the edits under its
+/// own most common working directory; `project_cwd` is the first one found.
+pub(crate) fn collect_recent_edits(
+    project_dirs: &[PathBuf],
+    offset: Option<usize>,
+    limit: Option<usize>,
+    filter: Option<RecentEditsFilter>,
 ) -> Result<PaginatedRecentEdits, String> {
     let offset = offset.unwrap_or(0);
     let limit = limit.unwrap_or(20);
+    let filter = filter.unwrap_or_default();
+    let compiled_filter = CompiledEditsFilter::new(&filter)?;
     // Phase 1: Collect all session files
-    let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
-        .map(|e| e.path().to_path_buf())
+    let session_files: Vec<(usize, PathBuf)> = project_dirs
+        .iter()
+        .enumerate()
+        .flat_map(|(index, dir)| {
+            WalkDir::new(dir)
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+                .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+                .map(move |e| (index, e.path().to_path_buf()))
+        })
         .collect();
 
-    // Phase 2: Process files in parallel
-    let file_results: Vec<SessionEditsResult> = session_files
+    // Phase 2: Process files in parallel, keeping only edit metadata
+    let file_results: Vec<(usize, SessionEditsResult)> = session_files
         .par_iter()
-        .filter_map(process_session_file_for_edits)
+        .filter_map(|(index, path)| {
+            Some((
+                *index,
+                process_session_file_for_edits(path, &ContentMode::Metadata)?,
+            ))
+        })
         .collect();
 
     // Phase 3: Aggregate results with pre-allocated capacity
-    let total_edits_estimate: usize = file_results.iter().map(|r| r.edits.len()).sum();
+    let total_edits_estimate: usize = file_results.iter().map(|(_, r)| r.edits.len()).sum();
     let mut all_edits: Vec<RecentFileEdit> = Vec::with_capacity(total_edits_estimate);
-    let mut cwd_counts: HashMap<String, usize> = HashMap::new();
+    let mut all_shell_modified: Vec<ShellModifiedFile> = Vec::new();
+    let mut cwd_counts: Vec<HashMap<String, usize>> = vec![HashMap::new(); project_dirs.len()];
 
-    for result in file_results {
+    for (index, result) in file_results {
         all_edits.extend(result.edits);
+        all_shell_modified.extend(result.shell_modified);
         for (cwd, count) in result.cwd_counts {
-            *cwd_counts.entry(cwd).or_insert(0) += count;
+            *cwd_counts[index].entry(cwd).or_insert(0) += count;
         }
     }
 
-    // Find the most common cwd (project directory)
-    let project_cwd = cwd_counts
+    // Find the most common cwd (project directory) of each folder
+    let project_cwds: Vec<String> = cwd_counts
         .into_iter()
-        .max_by_key(|(_, count)| *count)
-        .map(|(cwd, _)| cwd);
-
-    // Filter edits to only include files within the project directory
-    // Use case-insensitive comparison on Windows for path matching
-    let filtered_edits: Vec<RecentFileEdit> = if let Some(ref cwd) = project_cwd {
-        #[cfg(target_os = "windows")]
-        let cwd_normalized = cwd.to_lowercase();
-        #[cfg(not(target_os = "windows"))]
-        let cwd_normalized = cwd.clone();
-
-        all_edits
-            .into_iter()
-            .filter(|edit| {
-                #[cfg(target_os = "windows")]
-                let file_path_normalized = edit.file_path.to_lowercase();
-                #[cfg(not(target_os = "windows"))]
-                let file_path_normalized = edit.file_path.clone();
-
-                file_path_normalized.starts_with(&cwd_normalized)
-            })
-            .collect()
-    } else {
-        all_edits
+        .filter_map(|counts| {
+            counts
+                .into_iter()
+                .max_by_key(|(_, count)| *count)
+                .map(|(cwd, _)| cwd)
+        })
+        .collect();
+    let project_cwd = project_cwds.first().cloned();
+
+    // Restrict to the requested working directories
+    let scope_dirs: Option<Vec<&str>> = match &filter.cwd_scope {
+        CwdScope::MostCommon => {
+            (!project_cwds.is_empty()).then(|| project_cwds.iter().map(String::as_str).collect())
+        }
+        CwdScope::All => None,
+        CwdScope::Explicit { cwds } => Some(cwds.iter().map(String::as_str).collect()),
+    };
+    let in_scope = |file_path: &str| {
+        scope_dirs.as_ref().map_or(true, |dirs| {
+            dirs.iter().any(|dir| is_under_dir(file_path, dir))
+        })
     };
 
+    let mut excluded_by_cwd: BTreeSet<String> = BTreeSet::new();
+    let filtered_edits: Vec<RecentFileEdit> = all_edits
+        .into_iter()
+        .filter(|edit| compiled_filter.matches_edit(edit))
+        .filter(|edit| {
+            let keep = in_scope(&edit.file_path);
+            if !keep {
+                excluded_by_cwd.insert(edit.file_path.clone());
+            }
+            keep
+        })
+        .collect();
+
+    // Keep the latest shell command per path, newest first
+    all_shell_modified.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    let mut latest_shell_by_file: HashMap<String, ShellModifiedFile> = HashMap::new();
+    for entry in all_shell_modified {
+        let matches = compiled_filter.matches_entry(
+            &entry.session_id,
+            &entry.timestamp,
+            &entry.file_path,
+            entry.cwd.as_deref(),
+        );
+        if matches && in_scope(&entry.file_path) {
+            latest_shell_by_file
+                .entry(entry.file_path.clone())
+                .or_insert(entry);
+        }
+    }
+    let mut shell_modified_files: Vec<ShellModifiedFile> =
+        latest_shell_by_file.into_values().collect();
+    shell_modified_files.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
     let total_edits_count = filtered_edits.len();
 
     // Sort by timestamp descending (newest first)
@@ -265,8 +684,10 @@ pub async fn get_recent_edits(
     let mut files: Vec<RecentFileEdit> = latest_by_file.into_values().collect();
     files.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
 
-    // Apply pagination
-    let paginated_files: Vec<RecentFileEdit> = files.into_iter().skip(offset).take(limit).collect();
+    // Apply pagination, then load bodies for the requested page only
+    let mut paginated_files: Vec<RecentFileEdit> =
+        files.into_iter().skip(offset).take(limit).collect();
+    load_edit_contents(&mut paginated_files);
 
     let has_more = offset + paginated_files.len() < unique_files_count;
 
@@ -275,30 +696,106 @@ pub async fn get_recent_edits(
         total_edits_count,
         unique_files_count,
         project_cwd,
+        shell_modified_files,
+        excluded_by_cwd: excluded_by_cwd.into_iter().collect(),
         offset,
         limit,
         has_more,
     })
 }
 
-/// Restore a file by writing content to the specified path
+/// Paths a session edited or wrote through its tool calls
+pub(crate) fn session_edited_files(file_path: &PathBuf) -> HashSet<String> {
+    process_session_file_for_edits(file_path, &ContentMode::Metadata)
+        .map(|result| {
+            result
+                .edits
+                .into_iter()
+                .map(|edit| edit.file_path)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fill in `content_after_change`/`original_content` for metadata-only edits
 ///
-/// Uses atomic write pattern: writes to a temporary file first, then renames.
-/// This prevents data loss if the write operation fails midway.
+/// Each referenced session file is re-read once, in parallel.
+fn load_edit_contents(edits: &mut [RecentFileEdit]) {
+    let mut wanted_by_file: HashMap<String, HashSet<(usize, String)>> = HashMap::new();
+    for edit in edits.iter() {
+        if let Some(source) = &edit.source {
+            wanted_by_file
+                .entry(source.session_file.clone())
+                .or_default()
+                .insert((source.line_offset, edit.file_path.clone()));
+        }
+    }
+
+    let mut loaded: HashMap<(EditSource, String), RecentFileEdit> = wanted_by_file
+        .par_iter()
+        .filter_map(|(session_file, wanted)| {
+            process_session_file_for_edits(
+                &PathBuf::from(session_file),
+                &ContentMode::Selected(wanted),
+            )
+        })
+        .flat_map_iter(|result| result.edits)
+        .filter_map(|edit| Some(((edit.source.clone()?, edit.file_path.clone()), edit)))
+        .collect();
+
+    for edit in edits.iter_mut() {
+        let Some(source) = edit.source.clone() else {
+            continue;
+        };
+        if let Some(full) = loaded.remove(&(source, edit.file_path.clone())) {
+            *edit = full;
+        }
+    }
+}
+
+/// Replay one session file far enough to return a single edit in full
+fn load_recent_edit_content(
+    session_file: &str,
+    line_offset: usize,
+    file_path: String,
+) -> Result<RecentFileEdit, String> {
+    let wanted = HashSet::from([(line_offset, file_path)]);
+    process_session_file_for_edits(
+        &PathBuf::from(session_file),
+        &ContentMode::Selected(&wanted),
+    )
+    .and_then(|result| result.edits.into_iter().next())
+    .ok_or_else(|| format!("Edit not found in session file: {session_file}"))
+}
+
+/// Load the full contents of a single edit returned by `get_recent_edits`
 ///
-/// Security: Validates path to prevent path traversal attacks
+/// `session_file` must be a session inside a known Claude folder or the archive.
 #[tauri::command]
-pub async fn restore_file(file_path: String, content: String) -> Result<(), String> {
-    use std::fs;
-    use std::path::Path;
+pub async fn get_recent_edit_content(
+    session_file: String,
+    line_offset: usize,
+    file_path: String,
+) -> Result<RecentFileEdit, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        validate_session_path(&session_file, &session_read_roots())?;
+        load_recent_edit_content(&session_file, line_offset, file_path)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?
+}
 
+/// Validate a restore target path
+///
+/// Security: rejects null bytes, relative paths and parent traversal segments
+pub(crate) fn validate_restore_path(file_path: &str) -> Result<&Path, String> {
     // Security validation: reject paths with null bytes
     if file_path.contains('\0') {
         return Err("Invalid file path: contains null bytes".to_string());
     }
 
     // Security validation: reject relative paths (must be absolute)
-    let path = Path::new(&file_path);
+    let path = Path::new(file_path);
     if !path.is_absolute() {
         return Err("Invalid file path: must be an absolute path".to_string());
     }
@@ -310,6 +807,11 @@ pub async fn restore_file(file_path: String, content: String) -> Result<(), Stri
         }
     }
 
+    Ok(path)
+}
+
+/// Write bytes to an already validated path using the atomic write pattern
+pub(crate) fn write_restored_file(path: &Path, content: &[u8]) -> Result<(), String> {
     // Create parent directories if they don't exist
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("Failed to create directories: {e}"))?;
@@ -320,12 +822,22 @@ pub async fn restore_file(file_path: String, content: String) -> Result<(), Stri
     let temp_path = path.with_extension("tmp.restore");
 
     // Write to temporary file
-    fs::write(&temp_path, &content).map_err(|e| format!("Failed to write temporary file: {e}"))?;
+    fs::write(&temp_path, content).map_err(|e| format!("Failed to write temporary file: {e}"))?;
 
     // Cross-platform atomic rename
-    crate::commands::fs_utils::atomic_rename(&temp_path, path)?;
+    crate::commands::fs_utils::atomic_rename(&temp_path, path)
+}
 
-    Ok(())
+/// Restore a file by writing content to the specified path
+///
+/// Uses atomic write pattern: writes to a temporary file first, then renames.
+/// This prevents data loss if the write operation fails midway.
+///
+/// Security: Validates path to prevent path traversal attacks
+#[tauri::command]
+pub async fn restore_file(file_path: String, content: String) -> Result<(), String> {
+    let path = validate_restore_path(&file_path)?;
+    write_restored_file(path, content.as_bytes())
 }
 
 #[cfg(test)]
@@ -442,8 +954,13 @@ mod tests {
     async fn test_get_recent_edits_empty_dir() {
         let temp_dir = TempDir::new().unwrap();
 
-        let result =
-            get_recent_edits(temp_dir.path().to_string_lossy().to_string(), None, None).await;
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
         let edits_result = result.unwrap();
@@ -460,8 +977,13 @@ mod tests {
         let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"assistant","cwd":"/test/project","toolUse":{"name":"Write","input":{"file_path":"/test/project/src/main.rs","content":"fn main() {}"}}}"#;
         create_test_jsonl_file(&temp_dir, "session.jsonl", content);
 
-        let result =
-            get_recent_edits(temp_dir.path().to_string_lossy().to_string(), None, None).await;
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
         let edits_result = result.unwrap();
@@ -478,8 +1000,13 @@ mod tests {
         let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/src/lib.rs","oldString":"old","newString":"new","originalFile":"old code here"}}"#;
         create_test_jsonl_file(&temp_dir, "session.jsonl", content);
 
-        let result =
-            get_recent_edits(temp_dir.path().to_string_lossy().to_string(), None, None).await;
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
         let edits_result = result.unwrap();
@@ -496,8 +1023,13 @@ mod tests {
         let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/src/mod.rs","edits":[{"old_string":"old1","new_string":"new1"},{"old_string":"old2","new_string":"new2"}],"originalFile":"old1 old2"}}"#;
         create_test_jsonl_file(&temp_dir, "session.jsonl", content);
 
-        let result =
-            get_recent_edits(temp_dir.path().to_string_lossy().to_string(), None, None).await;
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
         let edits_result = result.unwrap();
@@ -505,6 +1037,48 @@ mod tests {
         assert_eq!(edits_result.files[0].content_after_change, "new1 new2");
     }
 
+    #[tokio::test]
+    async fn test_get_recent_edits_multi_edit_replace_all() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/src/mod.rs","edits":[{"old_string":"foo","new_string":"bar","replace_all":true}],"originalFile":"foo\nfoo\n","structuredPatch":[{"oldStart":1,"oldLines":2,"newStart":1,"newLines":2,"lines":["-foo","-foo","+bar","+bar"]}]}}"#;
+        create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.files[0].content_after_change, "bar\nbar\n");
+        assert_eq!(result.files[0].replay_status, ReplayStatus::Verified);
+        assert_eq!(result.files[0].failed_replacements, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_edits_flags_missing_old_string() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/src/lib.rs","oldString":"absent","newString":"new","originalFile":"old code here"}}"#;
+        create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.files[0].content_after_change, "old code here");
+        assert_eq!(result.files[0].replay_status, ReplayStatus::Mismatched);
+        assert_eq!(result.files[0].failed_replacements, 1);
+    }
+
     #[tokio::test]
     async fn test_get_recent_edits_keeps_latest_per_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -514,8 +1088,13 @@ mod tests {
 {"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/file.txt","oldString":"v2","newString":"v3","originalFile":"v2"}}"#;
         create_test_jsonl_file(&temp_dir, "session.jsonl", content);
 
-        let result =
-            get_recent_edits(temp_dir.path().to_string_lossy().to_string(), None, None).await;
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
         let edits_result = result.unwrap();
@@ -528,6 +1107,98 @@ mod tests {
         assert_eq!(edits_result.files[0].content_after_change, "v3");
     }
 
+    #[tokio::test]
+    async fn test_get_recent_edits_loads_contents_for_page_only() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/a.txt","oldString":"a","newString":"A","originalFile":"a"}}
+{"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/b.txt","oldString":"b","newString":"B","originalFile":"b"}}"#;
+        let session_file = create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            Some(1),
+            Some(1),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let edit = &result.files[0];
+        assert_eq!(edit.file_path, "/test/project/a.txt");
+        assert_eq!(edit.content_after_change, "A");
+        assert_eq!(edit.original_content.as_deref(), Some("a"));
+
+        let source = edit.source.clone().unwrap();
+        assert_eq!(source.session_file, session_file.to_string_lossy());
+        assert_eq!(source.line_offset, 0);
+    }
+
+    #[test]
+    fn test_load_recent_edit_content() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let first = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"type":"create","filePath":"/test/project/a.txt","content":"first"}}"#;
+        let second = r#"{"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"user","cwd":"/test/project","toolUseResult":{"type":"create","filePath":"/test/project/a.txt","content":"second"}}"#;
+        let session_file =
+            create_test_jsonl_file(&temp_dir, "session.jsonl", &format!("{first}\n{second}"));
+        let session_file = session_file.to_string_lossy().to_string();
+
+        let edit = load_recent_edit_content(
+            &session_file,
+            first.len() + 1,
+            "/test/project/a.txt".to_string(),
+        )
+        .unwrap();
+        assert_eq!(edit.content_after_change, "second");
+
+        let missing =
+            load_recent_edit_content(&session_file, 0, "/test/project/other.txt".to_string());
+        assert!(missing.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_edit_content_rejects_unknown_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_file = create_test_jsonl_file(&temp_dir, "session.jsonl", "{}");
+
+        let result = get_recent_edit_content(
+            session_file.to_string_lossy().to_string(),
+            0,
+            "/test/project/a.txt".to_string(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_metadata_pass_keeps_no_notebook_bodies() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"type":"create","filePath":"/test/project/nb.ipynb","content":"{\"cells\":[{\"id\":\"a1\",\"cell_type\":\"code\",\"metadata\":{},\"source\":[\"x = 1\"]}],\"metadata\":{},\"nbformat\":4,\"nbformat_minor\":5}"}}
+{"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"user","cwd":"/test/project","toolUseResult":{"notebook_path":"/test/project/nb.ipynb","cell_id":"a1","new_source":"a = 1\nb = 2","edit_mode":"insert","error":""}}"#;
+        let session_file = create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+
+        let result = process_session_file_for_edits(&session_file, &ContentMode::Metadata).unwrap();
+        assert_eq!(result.edits.len(), 2);
+        let notebook = &result.edits[1];
+        assert_eq!(notebook.operation_type, "notebook");
+        assert!(notebook.content_after_change.is_empty());
+        assert_eq!(notebook.lines_added, 2);
+        assert!(result.shell_modified.is_empty());
+
+        // Loading the edit replays it on the full body
+        let source = notebook.source.clone().unwrap();
+        let full = load_recent_edit_content(
+            &source.session_file,
+            source.line_offset,
+            notebook.file_path.clone(),
+        )
+        .unwrap();
+        let body: serde_json::Value = serde_json::from_str(&full.content_after_change).unwrap();
+        assert_eq!(body["cells"].as_array().unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_get_recent_edits_with_create_type() {
         let temp_dir = TempDir::new().unwrap();
@@ -536,8 +1207,13 @@ mod tests {
         let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"type":"create","filePath":"/test/project/new_file.rs","content":"pub fn new() {}"}}"#;
         create_test_jsonl_file(&temp_dir, "session.jsonl", content);
 
-        let result =
-            get_recent_edits(temp_dir.path().to_string_lossy().to_string(), None, None).await;
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
         let edits_result = result.unwrap();
@@ -549,6 +1225,182 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_recent_edits_chains_notebook_edits() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r##"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"notebook_path":"/test/project/nb.ipynb","cell_id":"a1","new_source":"x = 2","edit_mode":"replace","error":"","original_file":"{\"cells\":[{\"id\":\"a1\",\"cell_type\":\"code\",\"metadata\":{},\"source\":[\"x = 1\"]}],\"metadata\":{},\"nbformat\":4,\"nbformat_minor\":5}"}}
+{"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"user","cwd":"/test/project","toolUseResult":{"notebook_path":"/test/project/nb.ipynb","cell_id":"a1","new_source":"# Notes","cell_type":"markdown","edit_mode":"insert","error":""}}"##;
+        create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.total_edits_count, 2);
+        let latest = &result.files[0];
+        assert_eq!(latest.operation_type, "notebook");
+        assert_eq!(latest.replay_status, ReplayStatus::Unverified);
+
+        let notebook: serde_json::Value =
+            serde_json::from_str(&latest.content_after_change).unwrap();
+        assert_eq!(notebook["cells"][0]["source"], serde_json::json!(["x = 2"]));
+        assert_eq!(notebook["cells"][1]["cell_type"], "markdown");
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_edits_lists_unreplayable_notebook_edit() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"notebook_path":"/test/project/nb.ipynb","cell_id":"a1","new_source":"x = 2","edit_mode":"replace","error":""}}"#;
+        create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(result.files.is_empty());
+        assert_eq!(result.shell_modified_files.len(), 1);
+        let listed = &result.shell_modified_files[0];
+        assert_eq!(listed.file_path, "/test/project/nb.ipynb");
+        assert_eq!(listed.command, "NotebookEdit replace cell a1");
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_edits_lists_shell_modified_files() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"assistant","cwd":"/test/project","message":{"role":"assistant","content":[{"type":"tool_use","id":"tool_1","name":"Bash","input":{"command":"sed -i 's/a/b/' src/lib.rs && mv old.txt /elsewhere/new.txt"}}]}}"#;
+        create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(result.files.is_empty());
+        let paths: Vec<&str> = result
+            .shell_modified_files
+            .iter()
+            .map(|f| f.file_path.as_str())
+            .collect();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&"/test/project/src/lib.rs"));
+        assert!(paths.contains(&"/test/project/old.txt"));
+        assert!(result.shell_modified_files[0].command.starts_with("sed -i"));
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_edits_applies_filter() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/src/lib.rs","oldString":"old","newString":"new","originalFile":"old"}}
+{"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T11:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/src/gen/out.rs","oldString":"old","newString":"new","originalFile":"old"}}
+{"uuid":"uuid-3","sessionId":"session-2","timestamp":"2025-06-27T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"type":"create","filePath":"/test/project/src/main.rs","content":"fn main() {}"}}
+{"uuid":"uuid-4","sessionId":"session-2","timestamp":"2025-06-27T11:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/README.md","oldString":"old","newString":"new","originalFile":"old"}}"#;
+        create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+        let project = temp_dir.path().to_string_lossy().to_string();
+
+        let filter = RecentEditsFilter {
+            include_globs: vec!["src/**/*.rs".to_string()],
+            exclude_globs: vec!["**/gen/**".to_string()],
+            ..Default::default()
+        };
+        let result = get_recent_edits(project.clone(), None, None, Some(filter))
+            .await
+            .unwrap();
+        let paths: Vec<&str> = result.files.iter().map(|f| f.file_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["/test/project/src/main.rs", "/test/project/src/lib.rs"]
+        );
+
+        let filter = RecentEditsFilter {
+            session_ids: vec!["session-2".to_string()],
+            operation_types: vec!["edit".to_string()],
+            ..Default::default()
+        };
+        let result = get_recent_edits(project.clone(), None, None, Some(filter))
+            .await
+            .unwrap();
+        assert_eq!(result.total_edits_count, 1);
+        assert_eq!(result.files[0].file_path, "/test/project/README.md");
+
+        let filter = RecentEditsFilter {
+            since: Some("2025-06-26T10:30:00Z".to_string()),
+            until: Some("2025-06-27T10:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let result = get_recent_edits(project.clone(), None, None, Some(filter))
+            .await
+            .unwrap();
+        assert_eq!(result.total_edits_count, 2);
+
+        let invalid = RecentEditsFilter {
+            since: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        assert!(get_recent_edits(project, None, None, Some(invalid))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_edits_cwd_scope() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project/a.txt","oldString":"old","newString":"new","originalFile":"old"}}
+{"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/test/project-worktree/b.txt","oldString":"old","newString":"new","originalFile":"old"}}
+{"uuid":"uuid-3","sessionId":"session-1","timestamp":"2025-06-26T10:02:00Z","type":"user","cwd":"/test/other","toolUseResult":{"filePath":"/test/other/c.txt","oldString":"old","newString":"new","originalFile":"old"}}"#;
+        create_test_jsonl_file(&temp_dir, "session.jsonl", content);
+        let project = temp_dir.path().to_string_lossy().to_string();
+
+        let result = get_recent_edits(project.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(result.unique_files_count, 1);
+        assert_eq!(
+            result.excluded_by_cwd,
+            vec!["/test/other/c.txt", "/test/project-worktree/b.txt"]
+        );
+
+        let all = RecentEditsFilter {
+            cwd_scope: CwdScope::All,
+            ..Default::default()
+        };
+        let result = get_recent_edits(project.clone(), None, None, Some(all))
+            .await
+            .unwrap();
+        assert_eq!(result.unique_files_count, 3);
+        assert!(result.excluded_by_cwd.is_empty());
+
+        let explicit = RecentEditsFilter {
+            cwd_scope: CwdScope::Explicit {
+                cwds: vec!["/test/project".to_string(), "/test/other".to_string()],
+            },
+            ..Default::default()
+        };
+        let result = get_recent_edits(project, None, None, Some(explicit))
+            .await
+            .unwrap();
+        assert_eq!(result.unique_files_count, 2);
+        assert_eq!(result.excluded_by_cwd, vec!["/test/project-worktree/b.txt"]);
+    }
+
     #[tokio::test]
     async fn test_get_recent_edits_filters_by_project_cwd() {
         let temp_dir = TempDir::new().unwrap();
@@ -559,8 +1411,13 @@ mod tests {
 {"uuid":"uuid-3","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"user","cwd":"/test/project","toolUseResult":{"filePath":"/other/location/file3.txt","oldString":"old","newString":"new","originalFile":"old"}}"#;
         create_test_jsonl_file(&temp_dir, "session.jsonl", content);
 
-        let result =
-            get_recent_edits(temp_dir.path().to_string_lossy().to_string(), None, None).await;
+        let result = get_recent_edits(
+            temp_dir.path().to_string_lossy().to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
         let edits_result = result.unwrap();
@@ -1,14 +1,18 @@
 //! Session loading functions
 
+use crate::commands::archive::{
+    archived_project_dir, is_archived_session, is_session_file, open_session_reader,
+    read_session_bytes,
+};
 use crate::models::{ClaudeMessage, ClaudeSession, MessagePage, RawLogEntry};
 use crate::utils::{extract_project_name, find_line_ranges, find_line_starts};
 use chrono::{DateTime, Utc};
-use memmap2::Mmap;
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::SystemTime;
 use uuid::Uuid;
 use walkdir::WalkDir;
@@ -39,6 +43,9 @@ struct CachedSessionMetadata {
     /// First assistant text (for multi-tier fallback)
     #[serde(default)]
     first_assistant_text: Option<String>,
+    /// Messages per `cwd` (for project path decoding)
+    #[serde(default)]
+    cwd_counts: HashMap<String, usize>,
 }
 
 /// Session metadata cache file structure
@@ -48,9 +55,21 @@ struct SessionMetadataCache {
     version: u32,
     /// Map of file path -> cached metadata
     entries: HashMap<String, CachedSessionMetadata>,
+    /// Map of file path -> project scan, for files without a metadata entry
+    #[serde(default)]
+    scans: HashMap<String, CachedSessionScan>,
+}
+
+/// Cache entry for a file counted by project scanning but never fully parsed
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+struct CachedSessionScan {
+    modified_time: u64,
+    file_size: u64,
+    message_count: usize,
+    cwd_counts: HashMap<String, usize>,
 }
 
-const CACHE_VERSION: u32 = 6;
+const CACHE_VERSION: u32 = 8;
 
 /// Get the cache file path for a project
 fn get_cache_path(project_path: &str) -> PathBuf {
@@ -92,6 +111,112 @@ fn get_file_size(path: &PathBuf) -> Option<u64> {
     path.metadata().ok().map(|m| m.len())
 }
 
+/// What project scanning needs from one session file
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct SessionScan {
+    pub(crate) message_count: usize,
+    /// Messages per `cwd`
+    pub(crate) cwd_counts: HashMap<String, usize>,
+}
+
+/// A project's metadata cache as used when scanning projects
+///
+/// Files scanned cold are recorded and written back by [`SessionScanCache::save`],
+/// so the next scan doesn't re-read them.
+pub(crate) struct SessionScanCache {
+    project_path: String,
+    cache: SessionMetadataCache,
+    cold: Mutex<Vec<(String, CachedSessionScan)>>,
+}
+
+impl SessionScanCache {
+    pub(crate) fn load(project_path: &Path) -> Self {
+        let project_path = project_path.to_string_lossy().to_string();
+        Self {
+            cache: load_cache(&project_path),
+            project_path,
+            cold: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Cached scan, if the file hasn't changed since it was cached
+    pub(crate) fn get(&self, path: &PathBuf) -> Option<SessionScan> {
+        let key = path.to_string_lossy();
+        let modified_time = get_modified_time(path);
+        let file_size = get_file_size(path);
+        if let Some(cached) = self.cache.entries.get(key.as_ref()) {
+            if Some(cached.modified_time) == modified_time && Some(cached.file_size) == file_size {
+                return Some(SessionScan {
+                    message_count: cached.session.as_ref().map_or(0, |s| s.message_count),
+                    cwd_counts: cached.cwd_counts.clone(),
+                });
+            }
+        }
+        let cached = self.cache.scans.get(key.as_ref())?;
+        let unchanged =
+            Some(cached.modified_time) == modified_time && Some(cached.file_size) == file_size;
+        unchanged.then(|| SessionScan {
+            message_count: cached.message_count,
+            cwd_counts: cached.cwd_counts.clone(),
+        })
+    }
+
+    /// Cached scan, or a fresh one that is recorded for [`SessionScanCache::save`]
+    pub(crate) fn scan(&self, path: &PathBuf) -> Option<SessionScan> {
+        if let Some(scan) = self.get(path) {
+            return Some(scan);
+        }
+        // Stamp before reading, so a write during the scan invalidates it
+        let stamp = get_modified_time(path).zip(get_file_size(path));
+        let scan = scan_session_file(path)?;
+        if let Some((modified_time, file_size)) = stamp {
+            let cached = CachedSessionScan {
+                modified_time,
+                file_size,
+                message_count: scan.message_count,
+                cwd_counts: scan.cwd_counts.clone(),
+            };
+            if let Ok(mut cold) = self.cold.lock() {
+                cold.push((path.to_string_lossy().to_string(), cached));
+            }
+        }
+        Some(scan)
+    }
+
+    /// Write cold scans back to the cache (best effort)
+    pub(crate) fn save(self) {
+        let cold = self.cold.into_inner().unwrap_or_default();
+        if cold.is_empty() {
+            return;
+        }
+        // Reload so entries written since `load` aren't lost
+        let mut cache = load_cache(&self.project_path);
+        cache.version = CACHE_VERSION;
+        cache.scans.extend(cold);
+        cache.scans.retain(|path, _| Path::new(path).exists());
+        save_cache(&self.project_path, &cache);
+    }
+}
+
+/// Count the messages in a session file, and their `cwd`s, with fast line classification
+fn scan_session_file(path: &Path) -> Option<SessionScan> {
+    let contents = read_session_bytes(path).ok()?;
+    let cwds: Vec<Option<String>> = find_line_ranges(&contents)
+        .par_iter()
+        .filter_map(|&(start, end)| parse_message_classifier(&contents[start..end], false))
+        .map(|classifier| classifier.cwd)
+        .collect();
+
+    let mut scan = SessionScan {
+        message_count: cwds.len(),
+        cwd_counts: HashMap::new(),
+    };
+    for cwd in cwds.into_iter().flatten() {
+        *scan.cwd_counts.entry(cwd).or_insert(0) += 1;
+    }
+    Some(scan)
+}
+
 /// Data needed for incremental parsing continuation
 #[derive(Clone)]
 struct IncrementalParseState {
@@ -119,6 +244,41 @@ struct IncrementalParseState {
     last_user_content: Option<String>,
     /// First assistant text (already known, for fallback)
     first_assistant_text: Option<String>,
+    /// Messages per `cwd` so far
+    cwd_counts: HashMap<String, usize>,
+    /// Branches and version seen so far
+    context: SessionContext,
+}
+
+/// Git branches and Claude Code version recorded on a session's messages
+#[derive(Clone, Default)]
+struct SessionContext {
+    git_branch: Option<String>,
+    git_branches: Vec<String>,
+    version: Option<String>,
+}
+
+impl SessionContext {
+    fn from_session(session: &ClaudeSession) -> Self {
+        Self {
+            git_branch: session.git_branch.clone(),
+            git_branches: session.git_branches.clone(),
+            version: session.version.clone(),
+        }
+    }
+
+    fn record(&mut self, git_branch: Option<String>, version: Option<String>) {
+        // Sessions outside a repository record an empty branch
+        if let Some(branch) = git_branch.filter(|branch| !branch.is_empty()) {
+            if !self.git_branches.contains(&branch) {
+                self.git_branches.push(branch.clone());
+            }
+            self.git_branch = Some(branch);
+        }
+        if let Some(version) = version.filter(|version| !version.is_empty()) {
+            self.version = Some(version);
+        }
+    }
 }
 
 /// Minimal struct for fast line classification (avoids full parsing)
@@ -130,6 +290,7 @@ struct LineClassifier {
     is_sidechain: Option<bool>,
     #[serde(rename = "isMeta")]
     is_meta: Option<bool>,
+    cwd: Option<String>,
 }
 
 /// Minimal struct for extracting session metadata without full message parsing
@@ -145,6 +306,10 @@ struct SessionMetadataEntry {
     #[serde(rename = "isMeta")]
     is_meta: Option<bool>,
     summary: Option<String>,
+    cwd: Option<String>,
+    #[serde(rename = "gitBranch")]
+    git_branch: Option<String>,
+    version: Option<String>,
     #[serde(rename = "toolUse")]
     tool_use: Option<serde_json::Value>,
     #[serde(rename = "toolUseResult")]
@@ -169,6 +334,10 @@ struct QuickLineClassifier {
     is_sidechain: Option<bool>,
     #[serde(rename = "isMeta")]
     is_meta: Option<bool>,
+    cwd: Option<String>,
+    #[serde(rename = "gitBranch")]
+    git_branch: Option<String>,
+    version: Option<String>,
 }
 
 /// Fast session metadata extraction result
@@ -187,6 +356,8 @@ struct SessionExtractionResult {
     last_user_content: Option<String>,
     /// First assistant text (for incremental caching)
     first_assistant_text: Option<String>,
+    /// Messages per `cwd` (for incremental caching)
+    cwd_counts: HashMap<String, usize>,
 }
 
 /// Fast session metadata extraction with two-phase parsing:
@@ -221,7 +392,6 @@ fn extract_session_metadata_internal(
         })
         .unwrap_or_else(|| Utc::now().to_rfc3339());
 
-    let mut file = fs::File::open(file_path).ok()?;
     let file_path_str = file_path.to_string_lossy().to_string();
 
     // Initialize from incremental state or start fresh
@@ -259,13 +429,29 @@ fn extract_session_metadata_internal(
         )
     };
 
-    // Seek to start position for incremental parsing
-    if start_offset > 0 && file.seek(SeekFrom::Start(start_offset)).is_err() {
-        return None;
-    }
+    let mut cwd_counts = incremental_state
+        .as_ref()
+        .map(|state| state.cwd_counts.clone())
+        .unwrap_or_default();
+    let mut context = incremental_state
+        .as_ref()
+        .map(|state| state.context.clone())
+        .unwrap_or_default();
 
-    // Use larger buffer for better I/O performance on large files
-    let reader = BufReader::with_capacity(64 * 1024, file);
+    // Archived sessions are compressed and never grow, so they are always read in full
+    let reader: Box<dyn BufRead + Send> = if is_archived_session(file_path) {
+        open_session_reader(file_path).ok()?
+    } else {
+        let mut file = fs::File::open(file_path).ok()?;
+
+        // Seek to start position for incremental parsing
+        if start_offset > 0 && file.seek(SeekFrom::Start(start_offset)).is_err() {
+            return None;
+        }
+
+        // Use larger buffer for better I/O performance on large files
+        Box::new(BufReader::with_capacity(64 * 1024, file))
+    };
 
     // For incremental parsing, we skip the metadata collection phase
     // since we already have it from the previous parse
@@ -318,6 +504,10 @@ fn extract_session_metadata_internal(
                     sidechain_count += 1;
                 }
                 message_count += 1;
+                if let Some(cwd) = entry.cwd {
+                    *cwd_counts.entry(cwd).or_insert(0) += 1;
+                }
+                context.record(entry.git_branch, entry.version);
 
                 // Track timestamps
                 if let Some(ref ts) = entry.timestamp {
@@ -442,6 +632,10 @@ fn extract_session_metadata_internal(
                 sidechain_count += 1;
             }
             message_count += 1;
+            if let Some(cwd) = classifier.cwd {
+                *cwd_counts.entry(cwd).or_insert(0) += 1;
+            }
+            context.record(classifier.git_branch, classifier.version);
 
             // Update last timestamp
             if let Some(ts) = classifier.timestamp {
@@ -496,6 +690,10 @@ fn extract_session_metadata_internal(
             has_tool_use,
             has_errors,
             summary: final_summary,
+            is_archived: is_archived_session(file_path),
+            git_branch: context.git_branch,
+            git_branches: context.git_branches,
+            version: context.version,
         },
         sidechain_count,
         final_byte_offset: file_size,
@@ -504,6 +702,7 @@ fn extract_session_metadata_internal(
         first_user_content,
         last_user_content,
         first_assistant_text,
+        cwd_counts,
     })
 }
 
@@ -706,13 +905,22 @@ pub async fn load_project_sessions(
     let mut cache = load_cache(&project_path);
     let mut cache_updated = false;
 
-    // 2. Collect all JSONL file paths
-    let file_paths: Vec<PathBuf> = WalkDir::new(&project_path)
+    // 2. Collect all JSONL file paths, plus sessions moved to the archive
+    let mut file_paths: Vec<PathBuf> = WalkDir::new(&project_path)
         .into_iter()
         .filter_map(std::result::Result::ok)
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter(|e| is_session_file(e.path()))
         .map(|e| e.path().to_path_buf())
         .collect();
+    if let Some(archived) = archived_project_dir(Path::new(&project_path)) {
+        file_paths.extend(
+            WalkDir::new(archived)
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+                .filter(|e| is_archived_session(e.path()))
+                .map(|e| e.path().to_path_buf()),
+        );
+    }
 
     #[cfg(debug_assertions)]
     eprintln!(
@@ -751,7 +959,7 @@ pub async fn load_project_sessions(
             }
 
             // Check if file grew (append-only) - use incremental parsing
-            if current_size > cached.file_size {
+            if current_size > cached.file_size && !is_archived_session(path) {
                 if let Some(session) = cached.session.as_ref() {
                     #[cfg(debug_assertions)]
                     {
@@ -772,6 +980,8 @@ pub async fn load_project_sessions(
                             first_user_content: cached.first_user_content.clone(),
                             last_user_content: cached.last_user_content.clone(),
                             first_assistant_text: cached.first_assistant_text.clone(),
+                            cwd_counts: cached.cwd_counts.clone(),
+                            context: SessionContext::from_session(session),
                         },
                     ));
                     continue;
@@ -838,6 +1048,7 @@ pub async fn load_project_sessions(
                     first_user_content,
                     last_user_content,
                     first_assistant_text,
+                    cwd_counts,
                 ) = match &result_opt {
                     Some(result) => (
                         Some(result.session.clone()),
@@ -848,8 +1059,9 @@ pub async fn load_project_sessions(
                         result.first_user_content.clone(),
                         result.last_user_content.clone(),
                         result.first_assistant_text.clone(),
+                        result.cwd_counts.clone(),
                     ),
-                    None => (None, 0, 0, false, false, None, None, None),
+                    None => (None, 0, 0, false, false, None, None, None, HashMap::new()),
                 };
 
                 cache.entries.insert(
@@ -865,6 +1077,7 @@ pub async fn load_project_sessions(
                         first_user_content,
                         last_user_content,
                         first_assistant_text,
+                        cwd_counts,
                     },
                 );
                 cache_updated = true;
@@ -914,6 +1127,8 @@ pub async fn load_project_sessions(
     // 9. Save updated cache
     if cache_updated {
         cache.version = CACHE_VERSION;
+        let entries = &cache.entries;
+        cache.scans.retain(|path, _| !entries.contains_key(path));
         save_cache(&project_path, &cache);
     }
 
@@ -1054,6 +1269,19 @@ fn parse_line_to_message(
     })
 }
 
+/// Messages in a run of complete lines appended to a session file
+pub(crate) fn parse_appended_lines(bytes: &[u8]) -> Vec<ClaudeMessage> {
+    find_line_ranges(bytes)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (start, end))| {
+            let mut line_bytes = bytes[start..end].to_vec();
+            parse_line_simd(i, &mut line_bytes, false)
+        })
+        .filter(|msg| !is_system_message_type(&msg.message_type))
+        .collect()
+}
+
 /// Parse a single line using simd-json for faster parsing
 /// Returns None if the line is empty or fails to parse
 fn parse_line_simd(
@@ -1196,20 +1424,13 @@ fn parse_line_simd(
 }
 
 #[tauri::command]
-#[allow(unsafe_code)] // Required for mmap performance optimization
 pub async fn load_session_messages(session_path: String) -> Result<Vec<ClaudeMessage>, String> {
     #[cfg(debug_assertions)]
     let start_time = std::time::Instant::now();
 
-    // Use memory-mapped file for faster I/O
-    let file =
-        fs::File::open(&session_path).map_err(|e| format!("Failed to open session file: {e}"))?;
-
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. No concurrent modifications expected
-    // as session files are append-only by Claude.
-    let mmap = unsafe { Mmap::map(&file) }
-        .map_err(|e| format!("Failed to memory-map session file: {e}"))?;
+    // Use memory-mapped file for faster I/O (archived sessions are inflated in memory)
+    let mmap = read_session_bytes(Path::new(&session_path))
+        .map_err(|e| format!("Failed to open session file: {e}"))?;
 
     // Find line boundaries efficiently using SIMD-accelerated memchr
     let line_starts = find_line_starts(&mmap);
@@ -1252,35 +1473,32 @@ pub async fn load_session_messages(session_path: String) -> Result<Vec<ClaudeMes
 
 /// Fast line classifier for simd-json (mutable slice)
 fn classify_line_fast(line: &[u8], exclude_sidechain: bool) -> bool {
+    parse_message_classifier(line, exclude_sidechain).is_some()
+}
+
+/// Parse a line's classifier fields, returning them only if the line is a message
+fn parse_message_classifier(line: &[u8], exclude_sidechain: bool) -> Option<LineClassifier> {
     if line
         .iter()
         .all(|&b| b == b' ' || b == b'\t' || b == b'\n' || b == b'\r')
     {
-        return false;
+        return None;
     }
 
     // Try fast simd-json parsing with minimal struct
     let mut line_copy = line.to_vec();
-    if let Ok(classifier) = simd_json::serde::from_slice::<LineClassifier>(&mut line_copy) {
-        if classifier.message_type == "summary" {
-            return false;
-        }
-        if is_system_message_type(&classifier.message_type) {
-            return false;
-        }
-        if classifier.is_meta.unwrap_or(false) {
-            return false;
-        }
-        if exclude_sidechain && classifier.is_sidechain.unwrap_or(false) {
-            return false;
-        }
-        return true;
+    let classifier = simd_json::serde::from_slice::<LineClassifier>(&mut line_copy).ok()?;
+    if classifier.message_type == "summary"
+        || is_system_message_type(&classifier.message_type)
+        || classifier.is_meta.unwrap_or(false)
+        || (exclude_sidechain && classifier.is_sidechain.unwrap_or(false))
+    {
+        return None;
     }
-    false
+    Some(classifier)
 }
 
 #[tauri::command]
-#[allow(unsafe_code)] // Required for mmap performance optimization
 pub async fn load_session_messages_paginated(
     session_path: String,
     offset: usize,
@@ -1290,15 +1508,9 @@ pub async fn load_session_messages_paginated(
     #[cfg(debug_assertions)]
     let start_time = std::time::Instant::now();
 
-    // Use memory-mapped file for faster I/O
-    let file =
-        fs::File::open(&session_path).map_err(|e| format!("Failed to open session file: {e}"))?;
-
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. No concurrent modifications expected
-    // as session files are append-only by Claude.
-    let mmap = unsafe { Mmap::map(&file) }
-        .map_err(|e| format!("Failed to memory-map session file: {e}"))?;
+    // Use memory-mapped file for faster I/O (archived sessions are inflated in memory)
+    let mmap = read_session_bytes(Path::new(&session_path))
+        .map_err(|e| format!("Failed to open session file: {e}"))?;
 
     let exclude = exclude_sidechain.unwrap_or(false);
 
@@ -1375,20 +1587,13 @@ pub async fn load_session_messages_paginated(
 }
 
 #[tauri::command]
-#[allow(unsafe_code)] // Required for mmap performance optimization
 pub async fn get_session_message_count(
     session_path: String,
     exclude_sidechain: Option<bool>,
 ) -> Result<usize, String> {
-    // Use memory-mapped file for faster I/O
-    let file =
-        fs::File::open(&session_path).map_err(|e| format!("Failed to open session file: {e}"))?;
-
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. No concurrent modifications expected
-    // as session files are append-only by Claude.
-    let mmap = unsafe { Mmap::map(&file) }
-        .map_err(|e| format!("Failed to memory-map session file: {e}"))?;
+    // Use memory-mapped file for faster I/O (archived sessions are inflated in memory)
+    let mmap = read_session_bytes(Path::new(&session_path))
+        .map_err(|e| format!("Failed to open session file: {e}"))?;
 
     let exclude = exclude_sidechain.unwrap_or(false);
 
@@ -1855,6 +2060,42 @@ mod tests {
         assert_eq!(result2[0].last_message_time, "2025-06-26T10:03:00Z");
     }
 
+    #[tokio::test]
+    async fn test_load_project_sessions_git_branch_and_version() {
+        use std::io::Write;
+
+        let temp_dir = TempDir::new().unwrap();
+        let initial_content = r#"{"uuid":"uuid-1","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","gitBranch":"main","version":"1.0.80","message":{"role":"user","content":"Hello"}}
+{"uuid":"uuid-2","sessionId":"session-1","timestamp":"2025-06-26T10:01:00Z","type":"assistant","gitBranch":"feature/login","version":"1.0.80","message":{"role":"assistant","content":"Hi there"}}
+"#;
+        let file_path = temp_dir.path().join("test.jsonl");
+        std::fs::write(&file_path, initial_content).unwrap();
+
+        let sessions = load_project_sessions(temp_dir.path().to_string_lossy().to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(sessions[0].git_branch.as_deref(), Some("feature/login"));
+        assert_eq!(sessions[0].git_branches, vec!["main", "feature/login"]);
+        assert_eq!(sessions[0].version.as_deref(), Some("1.0.80"));
+
+        // Appended lines are parsed incrementally on top of the cached context
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&file_path)
+            .unwrap();
+        writeln!(file, r#"{{"uuid":"uuid-3","sessionId":"session-1","timestamp":"2025-06-26T10:02:00Z","type":"user","gitBranch":"main","version":"1.0.85","message":{{"role":"user","content":"Back to main"}}}}"#).unwrap();
+        writeln!(file, r#"{{"uuid":"uuid-4","sessionId":"session-1","timestamp":"2025-06-26T10:03:00Z","type":"assistant","gitBranch":"","message":{{"role":"assistant","content":"Done"}}}}"#).unwrap();
+        drop(file);
+
+        let sessions = load_project_sessions(temp_dir.path().to_string_lossy().to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(sessions[0].message_count, 4);
+        assert_eq!(sessions[0].git_branch.as_deref(), Some("main"));
+        assert_eq!(sessions[0].git_branches, vec!["main", "feature/login"]);
+        assert_eq!(sessions[0].version.as_deref(), Some("1.0.85"));
+    }
+
     #[tokio::test]
     async fn test_message_with_missing_uuid_generates_new_one() {
         let temp_dir = TempDir::new().unwrap();
@@ -2083,4 +2324,28 @@ mod tests {
         // Should fall back to user message since assistant text is too short
         assert_eq!(result[0].summary, Some("User fallback message".to_string()));
     }
+
+    #[test]
+    fn test_session_scan_cache_persists_cold_scans() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = format!(
+            "{}\n{}\n",
+            create_sample_user_message("u1", "s1", "Hello"),
+            create_sample_assistant_message("a1", "s1", "Hi")
+        );
+        let path = create_test_jsonl_file(&temp_dir, "s1.jsonl", &content);
+
+        let cache = SessionScanCache::load(temp_dir.path());
+        assert!(cache.get(&path).is_none());
+        assert_eq!(cache.scan(&path).unwrap().message_count, 2);
+        cache.save();
+
+        let cache = SessionScanCache::load(temp_dir.path());
+        assert_eq!(cache.get(&path).unwrap().message_count, 2);
+
+        // A grown file is scanned again
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{}", create_sample_user_message("u2", "s1", "More")).unwrap();
+        assert!(cache.get(&path).is_none());
+    }
 }
@@ -1,17 +1,32 @@
 //! Session commands module
 //!
 //! This module contains all session-related Tauri commands organized into submodules:
+//! - `checkpoints`: File history checkpoint listing and restore functions
 //! - `load`: Session and message loading functions
 //! - `search`: Message search functions
 //! - `edits`: File edit tracking and restore functions
 //! - `rename`: Native session renaming functions
+//! - `replay`: Edit replay engine used by edit recovery
+//! - `notebook`: Cell-level `NotebookEdit` replay used by edit recovery
+//! - `shell`: Paths touched by Bash commands, flagged by edit recovery
+//! - `audit`: Secret-leak audit across all sessions
+//! - `disk_usage`: Disk usage breakdown per project, session and content type
 
+mod audit;
+mod checkpoints;
+mod disk_usage;
 mod edits;
 mod load;
+mod notebook;
 mod rename;
+mod replay;
 mod search;
+mod shell;
 
 // Re-export all commands
+pub use audit::*;
+pub use checkpoints::*;
+pub use disk_usage::*;
 pub use edits::*;
 pub use load::*;
 pub use rename::*;
@@ -0,0 +1,251 @@
+//! Notebook edit replay
+//!
+//! Reconstructs Jupyter notebooks from `NotebookEdit` tool results by applying
+//! the recorded cell operation to the `cells` array of the original notebook.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Cell-level operation recorded by the `NotebookEdit` tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotebookEditMode {
+    Replace,
+    Insert,
+    Delete,
+}
+
+/// A single `NotebookEdit` operation
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NotebookEditOperation<'a> {
+    pub cell_id: Option<&'a str>,
+    pub cell_number: Option<usize>,
+    pub new_source: &'a str,
+    pub cell_type: Option<&'a str>,
+    pub mode: NotebookEditMode,
+}
+
+impl<'a> NotebookEditOperation<'a> {
+    /// Parse a `NotebookEdit` tool result or tool input
+    /// (`cell_id`/`cell_number`/`new_source`/`cell_type`/`edit_mode`)
+    pub fn from_value(value: &'a Value) -> Option<Self> {
+        let mode = match value.get("edit_mode").and_then(Value::as_str) {
+            None | Some("replace") => NotebookEditMode::Replace,
+            Some("insert") => NotebookEditMode::Insert,
+            Some("delete") => NotebookEditMode::Delete,
+            Some(_) => return None,
+        };
+
+        Some(Self {
+            cell_id: value.get("cell_id").and_then(Value::as_str),
+            cell_number: value
+                .get("cell_number")
+                .and_then(Value::as_u64)
+                .and_then(|n| usize::try_from(n).ok()),
+            new_source: value
+                .get("new_source")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+            cell_type: value.get("cell_type").and_then(Value::as_str),
+            mode,
+        })
+    }
+}
+
+/// Result of replaying a notebook edit
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NotebookReplayOutcome {
+    pub content: String,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+/// Locate the target cell, by `id` first and then by `cell-N`/`cell_number` index
+fn find_cell_index(cells: &[Value], op: &NotebookEditOperation<'_>) -> Option<usize> {
+    if let Some(cell_id) = op.cell_id {
+        if let Some(idx) = cells
+            .iter()
+            .position(|cell| cell.get("id").and_then(Value::as_str) == Some(cell_id))
+        {
+            return Some(idx);
+        }
+        // Notebooks without cell ids are addressed as `cell-N`
+        return cell_id
+            .strip_prefix("cell-")
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|idx| *idx < cells.len());
+    }
+    // An insert may also address the slot after the last cell
+    let slots = match op.mode {
+        NotebookEditMode::Insert => cells.len() + 1,
+        NotebookEditMode::Replace | NotebookEditMode::Delete => cells.len(),
+    };
+    op.cell_number.filter(|idx| *idx < slots)
+}
+
+/// Jupyter stores cell sources as a list of lines that keep their newlines
+fn source_to_lines(source: &str) -> Value {
+    Value::Array(
+        source
+            .split_inclusive('\n')
+            .map(|line| Value::String(line.to_string()))
+            .collect(),
+    )
+}
+
+fn cell_source(cell: &Value) -> String {
+    match cell.get("source") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+fn new_cell(op: &NotebookEditOperation<'_>) -> Value {
+    let cell_type = op.cell_type.unwrap_or("code");
+    let mut cell = serde_json::json!({
+        "cell_type": cell_type,
+        "metadata": {},
+        "source": source_to_lines(op.new_source),
+    });
+    if cell_type == "code" {
+        cell["execution_count"] = Value::Null;
+        cell["outputs"] = Value::Array(Vec::new());
+    }
+    cell
+}
+
+/// Serialize with the one-space indent Jupyter itself uses
+fn to_notebook_string(notebook: &Value) -> Option<String> {
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b" ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    notebook.serialize(&mut serializer).ok()?;
+    String::from_utf8(buf).ok()
+}
+
+/// Apply `op` to the notebook JSON in `original`
+///
+/// Returns None when the notebook cannot be parsed or the target cell is missing.
+pub(crate) fn replay_notebook_edit(
+    original: &str,
+    op: &NotebookEditOperation<'_>,
+) -> Option<NotebookReplayOutcome> {
+    let mut notebook: Value = serde_json::from_str(original).ok()?;
+    let cells = notebook.get_mut("cells")?.as_array_mut()?;
+    let target = find_cell_index(cells, op);
+    let added = op.new_source.lines().count();
+
+    let (lines_added, lines_removed) = match op.mode {
+        NotebookEditMode::Replace => {
+            let cell = cells.get_mut(target?)?;
+            let removed = cell_source(cell).lines().count();
+            cell["source"] = source_to_lines(op.new_source);
+            if let Some(cell_type) = op.cell_type {
+                cell["cell_type"] = Value::String(cell_type.to_string());
+            }
+            (added, removed)
+        }
+        NotebookEditMode::Insert => {
+            // Inserts go after the referenced cell, or at the top without one
+            let position = match (op.cell_id, op.cell_number) {
+                (None, None) => 0,
+                (None, Some(_)) => target?,
+                (Some(_), _) => target? + 1,
+            };
+            cells.insert(position, new_cell(op));
+            (added, 0)
+        }
+        NotebookEditMode::Delete => {
+            let cell = cells.remove(target?);
+            (0, cell_source(&cell).lines().count())
+        }
+    };
+
+    Some(NotebookReplayOutcome {
+        content: to_notebook_string(&notebook)?,
+        lines_added,
+        lines_removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn notebook() -> String {
+        json!({
+            "cells": [
+                {"id": "a1", "cell_type": "code", "metadata": {}, "source": ["x = 1\n", "y = 2"], "outputs": [], "execution_count": null},
+                {"id": "b2", "cell_type": "markdown", "metadata": {}, "source": "# Title"}
+            ],
+            "metadata": {},
+            "nbformat": 4,
+            "nbformat_minor": 5
+        })
+        .to_string()
+    }
+
+    fn cells(content: &str) -> Vec<Value> {
+        let nb: Value = serde_json::from_str(content).unwrap();
+        nb["cells"].as_array().unwrap().clone()
+    }
+
+    #[test]
+    fn test_replace_cell_by_id() {
+        let input =
+            json!({"cell_id": "a1", "new_source": "x = 10\nprint(x)", "edit_mode": "replace"});
+        let op = NotebookEditOperation::from_value(&input).unwrap();
+        let outcome = replay_notebook_edit(&notebook(), &op).unwrap();
+
+        let cells = cells(&outcome.content);
+        assert_eq!(cell_source(&cells[0]), "x = 10\nprint(x)");
+        assert_eq!(cells[0]["source"], json!(["x = 10\n", "print(x)"]));
+        assert_eq!(outcome.lines_added, 2);
+        assert_eq!(outcome.lines_removed, 2);
+    }
+
+    #[test]
+    fn test_insert_after_cell_and_delete() {
+        let insert = json!({"cell_id": "a1", "new_source": "z = 3", "cell_type": "code", "edit_mode": "insert"});
+        let op = NotebookEditOperation::from_value(&insert).unwrap();
+        let inserted = replay_notebook_edit(&notebook(), &op).unwrap();
+        let after_insert = cells(&inserted.content);
+        assert_eq!(after_insert.len(), 3);
+        assert_eq!(cell_source(&after_insert[1]), "z = 3");
+        assert_eq!(after_insert[1]["outputs"], json!([]));
+
+        let delete = json!({"cell_id": "b2", "new_source": "", "edit_mode": "delete"});
+        let op = NotebookEditOperation::from_value(&delete).unwrap();
+        let deleted = replay_notebook_edit(&inserted.content, &op).unwrap();
+        assert_eq!(cells(&deleted.content).len(), 2);
+        assert_eq!(deleted.lines_removed, 1);
+    }
+
+    #[test]
+    fn test_insert_at_end_by_number() {
+        let insert = json!({"cell_number": 2, "new_source": "z = 3", "edit_mode": "insert"});
+        let op = NotebookEditOperation::from_value(&insert).unwrap();
+        let inserted = replay_notebook_edit(&notebook(), &op).unwrap();
+        let after_insert = cells(&inserted.content);
+        assert_eq!(after_insert.len(), 3);
+        assert_eq!(cell_source(&after_insert[2]), "z = 3");
+
+        // Only inserts may address the slot after the last cell
+        let replace = json!({"cell_number": 2, "new_source": "z = 3"});
+        let op = NotebookEditOperation::from_value(&replace).unwrap();
+        assert!(replay_notebook_edit(&notebook(), &op).is_none());
+    }
+
+    #[test]
+    fn test_positional_cell_id_and_missing_cell() {
+        let input = json!({"cell_id": "cell-1", "new_source": "# Renamed"});
+        let op = NotebookEditOperation::from_value(&input).unwrap();
+        let outcome = replay_notebook_edit(&notebook(), &op).unwrap();
+        assert_eq!(cell_source(&cells(&outcome.content)[1]), "# Renamed");
+
+        let missing = json!({"cell_id": "nope", "new_source": "x"});
+        let op = NotebookEditOperation::from_value(&missing).unwrap();
+        assert!(replay_notebook_edit(&notebook(), &op).is_none());
+    }
+}
@@ -3,11 +3,14 @@
 //! Provides functionality to rename Claude Code sessions by modifying
 //! the first user message in the session JSONL file.
 
+use crate::commands::claude_dir::claude_dir;
+use crate::commands::metadata::read_user_settings;
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
 use tauri::command;
 
 lazy_static! {
@@ -75,7 +78,7 @@ pub async fn rename_session_native(
         return Err(RenameError::FileNotFound(file_path).to_string());
     }
 
-    // 2. Validate file path is within ~/.claude directory (security: prevent path traversal)
+    // 2. Validate file path is within a Claude folder (security: prevent path traversal)
     validate_claude_path(&file_path)?;
 
     // 3. Validate title does not contain ']' character (due to nested bracket limitation)
@@ -165,14 +168,28 @@ pub async fn rename_session_native(
     })
 }
 
-/// Validates that the file path is within the ~/.claude directory.
+/// Validates that the file path is within ~/.claude or a registered data root.
 /// This prevents path traversal attacks that could modify arbitrary files.
+fn validate_claude_path(file_path: &str) -> Result<(), String> {
+    let mut roots = vec![claude_dir().map_err(|e| RenameError::IoError(e).to_string())?];
+    // Unreadable settings only cost the registered roots
+    roots.extend(
+        read_user_settings()
+            .map(|settings| settings.data_roots)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|root| PathBuf::from(root.path)),
+    );
+    validate_path_in_roots(file_path, &roots)
+}
+
+/// Validates that the file path is within one of `roots`.
 ///
 /// Security checks performed:
 /// 1. Path must be absolute
 /// 2. No symlinks allowed in any path component
 /// 3. Filename must match pattern ^[A-Za-z0-9_-]+$
-fn validate_claude_path(file_path: &str) -> Result<(), String> {
+fn validate_path_in_roots(file_path: &str, roots: &[PathBuf]) -> Result<(), String> {
     let file_path_buf = std::path::PathBuf::from(file_path);
 
     // 1. Require absolute path
@@ -230,18 +247,14 @@ fn validate_claude_path(file_path: &str) -> Result<(), String> {
         .canonicalize()
         .map_err(|e| RenameError::IoError(e.to_string()).to_string())?;
 
-    // Get home directory
-    let home_dir = dirs::home_dir().ok_or_else(|| {
-        RenameError::IoError("Cannot determine home directory".to_string()).to_string()
-    })?;
-
-    // Build the allowed claude directory path
-    let claude_dir = home_dir.join(".claude");
-
-    // Verify the file is within ~/.claude
-    if !canonical_path.starts_with(&claude_dir) {
+    // Verify the file is within a Claude folder
+    let in_root = roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| canonical_path.starts_with(root));
+    if !in_root {
         return Err(RenameError::PermissionDenied(
-            "File path must be within ~/.claude directory".to_string(),
+            "File path must be within ~/.claude or a registered data root".to_string(),
         )
         .to_string());
     }
@@ -690,8 +703,8 @@ mod tests {
     #[test]
     fn test_validate_claude_path_valid_path() {
         // This test requires a real .jsonl file in ~/.claude to exist
-        if let Some(home) = dirs::home_dir() {
-            let claude_projects = home.join(".claude/projects");
+        if let Ok(claude_dir) = claude_dir() {
+            let claude_projects = claude_dir.join("projects");
             if claude_projects.exists() {
                 // Try to find any .jsonl file in projects subdirectories
                 if let Ok(projects) = fs::read_dir(&claude_projects) {
@@ -719,6 +732,23 @@ mod tests {
         // Skip test if no suitable file found
     }
 
+    #[test]
+    fn test_validate_path_in_registered_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("remote").join(".claude");
+        let project = root.join("projects").join("-work-app");
+        fs::create_dir_all(&project).unwrap();
+        let session = project.join("session-1.jsonl");
+        fs::write(&session, "{}\n").unwrap();
+        let session = session.to_string_lossy().to_string();
+
+        assert!(validate_path_in_roots(&session, std::slice::from_ref(&root)).is_ok());
+        let other = temp.path().join("other");
+        fs::create_dir_all(&other).unwrap();
+        let result = validate_path_in_roots(&session, &[other, root.join("missing")]);
+        assert!(result.unwrap_err().contains("registered data root"));
+    }
+
     #[test]
     fn test_validate_claude_path_nonexistent_file() {
         // Nonexistent file should fail at canonicalize
@@ -729,8 +759,8 @@ mod tests {
     #[test]
     fn test_validate_claude_path_filename_with_special_chars() {
         // Test filename validation with various invalid characters
-        if let Some(home) = dirs::home_dir() {
-            let claude_dir = home.join(".claude/projects");
+        if let Ok(claude_dir) = claude_dir() {
+            let claude_dir = claude_dir.join("projects");
             // Filename with dot (besides extension) should fail
             let path_with_dot = claude_dir
                 .join("test.file.jsonl")
@@ -0,0 +1,229 @@
+//! Edit replay engine
+//!
+//! Reconstructs file contents from `Edit`/`MultiEdit` tool results by replaying
+//! each replacement against `originalFile`, honoring `replace_all`, and checks
+//! the outcome against the recorded `structuredPatch` when one is available.
+
+use crate::models::ReplayStatus;
+
+/// A single string replacement as recorded by the Edit/MultiEdit tools
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EditOperation<'a> {
+    pub old_string: &'a str,
+    pub new_string: &'a str,
+    pub replace_all: bool,
+}
+
+impl<'a> EditOperation<'a> {
+    /// Parse a `MultiEdit` `edits[]` item (`old_string`/`new_string`/`replace_all`)
+    pub fn from_multi_edit_item(item: &'a serde_json::Value) -> Option<Self> {
+        Some(Self {
+            old_string: item.get("old_string")?.as_str()?,
+            new_string: item.get("new_string")?.as_str()?,
+            replace_all: item
+                .get("replace_all")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+
+    /// Parse a single Edit tool result (`oldString`/`newString`/`replaceAll`)
+    pub fn from_edit_result(result: &'a serde_json::Value) -> Option<Self> {
+        Some(Self {
+            old_string: result.get("oldString")?.as_str()?,
+            new_string: result.get("newString")?.as_str()?,
+            replace_all: result
+                .get("replaceAll")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+}
+
+/// Result of replaying a sequence of edits
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ReplayOutcome {
+    pub content: String,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    /// Indices of edits whose `old_string` was not found
+    pub failed: Vec<usize>,
+}
+
+/// Replay `edits` in order on top of `original`
+///
+/// An empty `old_string` only applies to empty content (file creation),
+/// matching how the Edit tool behaves.
+pub(crate) fn replay_edits(original: &str, edits: &[EditOperation<'_>]) -> ReplayOutcome {
+    let mut content = original.to_string();
+    let mut lines_added = 0usize;
+    let mut lines_removed = 0usize;
+    let mut failed = Vec::new();
+
+    for (idx, edit) in edits.iter().enumerate() {
+        let occurrences = if edit.old_string.is_empty() {
+            usize::from(content.is_empty())
+        } else if edit.replace_all {
+            content.matches(edit.old_string).count()
+        } else {
+            usize::from(content.contains(edit.old_string))
+        };
+
+        if occurrences == 0 {
+            failed.push(idx);
+            continue;
+        }
+
+        content = if edit.old_string.is_empty() {
+            edit.new_string.to_string()
+        } else if edit.replace_all {
+            content.replace(edit.old_string, edit.new_string)
+        } else {
+            content.replacen(edit.old_string, edit.new_string, 1)
+        };
+        lines_removed += edit.old_string.lines().count() * occurrences;
+        lines_added += edit.new_string.lines().count() * occurrences;
+    }
+
+    ReplayOutcome {
+        content,
+        lines_added,
+        lines_removed,
+        failed,
+    }
+}
+
+/// Check reconstructed content against a `structuredPatch` hunk list
+///
+/// Every hunk's context and added lines must appear at `newStart` in the
+/// reconstructed content. Returns None when the patch is missing or malformed.
+pub(crate) fn verify_structured_patch(content: &str, patch: &serde_json::Value) -> Option<bool> {
+    let hunks = patch.as_array()?;
+    if hunks.is_empty() {
+        return None;
+    }
+
+    let content_lines: Vec<&str> = content.lines().collect();
+
+    for hunk in hunks {
+        let new_start = hunk.get("newStart")?.as_u64()? as usize;
+        let lines = hunk.get("lines")?.as_array()?;
+
+        let expected: Vec<&str> = lines
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .filter(|line| !line.starts_with('-') && !line.starts_with('\\'))
+            .map(|line| line.get(1..).unwrap_or(""))
+            .collect();
+
+        // newStart is 1-based; a zero start means the hunk is an empty file
+        let start = new_start.saturating_sub(1);
+        let Some(actual) = content_lines.get(start..start + expected.len()) else {
+            return Some(false);
+        };
+        if actual != expected.as_slice() {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+/// Decide the replay status from the replay outcome and the optional patch
+pub(crate) fn replay_status(
+    outcome: &ReplayOutcome,
+    patch: Option<&serde_json::Value>,
+) -> ReplayStatus {
+    if !outcome.failed.is_empty() {
+        return ReplayStatus::Mismatched;
+    }
+    match patch.and_then(|p| verify_structured_patch(&outcome.content, p)) {
+        Some(true) => ReplayStatus::Verified,
+        Some(false) => ReplayStatus::Mismatched,
+        None => ReplayStatus::Unverified,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn op<'a>(old: &'a str, new: &'a str, replace_all: bool) -> EditOperation<'a> {
+        EditOperation {
+            old_string: old,
+            new_string: new,
+            replace_all,
+        }
+    }
+
+    #[test]
+    fn test_replay_replaces_first_occurrence_by_default() {
+        let outcome = replay_edits("a a a", &[op("a", "b", false)]);
+        assert_eq!(outcome.content, "b a a");
+        assert!(outcome.failed.is_empty());
+    }
+
+    #[test]
+    fn test_replay_honors_replace_all() {
+        let outcome = replay_edits("x\nfoo\nfoo\n", &[op("foo", "bar\nbaz", true)]);
+        assert_eq!(outcome.content, "x\nbar\nbaz\nbar\nbaz\n");
+        assert_eq!(outcome.lines_removed, 2);
+        assert_eq!(outcome.lines_added, 4);
+    }
+
+    #[test]
+    fn test_replay_reports_missing_old_string() {
+        let outcome = replay_edits(
+            "hello",
+            &[op("missing", "x", false), op("hello", "hi", false)],
+        );
+        assert_eq!(outcome.failed, vec![0]);
+        assert_eq!(outcome.content, "hi");
+        assert_eq!(replay_status(&outcome, None), ReplayStatus::Mismatched);
+    }
+
+    #[test]
+    fn test_replay_empty_old_string_creates_file() {
+        assert_eq!(replay_edits("", &[op("", "new", false)]).content, "new");
+        assert_eq!(
+            replay_edits("existing", &[op("", "new", false)]).failed,
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn test_verify_structured_patch() {
+        let content = "fn main() {\n    println!(\"hi\");\n}\n";
+        let patch = json!([{
+            "oldStart": 1, "oldLines": 3, "newStart": 1, "newLines": 3,
+            "lines": [" fn main() {", "-    println!(\"hello\");", "+    println!(\"hi\");", " }"]
+        }]);
+        assert_eq!(verify_structured_patch(content, &patch), Some(true));
+
+        let wrong = "fn main() {\n    println!(\"hello\");\n}\n";
+        assert_eq!(verify_structured_patch(wrong, &patch), Some(false));
+        assert_eq!(verify_structured_patch(content, &json!([])), None);
+    }
+
+    #[test]
+    fn test_replay_status_without_patch_is_unverified() {
+        let outcome = replay_edits("a", &[op("a", "b", false)]);
+        assert_eq!(replay_status(&outcome, None), ReplayStatus::Unverified);
+    }
+
+    #[test]
+    fn test_edit_operation_parsing() {
+        let item = json!({"old_string": "a", "new_string": "b", "replace_all": true});
+        assert_eq!(
+            EditOperation::from_multi_edit_item(&item),
+            Some(op("a", "b", true))
+        );
+
+        let result = json!({"oldString": "a", "newString": "b"});
+        assert_eq!(
+            EditOperation::from_edit_result(&result),
+            Some(op("a", "b", false))
+        );
+    }
+}
@@ -1,12 +1,12 @@
 //! Session search functions
 
-use crate::models::{ClaudeMessage, RawLogEntry};
+use crate::commands::archive::{archive_projects_dir, is_session_file, read_session_bytes};
+use crate::commands::roots::selected_roots;
+use crate::models::{ClaudeMessage, RawLogEntry, RootSelection, SessionContextFilter};
 use crate::utils::find_line_ranges;
 use chrono::Utc;
-use memmap2::Mmap;
 use rayon::prelude::*;
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 use walkdir::WalkDir;
 
@@ -39,52 +39,66 @@ fn extract_project_name(file_path: &PathBuf) -> Option<String> {
         .map(std::string::ToString::to_string)
 }
 
-/// Search for messages matching the query in a single file
+/// Collect every session file (`*.jsonl`, or archived `*.jsonl.gz`) under a projects directory
+pub(crate) fn collect_session_files(projects_path: &Path) -> Vec<PathBuf> {
+    WalkDir::new(projects_path)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|e| is_session_file(e.path()))
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+/// Memory-map a session file (or inflate an archived one) and pass every
+/// parseable line to `visit` along with its zero-based line index.
+/// Unreadable files and lines that fail to parse are skipped.
 ///
 /// Uses a reusable buffer to avoid repeated heap allocations during JSON parsing.
-#[allow(unsafe_code)] // Required for mmap performance optimization
-fn search_in_file(file_path: &PathBuf, query: &str) -> Vec<ClaudeMessage> {
-    let query_lower = query.to_lowercase();
-    let project_name = extract_project_name(file_path);
-
-    let file = match fs::File::open(file_path) {
-        Ok(f) => f,
-        Err(_) => return Vec::new(),
-    };
-
-    // SAFETY: We're only reading the file, and the file handle is kept open
-    // for the duration of the mmap's lifetime. Session files are append-only.
-    let mmap = match unsafe { Mmap::map(&file) } {
-        Ok(m) => m,
-        Err(_) => return Vec::new(),
+pub(crate) fn scan_log_entries(file_path: &Path, mut visit: impl FnMut(usize, RawLogEntry)) {
+    let Ok(contents) = read_session_bytes(file_path) else {
+        return;
     };
 
-    // Use SIMD-accelerated line detection
-    let line_ranges = find_line_ranges(&mmap);
-
-    let mut results = Vec::with_capacity(SEARCH_RESULTS_INITIAL_CAPACITY);
-
     // Reusable buffer for simd-json parsing (requires mutable slice)
     // This avoids heap allocation per line
     let mut parse_buffer = Vec::with_capacity(PARSE_BUFFER_INITIAL_CAPACITY);
 
-    for (line_num, (start, end)) in line_ranges.iter().enumerate() {
-        // Reuse buffer instead of allocating new Vec each iteration
+    // Use SIMD-accelerated line detection
+    for (line_num, (start, end)) in find_line_ranges(&contents).into_iter().enumerate() {
         parse_buffer.clear();
-        parse_buffer.extend_from_slice(&mmap[*start..*end]);
+        parse_buffer.extend_from_slice(&contents[start..end]);
 
-        let log_entry: RawLogEntry = match simd_json::serde::from_slice(&mut parse_buffer) {
-            Ok(entry) => entry,
-            Err(_) => continue,
-        };
+        if let Ok(entry) = simd_json::serde::from_slice::<RawLogEntry>(&mut parse_buffer) {
+            visit(line_num, entry);
+        }
+    }
+}
+
+/// Search for messages matching the query in a single file
+fn search_in_file(
+    file_path: &PathBuf,
+    query: &str,
+    context: &SessionContextFilter,
+) -> Vec<ClaudeMessage> {
+    let query_lower = query.to_lowercase();
+    let project_name = extract_project_name(file_path);
+
+    let mut results = Vec::with_capacity(SEARCH_RESULTS_INITIAL_CAPACITY);
 
+    scan_log_entries(file_path, |line_num, log_entry| {
         if log_entry.message_type != "user" && log_entry.message_type != "assistant" {
-            continue;
+            return;
+        }
+
+        if !context.matches(
+            log_entry.git_branch.as_deref(),
+            log_entry.version.as_deref(),
+        ) {
+            return;
         }
 
-        let message_content = match &log_entry.message {
-            Some(mc) => mc,
-            None => continue,
+        let Some(message_content) = &log_entry.message else {
+            return;
         };
 
         // Use recursive search to avoid JSON serialization overhead
@@ -97,7 +111,7 @@ fn search_in_file(file_path: &PathBuf, query: &str) -> Vec<ClaudeMessage> {
         };
 
         if !matches {
-            continue;
+            return;
         }
 
         let claude_message = ClaudeMessage {
@@ -140,7 +154,7 @@ fn search_in_file(file_path: &PathBuf, query: &str) -> Vec<ClaudeMessage> {
             microcompact_metadata: None,
         };
         results.push(claude_message);
-    }
+    });
 
     results
 }
@@ -148,38 +162,55 @@ fn search_in_file(file_path: &PathBuf, query: &str) -> Vec<ClaudeMessage> {
 /// Default limit for search results
 const DEFAULT_SEARCH_LIMIT: usize = 100;
 
+/// The `gitBranch` and `version` entries of the frontend's search filters
+fn context_filter(filters: &serde_json::Value) -> SessionContextFilter {
+    let text = |key: &str| {
+        filters
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    SessionContextFilter {
+        git_branch: text("gitBranch"),
+        version: text("version"),
+    }
+}
+
 #[tauri::command]
 pub async fn search_messages(
     claude_path: String,
     query: String,
-    _filters: serde_json::Value,
+    filters: serde_json::Value,
     limit: Option<usize>,
+    roots: Option<RootSelection>,
 ) -> Result<Vec<ClaudeMessage>, String> {
     #[cfg(debug_assertions)]
     let start_time = std::time::Instant::now();
 
     let max_results = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
-    let projects_path = PathBuf::from(&claude_path).join("projects");
+    let context = context_filter(&filters);
 
-    if !projects_path.exists() {
-        return Ok(vec![]);
+    // 1. Collect all JSONL file paths from the selected roots, including archived sessions
+    let mut file_paths = Vec::new();
+    for root in selected_roots(&claude_path, roots.as_ref())? {
+        let projects_path = root.projects_path();
+        if !projects_path.exists() {
+            continue;
+        }
+        file_paths.extend(collect_session_files(&projects_path));
+        if let Ok(archive) = archive_projects_dir(&root.path) {
+            file_paths.extend(collect_session_files(&archive));
+        }
     }
 
-    // 1. Collect all JSONL file paths
-    let file_paths: Vec<PathBuf> = WalkDir::new(&projects_path)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
-        .map(|e| e.path().to_path_buf())
-        .collect();
-
     #[cfg(debug_assertions)]
     eprintln!("🔍 search_messages: searching {} files", file_paths.len());
 
     // 2. Parallel search using rayon
     let mut all_messages: Vec<ClaudeMessage> = file_paths
         .par_iter()
-        .flat_map(|path| search_in_file(path, &query))
+        .flat_map(|path| search_in_file(path, &query, &context))
         .collect();
 
     // 3. Sort by timestamp descending and truncate to limit
@@ -242,6 +273,7 @@ mod tests {
             "Rust".to_string(),
             serde_json::json!({}),
             None,
+            None,
         )
         .await;
 
@@ -271,6 +303,7 @@ mod tests {
             "hello".to_string(), // lowercase
             serde_json::json!({}),
             None,
+            None,
         )
         .await;
 
@@ -300,6 +333,7 @@ mod tests {
             "nonexistent".to_string(),
             serde_json::json!({}),
             None,
+            None,
         )
         .await;
 
@@ -307,6 +341,49 @@ mod tests {
         assert!(result.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_search_messages_filters_by_branch_and_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("test-project");
+        std::fs::create_dir_all(&project_dir).unwrap();
+
+        let line = |uuid: &str, branch: &str, version: &str| {
+            format!(
+                r#"{{"uuid":"{uuid}","sessionId":"session-1","timestamp":"2025-06-26T10:00:00Z","type":"user","gitBranch":"{branch}","version":"{version}","message":{{"role":"user","content":"Fix the parser"}}}}"#
+            )
+        };
+        let content = format!(
+            "{}\n{}\n{}\n",
+            line("uuid-1", "main", "1.0.80"),
+            line("uuid-2", "feature/parser", "1.0.80"),
+            line("uuid-3", "feature/parser", "1.0.85"),
+        );
+        std::fs::write(project_dir.join("test.jsonl"), content).unwrap();
+
+        let search = |filters: serde_json::Value| {
+            search_messages(
+                temp_dir.path().to_string_lossy().to_string(),
+                "parser".to_string(),
+                filters,
+                None,
+                None,
+            )
+        };
+        assert_eq!(search(serde_json::json!({})).await.unwrap().len(), 3);
+        let on_branch = search(serde_json::json!({"gitBranch": "feature/parser"}))
+            .await
+            .unwrap();
+        assert_eq!(on_branch.len(), 2);
+        let on_version = search(serde_json::json!({
+            "gitBranch": "feature/parser",
+            "version": "1.0.85"
+        }))
+        .await
+        .unwrap();
+        assert_eq!(on_version.len(), 1);
+        assert_eq!(on_version[0].uuid, "uuid-3");
+    }
+
     #[tokio::test]
     async fn test_search_messages_empty_projects_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -317,6 +394,7 @@ mod tests {
             "test".to_string(),
             serde_json::json!({}),
             None,
+            None,
         )
         .await;
 
@@ -0,0 +1,420 @@
+//! Shell command path extraction
+//!
+//! Files changed through the Bash tool (`sed -i`, `mv`, `cat > file`, ...) leave
+//! no content behind in the transcript. This module only figures out which
+//! paths a command may have touched so edit recovery can flag them.
+
+use std::path::Path;
+
+/// Commands whose every non-option argument is a modified path
+const ALL_ARGS_COMMANDS: &[&str] = &["rm", "touch", "truncate", "tee", "unlink", "shred"];
+
+/// Commands whose non-option arguments are `source... destination`
+const MOVE_COMMANDS: &[&str] = &["mv"];
+
+/// Commands whose last non-option argument is the only modified path
+const COPY_COMMANDS: &[&str] = &["cp", "install", "rsync"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Redirect,
+    Separator,
+}
+
+/// Drop heredoc bodies so their text is not parsed as commands
+fn strip_heredocs(command: &str) -> String {
+    let mut out = String::with_capacity(command.len());
+    let mut delimiter: Option<String> = None;
+
+    for line in command.lines() {
+        if let Some(delim) = &delimiter {
+            if line.trim() == delim {
+                delimiter = None;
+            }
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+
+        if let Some(pos) = line.find("<<") {
+            let rest = line[pos + 2..].trim_start_matches(['-', '~']).trim_start();
+            let delim: String = rest
+                .chars()
+                .take_while(|c| !c.is_whitespace() && !matches!(c, ';' | '&' | '|' | '>' | ')'))
+                .filter(|c| !matches!(c, '\'' | '"' | '\\'))
+                .collect();
+            if !delim.is_empty() {
+                delimiter = Some(delim);
+            }
+        }
+    }
+
+    out
+}
+
+/// Split a command into words, redirections and command separators
+///
+/// Handles single/double quotes and backslash escapes; expansions are kept verbatim.
+fn tokenize(command: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+
+    let flush = |word: &mut String, in_word: &mut bool, tokens: &mut Vec<Token>| {
+        if *in_word {
+            tokens.push(Token::Word(std::mem::take(word)));
+            *in_word = false;
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                for q in chars.by_ref() {
+                    if q == '\'' {
+                        break;
+                    }
+                    word.push(q);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(q) = chars.next() {
+                    match q {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                word.push(escaped);
+                            }
+                        }
+                        _ => word.push(q),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    if escaped != '\n' {
+                        in_word = true;
+                        word.push(escaped);
+                    }
+                }
+            }
+            ';' | '\n' | '(' | ')' => {
+                flush(&mut word, &mut in_word, &mut tokens);
+                tokens.push(Token::Separator);
+            }
+            '|' | '&' => {
+                // `&>` redirects both streams, everything else ends the command
+                if c == '&' && chars.peek() == Some(&'>') {
+                    flush(&mut word, &mut in_word, &mut tokens);
+                    chars.next();
+                    if chars.peek() == Some(&'>') {
+                        chars.next();
+                    }
+                    tokens.push(Token::Redirect);
+                    continue;
+                }
+                flush(&mut word, &mut in_word, &mut tokens);
+                if chars.peek() == Some(&c) {
+                    chars.next();
+                }
+                tokens.push(Token::Separator);
+            }
+            '>' => {
+                // A leading file descriptor (`2>`) belongs to the redirect
+                let fd_prefix = in_word && word.chars().all(|d| d.is_ascii_digit());
+                if fd_prefix {
+                    word.clear();
+                    in_word = false;
+                }
+                flush(&mut word, &mut in_word, &mut tokens);
+                if matches!(chars.peek(), Some('>' | '|')) {
+                    chars.next();
+                }
+                if chars.peek() == Some(&'&') {
+                    // `>&2` duplicates a descriptor rather than naming a file
+                    chars.next();
+                    while chars.peek().is_some_and(char::is_ascii_digit) {
+                        chars.next();
+                    }
+                    continue;
+                }
+                tokens.push(Token::Redirect);
+            }
+            '<' => {
+                flush(&mut word, &mut in_word, &mut tokens);
+                while matches!(chars.peek(), Some('<' | '-')) {
+                    chars.next();
+                }
+                // Skip the input file / heredoc delimiter word
+                while chars
+                    .peek()
+                    .is_some_and(|c| c.is_whitespace() && *c != '\n')
+                {
+                    chars.next();
+                }
+                while chars
+                    .peek()
+                    .is_some_and(|c| !c.is_whitespace() && !matches!(c, ';' | '|' | '&' | '>'))
+                {
+                    chars.next();
+                }
+            }
+            c if c.is_whitespace() => flush(&mut word, &mut in_word, &mut tokens),
+            _ => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    flush(&mut word, &mut in_word, &mut tokens);
+
+    tokens
+}
+
+/// Positional arguments with option flags removed
+fn operands(args: &[String]) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut end_of_options = false;
+    for arg in args {
+        if !end_of_options && arg == "--" {
+            end_of_options = true;
+        } else if end_of_options || !arg.starts_with('-') || arg == "-" {
+            result.push(arg.as_str());
+        }
+    }
+    result
+}
+
+/// Whether `arg` is `-i`, `-i<suffix>` or `--in-place`, possibly bundled
+/// after switches that take no value, as in `perl -pi`
+fn is_in_place_flag(program: &str, arg: &str) -> bool {
+    if arg == "--in-place" || arg.starts_with("--in-place=") {
+        return true;
+    }
+    let Some(flags) = arg.strip_prefix('-') else {
+        return false;
+    };
+    let bundled: &[char] = if program == "perl" {
+        &['p', 'n', 'a', 'l', 'w']
+    } else {
+        &['n', 'E', 'r', 's', 'z']
+    };
+    flags
+        .find('i')
+        .is_some_and(|pos| flags[..pos].chars().all(|c| bundled.contains(&c)))
+}
+
+/// Files passed to `sed -i` / `perl -i`, skipping the inline script
+fn in_place_operands(program: &str, args: &[String]) -> Vec<String> {
+    if !args.iter().any(|arg| is_in_place_flag(program, arg)) {
+        return Vec::new();
+    }
+
+    let mut files = Vec::new();
+    let mut has_script_flag = false;
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-e" | "--expression" | "-f" | "--file" => {
+                has_script_flag = true;
+                iter.next();
+            }
+            a if program == "perl" && a.starts_with('-') && a.ends_with('e') => {
+                has_script_flag = true;
+                iter.next();
+            }
+            // BSD `sed -i ''` passes an empty backup suffix
+            a if a.is_empty() || (a.starts_with('-') && a != "-") => {}
+            a => files.push(a.to_string()),
+        }
+    }
+
+    // Without -e the first operand is the script itself
+    if !has_script_flag && !files.is_empty() {
+        files.remove(0);
+    }
+    files
+}
+
+/// Paths modified by a single simple command (`program args...`)
+fn command_targets(words: &[String]) -> Vec<String> {
+    // Skip environment assignments and wrappers such as `sudo` or `env`
+    let start = words.iter().position(|w| {
+        !w.contains('=') && !matches!(w.as_str(), "sudo" | "env" | "command" | "nohup" | "time")
+    });
+    let Some(start) = start else {
+        return Vec::new();
+    };
+    let program = words[start].rsplit('/').next().unwrap_or(&words[start]);
+    let args = &words[start + 1..];
+
+    match program {
+        "sed" | "gsed" | "perl" => in_place_operands(program, args),
+        p if ALL_ARGS_COMMANDS.contains(&p) => {
+            let mut ops = operands(args);
+            if p == "truncate" {
+                // `truncate -s SIZE file`
+                if let Some(size_idx) = args.iter().position(|a| a == "-s" || a == "--size") {
+                    if let Some(size) = args.get(size_idx + 1) {
+                        ops.retain(|op| *op != size.as_str());
+                    }
+                }
+            }
+            ops.into_iter().map(str::to_string).collect()
+        }
+        p if MOVE_COMMANDS.contains(&p) => operands(args).into_iter().map(str::to_string).collect(),
+        p if COPY_COMMANDS.contains(&p) => operands(args)
+            .last()
+            .map(|dest| vec![(*dest).to_string()])
+            .unwrap_or_default(),
+        "git" => {
+            // `git checkout -- file` and `git restore file` overwrite working tree files
+            let ops = operands(args);
+            match ops.first() {
+                Some(&"checkout") if args.iter().any(|a| a == "--") => {
+                    let dashdash = args.iter().position(|a| a == "--").unwrap_or(args.len());
+                    args[dashdash + 1..].to_vec()
+                }
+                Some(&"restore") => ops[1..].iter().map(|s| (*s).to_string()).collect(),
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn resolve_path(path: &str, cwd: Option<&str>) -> Option<String> {
+    if path.is_empty()
+        || path == "-"
+        || path.starts_with("/dev/")
+        || path.starts_with("/proc/")
+        || path.contains(['$', '`', '*', '?'])
+    {
+        return None;
+    }
+    if Path::new(path).is_absolute() {
+        return Some(path.to_string());
+    }
+    let cwd = cwd?;
+    let relative = path.strip_prefix("./").unwrap_or(path);
+    Some(Path::new(cwd).join(relative).to_string_lossy().to_string())
+}
+
+/// Extract the paths a Bash command may have modified
+///
+/// Relative paths are resolved against `cwd`; paths containing shell expansions
+/// and device files are ignored, and `cd` is followed between commands. The
+/// result is deduplicated in first-seen order.
+pub(crate) fn extract_shell_modified_paths(command: &str, cwd: Option<&str>) -> Vec<String> {
+    let tokens = tokenize(&strip_heredocs(command));
+    let mut cwd = cwd.map(str::to_string);
+    let mut paths: Vec<String> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut redirects: Vec<String> = Vec::new();
+    let mut expect_redirect_target = false;
+
+    for token in tokens.into_iter().chain(std::iter::once(Token::Separator)) {
+        match token {
+            Token::Word(word) if expect_redirect_target => {
+                redirects.push(word);
+                expect_redirect_target = false;
+            }
+            Token::Word(word) => words.push(word),
+            Token::Redirect => expect_redirect_target = true,
+            Token::Separator => {
+                expect_redirect_target = false;
+                redirects.extend(command_targets(&words));
+                for path in redirects.drain(..) {
+                    if let Some(path) = resolve_path(&path, cwd.as_deref()) {
+                        if !paths.contains(&path) {
+                            paths.push(path);
+                        }
+                    }
+                }
+                // Follow `cd` so later relative paths resolve correctly
+                if words.first().map(String::as_str) == Some("cd") {
+                    cwd = words
+                        .get(1)
+                        .and_then(|dir| resolve_path(dir, cwd.as_deref()));
+                }
+                words.clear();
+            }
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(command: &str) -> Vec<String> {
+        extract_shell_modified_paths(command, Some("/repo"))
+    }
+
+    #[test]
+    fn test_sed_in_place() {
+        assert_eq!(
+            paths("sed -i 's/a/b/' src/main.rs"),
+            vec!["/repo/src/main.rs"]
+        );
+        assert_eq!(
+            paths("sed -i.bak -e 's/a/b/' -e 's/c/d/' a.txt /abs/b.txt"),
+            vec!["/repo/a.txt", "/abs/b.txt"]
+        );
+        assert!(paths("sed 's/a/b/' a.txt").is_empty());
+        assert_eq!(paths("perl -pi -e 's/x/y/' lib.pl"), vec!["/repo/lib.pl"]);
+        assert_eq!(paths("sed -i '' 's/a/b/' f.txt"), vec!["/repo/f.txt"]);
+        assert_eq!(paths("sed -ni 's/a/b/p' f.txt"), vec!["/repo/f.txt"]);
+        assert!(paths("perl -Mstrict -e 'print 1' f.txt").is_empty());
+        assert!(paths("sed --debug 's/a/b/' f.txt").is_empty());
+    }
+
+    #[test]
+    fn test_redirections() {
+        assert_eq!(paths("echo hi > out.txt"), vec!["/repo/out.txt"]);
+        assert_eq!(paths("echo hi >>log.txt 2>&1"), vec!["/repo/log.txt"]);
+        assert!(paths("make 2>/dev/null").is_empty());
+        assert_eq!(
+            paths("cat > notes.md <<'EOF'\nrm -rf important\nEOF\ntouch done"),
+            vec!["/repo/notes.md", "/repo/done"]
+        );
+    }
+
+    #[test]
+    fn test_file_management_commands() {
+        assert_eq!(
+            paths("mv old.rs new.rs && cp a.rs backup/a.rs; rm -f tmp.txt"),
+            vec![
+                "/repo/old.rs",
+                "/repo/new.rs",
+                "/repo/backup/a.rs",
+                "/repo/tmp.txt"
+            ]
+        );
+        assert_eq!(
+            paths("cd x && ls | tee \"list file.txt\""),
+            vec!["/repo/x/list file.txt"]
+        );
+        assert_eq!(
+            paths("git checkout -- src/lib.rs"),
+            vec!["/repo/src/lib.rs"]
+        );
+        assert!(paths("git checkout main").is_empty());
+    }
+
+    #[test]
+    fn test_ignores_expansions_and_unresolvable_paths() {
+        assert!(paths("rm $TMPFILE *.log").is_empty());
+        assert!(extract_shell_modified_paths("touch rel.txt", None).is_empty());
+        assert_eq!(
+            extract_shell_modified_paths("touch /abs.txt", None),
+            vec!["/abs.txt"]
+        );
+    }
+}
@@ -1,10 +1,11 @@
+use crate::commands::roots::{selected_roots, ResolvedRoot};
 use crate::commands::session::load_session_messages;
 #[cfg(test)]
 use crate::models::MessageContent;
 use crate::models::{
-    ActivityHeatmap, ClaudeMessage, DailyStats, GlobalStatsSummary, ModelStats, ProjectRanking,
-    ProjectStatsSummary, RawLogEntry, SessionComparison, SessionTokenStats, TokenDistribution,
-    TokenUsage, ToolUsageStats,
+    ActivityHeatmap, BranchStats, ClaudeMessage, DailyStats, GlobalStatsSummary, ModelStats,
+    ProjectRanking, ProjectStatsSummary, RawLogEntry, RootSelection, SessionComparison,
+    SessionContextFilter, SessionTokenStats, TokenDistribution, TokenUsage, ToolUsageStats,
 };
 use crate::utils::find_line_ranges;
 use chrono::{DateTime, Datelike, Timelike, Utc};
@@ -36,11 +37,63 @@ struct SessionFileStats {
     first_message: Option<DateTime<Utc>>,
     last_message: Option<DateTime<Utc>>,
     project_name: String,
+    branch_usage: HashMap<String, (usize, TokenDistribution)>, // branch -> (messages, tokens)
 }
 
-/// Process a single session file and return aggregated stats
+/// Add one message's tokens to its branch's usage
+fn track_branch_usage(
+    branch_usage: &mut HashMap<String, (usize, TokenDistribution)>,
+    branch: String,
+    usage: &TokenUsage,
+) {
+    let (messages, tokens) = branch_usage.entry(branch).or_default();
+    *messages += 1;
+    tokens.input += u64::from(usage.input_tokens.unwrap_or(0));
+    tokens.output += u64::from(usage.output_tokens.unwrap_or(0));
+    tokens.cache_creation += u64::from(usage.cache_creation_input_tokens.unwrap_or(0));
+    tokens.cache_read += u64::from(usage.cache_read_input_tokens.unwrap_or(0));
+}
+
+/// Fold one session's branch usage into the per-branch totals
+fn merge_branch_usage(
+    branch_map: &mut HashMap<String, BranchStats>,
+    branch_usage: HashMap<String, (usize, TokenDistribution)>,
+) {
+    for (branch, (messages, tokens)) in branch_usage {
+        let entry = branch_map
+            .entry(branch.clone())
+            .or_insert_with(|| BranchStats {
+                git_branch: branch,
+                ..Default::default()
+            });
+        entry.session_count += 1;
+        entry.message_count += messages;
+        entry.total_tokens +=
+            tokens.input + tokens.output + tokens.cache_creation + tokens.cache_read;
+        entry.token_distribution.input += tokens.input;
+        entry.token_distribution.output += tokens.output;
+        entry.token_distribution.cache_creation += tokens.cache_creation;
+        entry.token_distribution.cache_read += tokens.cache_read;
+    }
+}
+
+/// Per-branch totals, most tokens first
+fn sorted_branch_stats(branch_map: HashMap<String, BranchStats>) -> Vec<BranchStats> {
+    let mut branch_stats: Vec<BranchStats> = branch_map.into_values().collect();
+    branch_stats.sort_by(|a, b| {
+        b.total_tokens
+            .cmp(&a.total_tokens)
+            .then_with(|| a.git_branch.cmp(&b.git_branch))
+    });
+    branch_stats
+}
+
+/// Process a single session file and return aggregated stats, counting only messages that pass `filter`
 #[allow(unsafe_code)] // Required for mmap performance optimization
-fn process_session_file_for_global_stats(session_path: &PathBuf) -> Option<SessionFileStats> {
+fn process_session_file_for_global_stats(
+    session_path: &PathBuf,
+    filter: &SessionContextFilter,
+) -> Option<SessionFileStats> {
     let file = fs::File::open(session_path).ok()?;
 
     // SAFETY: We're only reading the file, and the file handle is kept open
@@ -69,6 +122,14 @@ fn process_session_file_for_global_stats(session_path: &PathBuf) -> Option<Sessi
         let mut line_bytes = mmap[start..end].to_vec();
 
         if let Some(log_entry) = parse_raw_log_entry_simd(&mut line_bytes) {
+            if !filter.matches(
+                log_entry.git_branch.as_deref(),
+                log_entry.version.as_deref(),
+            ) {
+                continue;
+            }
+            let git_branch = log_entry.git_branch.clone().filter(|b| !b.is_empty());
+
             if let Ok(message) = ClaudeMessage::try_from(log_entry) {
                 stats.total_messages = stats.total_messages.saturating_add(1);
 
@@ -138,6 +199,10 @@ fn process_session_file_for_global_stats(session_path: &PathBuf) -> Option<Sessi
                         model_entry.4 += cache_creation_tokens;
                         model_entry.5 += cache_read_tokens;
                     }
+
+                    if let Some(branch) = git_branch {
+                        track_branch_usage(&mut stats.branch_usage, branch, &usage);
+                    }
                 }
 
                 // Track tool usage
@@ -146,6 +211,11 @@ fn process_session_file_for_global_stats(session_path: &PathBuf) -> Option<Sessi
         }
     }
 
+    // A session with no messages passing the filter does not count at all
+    if stats.total_messages == 0 && !filter.is_empty() {
+        return None;
+    }
+
     // Calculate session duration
     const SESSION_BREAK_THRESHOLD_MINUTES: i64 = 120;
 
@@ -189,12 +259,14 @@ struct ProjectSessionFileStats {
     session_duration_minutes: u32,
     session_dates: HashSet<String>,
     timestamps: Vec<DateTime<Utc>>,
+    branch_usage: HashMap<String, (usize, TokenDistribution)>, // branch -> (messages, tokens)
 }
 
-/// Process a single session file for project stats
+/// Process a single session file for project stats, counting only messages that pass `filter`
 #[allow(unsafe_code)] // Required for mmap performance optimization
 fn process_session_file_for_project_stats(
     session_path: &PathBuf,
+    filter: &SessionContextFilter,
 ) -> Option<ProjectSessionFileStats> {
     let file = fs::File::open(session_path).ok()?;
 
@@ -213,6 +285,14 @@ fn process_session_file_for_project_stats(
         let mut line_bytes = mmap[start..end].to_vec();
 
         if let Some(log_entry) = parse_raw_log_entry_simd(&mut line_bytes) {
+            if !filter.matches(
+                log_entry.git_branch.as_deref(),
+                log_entry.version.as_deref(),
+            ) {
+                continue;
+            }
+            let git_branch = log_entry.git_branch.clone().filter(|b| !b.is_empty());
+
             if let Ok(message) = ClaudeMessage::try_from(log_entry) {
                 stats.total_messages += 1;
 
@@ -254,6 +334,10 @@ fn process_session_file_for_project_stats(
                         u64::from(usage.cache_creation_input_tokens.unwrap_or(0));
                     stats.token_distribution.cache_read +=
                         u64::from(usage.cache_read_input_tokens.unwrap_or(0));
+
+                    if let Some(branch) = git_branch {
+                        track_branch_usage(&mut stats.branch_usage, branch, &usage);
+                    }
                 }
 
                 // Track tool usage
@@ -262,6 +346,11 @@ fn process_session_file_for_project_stats(
         }
     }
 
+    // A session with no messages passing the filter does not count at all
+    if stats.total_messages == 0 && !filter.is_empty() {
+        return None;
+    }
+
     // Calculate session duration
     const SESSION_BREAK_THRESHOLD_MINUTES: i64 = 120;
 
@@ -538,8 +627,13 @@ pub struct PaginatedTokenStats {
 }
 
 /// Synchronous version of session token stats extraction for parallel processing
+///
+/// Only messages that pass `filter` are counted.
 #[allow(unsafe_code)] // Required for mmap performance optimization
-fn extract_session_token_stats_sync(session_path: &PathBuf) -> Option<SessionTokenStats> {
+fn extract_session_token_stats_sync(
+    session_path: &PathBuf,
+    filter: &SessionContextFilter,
+) -> Option<SessionTokenStats> {
     let file = fs::File::open(session_path).ok()?;
 
     // SAFETY: We're only reading the file, and the file handle is kept open
@@ -579,6 +673,13 @@ fn extract_session_token_stats_sync(session_path: &PathBuf) -> Option<SessionTok
                 }
             }
 
+            if !filter.matches(
+                log_entry.git_branch.as_deref(),
+                log_entry.version.as_deref(),
+            ) {
+                continue;
+            }
+
             if let Ok(message) = ClaudeMessage::try_from(log_entry) {
                 if session_id.is_none() {
                     session_id = Some(message.session_id.clone());
@@ -651,6 +752,7 @@ pub async fn get_project_token_stats(
     limit: Option<usize>,
     start_date: Option<String>,
     end_date: Option<String>,
+    filter: Option<SessionContextFilter>,
 ) -> Result<PaginatedTokenStats, String> {
     if project_path.trim().is_empty() {
         return Err("project_path is required".to_string());
@@ -664,6 +766,7 @@ pub async fn get_project_token_stats(
     let start = std::time::Instant::now();
     let offset = offset.unwrap_or(0);
     let limit = limit.unwrap_or(20);
+    let filter = filter.unwrap_or_default();
 
     // Collect all session files
     let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
@@ -679,7 +782,7 @@ pub async fn get_project_token_stats(
     // Process all sessions in parallel using sync function
     let mut all_stats: Vec<SessionTokenStats> = session_files
         .par_iter()
-        .filter_map(extract_session_token_stats_sync)
+        .filter_map(|path| extract_session_token_stats_sync(path, &filter))
         .collect();
 
     #[cfg(debug_assertions)]
@@ -753,6 +856,7 @@ pub async fn get_project_stats_summary(
     project_path: String,
     start_date: Option<String>,
     end_date: Option<String>,
+    filter: Option<SessionContextFilter>,
 ) -> Result<ProjectStatsSummary, String> {
     if project_path.trim().is_empty() {
         return Err("project_path is required".to_string());
@@ -762,13 +866,30 @@ pub async fn get_project_stats_summary(
         return Err("project_path must be absolute".to_string());
     }
 
-    let start = std::time::Instant::now();
     let project_name = PathBuf::from(&project_path)
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("Unknown")
         .to_string();
 
+    Ok(summarize_project_dirs(
+        &[project_path_buf],
+        project_name,
+        start_date,
+        end_date,
+        &filter.unwrap_or_default(),
+    ))
+}
+
+/// Aggregate stats over the session files of one or more project folders
+pub(crate) fn summarize_project_dirs(
+    project_dirs: &[PathBuf],
+    project_name: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    filter: &SessionContextFilter,
+) -> ProjectStatsSummary {
+    let start = std::time::Instant::now();
     let mut s_limit = None;
     let mut e_limit = None;
 
@@ -787,8 +908,9 @@ pub async fn get_project_stats_summary(
     }
 
     // Phase 1: Collect all session files
-    let session_files: Vec<PathBuf> = WalkDir::new(&project_path)
-        .into_iter()
+    let session_files: Vec<PathBuf> = project_dirs
+        .iter()
+        .flat_map(WalkDir::new)
         .filter_map(std::result::Result::ok)
         .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
         .map(|e| e.path().to_path_buf())
@@ -798,7 +920,7 @@ pub async fn get_project_stats_summary(
     // Phase 2: Process all session files in parallel
     let mut file_stats: Vec<ProjectSessionFileStats> = session_files
         .par_iter()
-        .filter_map(process_session_file_for_project_stats)
+        .filter_map(|path| process_session_file_for_project_stats(path, filter))
         .collect();
 
     // Filter by date
@@ -827,6 +949,7 @@ pub async fn get_project_stats_summary(
     let mut daily_stats_map: HashMap<String, DailyStats> = HashMap::new();
     let mut activity_map: HashMap<(u8, u8), (u32, u64)> = HashMap::new();
     let mut session_dates: HashSet<String> = HashSet::new();
+    let mut branch_map: HashMap<String, BranchStats> = HashMap::new();
 
     for stats in file_stats {
         summary.total_messages += stats.total_messages as usize;
@@ -865,6 +988,9 @@ pub async fn get_project_stats_summary(
             entry.1 += tokens;
         }
 
+        // Aggregate usage per branch
+        merge_branch_usage(&mut branch_map, stats.branch_usage);
+
         // Aggregate session dates
         session_dates.extend(stats.session_dates);
 
@@ -910,6 +1036,8 @@ pub async fn get_project_stats_summary(
     summary.daily_stats = daily_stats_map.into_values().collect();
     summary.daily_stats.sort_by(|a, b| a.date.cmp(&b.date));
 
+    summary.branch_stats = sorted_branch_stats(branch_map);
+
     summary.activity_heatmap = activity_map
         .into_iter()
         .map(|((hour, day), (count, tokens))| ActivityHeatmap {
@@ -951,7 +1079,7 @@ pub async fn get_project_stats_summary(
         total_time.as_millis()
     );
 
-    Ok(summary)
+    summary
 }
 
 /// Lightweight session stats for comparison (parallel processing)
@@ -1185,45 +1313,51 @@ impl TryFrom<RawLogEntry> for ClaudeMessage {
 }
 
 #[tauri::command]
-pub async fn get_global_stats_summary(claude_path: String) -> Result<GlobalStatsSummary, String> {
-    let projects_path = PathBuf::from(&claude_path).join("projects");
+pub async fn get_global_stats_summary(
+    claude_path: String,
+    roots: Option<RootSelection>,
+    filter: Option<SessionContextFilter>,
+) -> Result<GlobalStatsSummary, String> {
+    let filter = filter.unwrap_or_default();
+    let projects_paths: Vec<PathBuf> = selected_roots(&claude_path, roots.as_ref())?
+        .iter()
+        .map(ResolvedRoot::projects_path)
+        .filter(|path| path.exists())
+        .collect();
 
-    if !projects_path.exists() {
+    if projects_paths.is_empty() {
         return Err("Projects directory not found".to_string());
     }
 
-    // Phase 1: Collect all session files and their project names
+    // Phase 1: Collect all session files and their projects (by folder, so
+    // same-named projects in different roots count separately)
     let mut session_files: Vec<PathBuf> = Vec::new();
-    let mut project_names: HashSet<String> = HashSet::new();
+    let mut project_names: HashSet<PathBuf> = HashSet::new();
 
-    for project_entry in fs::read_dir(&projects_path).map_err(|e| e.to_string())? {
-        let project_entry = project_entry.map_err(|e| e.to_string())?;
-        let project_path = project_entry.path();
-
-        if !project_path.is_dir() {
-            continue;
-        }
+    for projects_path in &projects_paths {
+        for project_entry in fs::read_dir(projects_path).map_err(|e| e.to_string())? {
+            let project_entry = project_entry.map_err(|e| e.to_string())?;
+            let project_path = project_entry.path();
 
-        let project_name = project_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("Unknown")
-            .to_string();
-        project_names.insert(project_name);
+            if !project_path.is_dir() {
+                continue;
+            }
 
-        for entry in WalkDir::new(&project_path)
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-            .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
-        {
-            session_files.push(entry.path().to_path_buf());
+            for entry in WalkDir::new(&project_path)
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+                .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+            {
+                session_files.push(entry.path().to_path_buf());
+            }
+            project_names.insert(project_path);
         }
     }
 
     // Phase 2: Process all session files in parallel
     let file_stats: Vec<SessionFileStats> = session_files
         .par_iter()
-        .filter_map(process_session_file_for_global_stats)
+        .filter_map(|path| process_session_file_for_global_stats(path, &filter))
         .collect();
 
     // Phase 3: Aggregate results
@@ -1236,6 +1370,7 @@ pub async fn get_global_stats_summary(claude_path: String) -> Result<GlobalStats
     let mut activity_map: HashMap<(u8, u8), (u32, u64)> = HashMap::new();
     let mut model_usage_map: HashMap<String, (u32, u64, u64, u64, u64, u64)> = HashMap::new();
     let mut project_stats_map: HashMap<String, (u32, u32, u64)> = HashMap::new();
+    let mut branch_map: HashMap<String, BranchStats> = HashMap::new();
     let mut global_first_message: Option<DateTime<Utc>> = None;
     let mut global_last_message: Option<DateTime<Utc>> = None;
 
@@ -1291,6 +1426,9 @@ pub async fn get_global_stats_summary(claude_path: String) -> Result<GlobalStats
             entry.5 += cache_read;
         }
 
+        // Aggregate usage per branch
+        merge_branch_usage(&mut branch_map, stats.branch_usage);
+
         // Aggregate project stats
         let project_entry = project_stats_map
             .entry(stats.project_name)
@@ -1372,6 +1510,8 @@ pub async fn get_global_stats_summary(claude_path: String) -> Result<GlobalStats
     summary.top_projects.sort_by(|a, b| b.tokens.cmp(&a.tokens));
     summary.top_projects.truncate(10);
 
+    summary.branch_stats = sorted_branch_stats(branch_map);
+
     summary.daily_stats = daily_stats_map.into_values().collect();
     summary.daily_stats.sort_by(|a, b| a.date.cmp(&b.date));
 
@@ -1399,6 +1539,113 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_summarize_project_dirs_by_branch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let line = |uuid: &str, branch: &str, version: &str, tokens: u32| {
+            format!(
+                r#"{{"uuid":"{uuid}","sessionId":"s","timestamp":"2025-06-26T10:00:00Z","type":"assistant","gitBranch":"{branch}","version":"{version}","message":{{"role":"assistant","content":"ok","usage":{{"input_tokens":{tokens},"output_tokens":0}}}}}}"#
+            )
+        };
+        std::fs::write(
+            temp_dir.path().join("a.jsonl"),
+            format!(
+                "{}\n{}\n",
+                line("a1", "main", "1.0.80", 100),
+                line("a2", "feature", "1.0.80", 30)
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("b.jsonl"),
+            format!("{}\n", line("b1", "main", "1.0.85", 50)),
+        )
+        .unwrap();
+        let dirs = [temp_dir.path().to_path_buf()];
+
+        let summary = summarize_project_dirs(
+            &dirs,
+            "p".to_string(),
+            None,
+            None,
+            &SessionContextFilter::default(),
+        );
+        assert_eq!(summary.total_tokens, 180);
+        let branches: Vec<(&str, usize, u64)> = summary
+            .branch_stats
+            .iter()
+            .map(|b| (b.git_branch.as_str(), b.session_count, b.total_tokens))
+            .collect();
+        assert_eq!(branches, vec![("main", 2, 150), ("feature", 1, 30)]);
+
+        let feature = SessionContextFilter {
+            git_branch: Some("feature".to_string()),
+            version: None,
+        };
+        let summary = summarize_project_dirs(&dirs, "p".to_string(), None, None, &feature);
+        assert_eq!(summary.total_sessions, 1);
+        assert_eq!(summary.total_tokens, 30);
+
+        let newer = SessionContextFilter {
+            git_branch: None,
+            version: Some("1.0.85".to_string()),
+        };
+        let stats: Vec<SessionTokenStats> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|entry| extract_session_token_stats_sync(&entry.unwrap().path(), &newer))
+            .collect();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].total_tokens, 50);
+    }
+
+    #[tokio::test]
+    async fn test_global_stats_by_branch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let projects = temp_dir.path().join("projects");
+        let line = |uuid: &str, branch: &str, tokens: u32| {
+            format!(
+                r#"{{"uuid":"{uuid}","sessionId":"s","timestamp":"2025-06-26T10:00:00Z","type":"assistant","gitBranch":"{branch}","version":"1.0.80","message":{{"role":"assistant","content":"ok","usage":{{"input_tokens":{tokens},"output_tokens":0}}}}}}"#
+            )
+        };
+        for (project, lines) in [
+            (
+                "-work-app",
+                vec![line("a1", "main", 100), line("a2", "feature", 30)],
+            ),
+            ("-work-lib", vec![line("b1", "main", 50)]),
+        ] {
+            std::fs::create_dir_all(projects.join(project)).unwrap();
+            std::fs::write(
+                projects.join(project).join("s.jsonl"),
+                lines.join("\n") + "\n",
+            )
+            .unwrap();
+        }
+        let claude_path = temp_dir.path().to_string_lossy().to_string();
+
+        let summary = get_global_stats_summary(claude_path.clone(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(summary.total_tokens, 180);
+        let branches: Vec<(&str, usize, u64)> = summary
+            .branch_stats
+            .iter()
+            .map(|b| (b.git_branch.as_str(), b.session_count, b.total_tokens))
+            .collect();
+        assert_eq!(branches, vec![("main", 2, 150), ("feature", 1, 30)]);
+
+        let feature = SessionContextFilter {
+            git_branch: Some("feature".to_string()),
+            version: None,
+        };
+        let summary = get_global_stats_summary(claude_path, None, Some(feature))
+            .await
+            .unwrap();
+        assert_eq!(summary.total_sessions, 1);
+        assert_eq!(summary.total_tokens, 30);
+        assert_eq!(summary.branch_stats.len(), 1);
+    }
+
     #[test]
     fn test_try_from_raw_log_entry_user_message() {
         let raw = RawLogEntry {
@@ -1421,6 +1668,8 @@ mod tests {
             tool_use_result: None,
             is_sidechain: Some(false),
             cwd: Some("/home/user/project".to_string()),
+            git_branch: None,
+            version: None,
             cost_usd: None,
             duration_ms: None,
             message_id: None,
@@ -1480,6 +1729,8 @@ mod tests {
             tool_use_result: None,
             is_sidechain: None,
             cwd: None,
+            git_branch: None,
+            version: None,
             cost_usd: Some(0.005),
             duration_ms: Some(1500),
             message_id: None,
@@ -1531,6 +1782,8 @@ mod tests {
             tool_use_result: None,
             is_sidechain: None,
             cwd: None,
+            git_branch: None,
+            version: None,
             cost_usd: None,
             duration_ms: None,
             message_id: None,
@@ -1579,6 +1832,8 @@ mod tests {
             tool_use_result: None,
             is_sidechain: None,
             cwd: None,
+            git_branch: None,
+            version: None,
             cost_usd: None,
             duration_ms: None,
             message_id: None,
@@ -1627,6 +1882,8 @@ mod tests {
             tool_use_result: None,
             is_sidechain: None,
             cwd: None,
+            git_branch: None,
+            version: None,
             cost_usd: None,
             duration_ms: None,
             message_id: None,
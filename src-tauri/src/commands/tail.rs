@@ -0,0 +1,267 @@
+//! Live tail of followed sessions
+//!
+//! While a session is followed, every change the file watcher reports for it
+//! is read from the last byte offset onward, and the new messages are pushed
+//! to the frontend as a `session-tail` event instead of reloading the session.
+
+use super::session::parse_appended_lines;
+use super::watcher::{last_line_end, ByteRange, FileWatchEvent, FileWatchEventType};
+use crate::models::ClaudeMessage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Followed session files and the offset each has been read up to
+pub type FollowedSessions = Arc<Mutex<HashMap<PathBuf, u64>>>;
+
+/// How much of a file's end to search for the last complete line first
+const TAIL_PROBE_BYTES: u64 = 1 << 20;
+
+/// New messages of a followed session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTailEvent {
+    pub session_path: String,
+    /// Bytes the messages were parsed from
+    pub range: ByteRange,
+    pub messages: Vec<ClaudeMessage>,
+    /// The file shrank or was rewritten: reload it, then expect messages from `range.end`
+    pub reset: bool,
+}
+
+/// End of the last complete line of a file
+fn complete_len(path: &Path, len: u64) -> u64 {
+    last_line_end(path, len.saturating_sub(TAIL_PROBE_BYTES), len)
+        .or_else(|| last_line_end(path, 0, len))
+        .unwrap_or(0)
+}
+
+/// Ask for a reload of a file that shrank or was rewritten
+fn reset_tail(path: &Path) -> Option<SessionTailEvent> {
+    let len = path.metadata().ok()?.len();
+    Some(SessionTailEvent {
+        session_path: path.to_string_lossy().to_string(),
+        range: ByteRange {
+            start: 0,
+            end: complete_len(path, len),
+        },
+        messages: Vec::new(),
+        reset: true,
+    })
+}
+
+/// Read whatever was written after `offset`
+///
+/// Returns `None` when there is no new complete line yet.
+fn read_tail(path: &Path, offset: u64) -> Option<SessionTailEvent> {
+    let len = path.metadata().ok()?.len();
+    if len < offset {
+        return reset_tail(path);
+    }
+    // A partial last line stays unread until it is finished
+    let end = last_line_end(path, offset, len)?;
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut bytes = Vec::new();
+    file.take(end - offset).read_to_end(&mut bytes).ok()?;
+    Some(SessionTailEvent {
+        session_path: path.to_string_lossy().to_string(),
+        range: ByteRange { start: offset, end },
+        messages: parse_appended_lines(&bytes),
+        reset: false,
+    })
+}
+
+/// Update followed sessions for a watcher event, returning what to push
+pub(crate) fn tail_followed(
+    followed: &FollowedSessions,
+    event: &FileWatchEvent,
+) -> Option<SessionTailEvent> {
+    let mut followed = followed.lock().ok()?;
+    let path = PathBuf::from(&event.session_path);
+    match event.event_type {
+        FileWatchEventType::Appended => {
+            let offset = followed.get_mut(&path)?;
+            let tail = read_tail(&path, *offset)?;
+            *offset = tail.range.end;
+            Some(tail)
+        }
+        // Earlier bytes may have moved, so nothing can be read from the old offset
+        FileWatchEventType::Changed => {
+            let offset = followed.get_mut(&path)?;
+            let tail = reset_tail(&path)?;
+            *offset = tail.range.end;
+            Some(tail)
+        }
+        FileWatchEventType::Renamed => {
+            let previous = PathBuf::from(event.previous_path.as_deref()?);
+            let offset = followed.remove(&previous)?;
+            followed.insert(path, offset);
+            None
+        }
+        FileWatchEventType::Deleted => {
+            followed.remove(&path);
+            None
+        }
+        FileWatchEventType::Created | FileWatchEventType::ProjectCreated => None,
+    }
+}
+
+pub(crate) fn emit_session_tail(app_handle: &AppHandle, tail: &SessionTailEvent) {
+    if let Err(e) = app_handle.emit("session-tail", tail) {
+        log::error!("Failed to emit session tail: {e}");
+    }
+}
+
+/// Start pushing new messages of a session as they are written
+///
+/// Returns the byte offset the tail starts from; messages before it should be
+/// loaded as usual. Requires the file watcher to be running.
+#[tauri::command]
+pub async fn follow_session(app_handle: AppHandle, session_path: String) -> Result<u64, String> {
+    let path = PathBuf::from(&session_path);
+    if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+        return Err("Only .jsonl session files can be followed".to_string());
+    }
+    // The watcher reports canonical paths
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to open session file: {e}"))?;
+    let len = path
+        .metadata()
+        .map_err(|e| format!("Failed to read session file metadata: {e}"))?
+        .len();
+    let offset = complete_len(&path, len);
+
+    let state: tauri::State<FollowedSessions> = app_handle.state();
+    let mut followed = state.lock().map_err(|e| format!("Lock error: {e}"))?;
+    followed.insert(path, offset);
+    Ok(offset)
+}
+
+/// Stop pushing new messages of a session
+#[tauri::command]
+pub async fn unfollow_session(app_handle: AppHandle, session_path: String) -> Result<(), String> {
+    let path = PathBuf::from(&session_path);
+    let path = path.canonicalize().unwrap_or(path);
+    let state: tauri::State<FollowedSessions> = app_handle.state();
+    let mut followed = state.lock().map_err(|e| format!("Lock error: {e}"))?;
+    followed.remove(&path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn line(uuid: &str, text: &str) -> String {
+        let entry = serde_json::json!({
+            "uuid": uuid,
+            "sessionId": "s1",
+            "timestamp": "2025-06-26T10:00:00Z",
+            "type": "user",
+            "message": {"role": "user", "content": text},
+        });
+        format!("{entry}\n")
+    }
+
+    fn append(path: &Path, content: &str) {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn appended(path: &Path) -> FileWatchEvent {
+        FileWatchEvent {
+            project_path: String::new(),
+            session_path: path.to_string_lossy().to_string(),
+            event_type: FileWatchEventType::Appended,
+            appended: None,
+            previous_path: None,
+        }
+    }
+
+    #[test]
+    fn test_tail_followed() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("s1.jsonl");
+        append(&path, &line("u1", "first"));
+        let followed = FollowedSessions::default();
+
+        // Not followed yet
+        append(&path, &line("u2", "second"));
+        assert!(tail_followed(&followed, &appended(&path)).is_none());
+
+        let start = complete_len(&path, path.metadata().unwrap().len());
+        followed.lock().unwrap().insert(path.clone(), start);
+
+        // Only the finished line is pushed
+        let third = line("u3", "third");
+        let (done, partial) = third.split_at(20);
+        append(&path, done);
+        assert!(tail_followed(&followed, &appended(&path)).is_none());
+        append(&path, partial);
+        let tail = tail_followed(&followed, &appended(&path)).unwrap();
+        assert!(!tail.reset);
+        assert_eq!(tail.range.start, start);
+        assert_eq!(tail.messages.len(), 1);
+        assert_eq!(tail.messages[0].uuid, "u3");
+
+        append(&path, &line("u4", "fourth"));
+        append(&path, &line("u5", "fifth"));
+        let tail = tail_followed(&followed, &appended(&path)).unwrap();
+        let uuids: Vec<&str> = tail.messages.iter().map(|m| m.uuid.as_str()).collect();
+        assert_eq!(uuids, vec!["u4", "u5"]);
+
+        // Truncation asks for a reload and resumes from the new end
+        fs::write(&path, line("u6", "rewritten")).unwrap();
+        let tail = tail_followed(&followed, &appended(&path)).unwrap();
+        assert!(tail.reset);
+        assert_eq!(tail.range.end, path.metadata().unwrap().len());
+        append(&path, &line("u7", "after"));
+        let tail = tail_followed(&followed, &appended(&path)).unwrap();
+        assert_eq!(tail.messages[0].uuid, "u7");
+
+        // Renaming the session rewrites its first line and grows the file
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replace("rewritten", "renamed session")).unwrap();
+        let changed = FileWatchEvent {
+            event_type: FileWatchEventType::Changed,
+            ..appended(&path)
+        };
+        let tail = tail_followed(&followed, &changed).unwrap();
+        assert!(tail.reset);
+        assert!(tail.messages.is_empty());
+        assert_eq!(tail.range.end, path.metadata().unwrap().len());
+        // Same length rewrites reset too
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replace("renamed session", "renamed-session")).unwrap();
+        assert!(tail_followed(&followed, &changed).unwrap().reset);
+        append(&path, &line("u9", "next"));
+        let tail = tail_followed(&followed, &appended(&path)).unwrap();
+        assert_eq!(tail.messages[0].uuid, "u9");
+
+        // Follows the file across a rename
+        let renamed = temp.path().join("s2.jsonl");
+        fs::rename(&path, &renamed).unwrap();
+        let event = FileWatchEvent {
+            event_type: FileWatchEventType::Renamed,
+            previous_path: Some(path.to_string_lossy().to_string()),
+            ..appended(&renamed)
+        };
+        assert!(tail_followed(&followed, &event).is_none());
+        append(&renamed, &line("u8", "moved"));
+        let tail = tail_followed(&followed, &appended(&renamed)).unwrap();
+        assert_eq!(tail.messages[0].uuid, "u8");
+    }
+}
@@ -1,26 +1,566 @@
+use super::tail::{emit_session_tail, tail_followed, FollowedSessions};
+use chrono::{DateTime, Utc};
 use notify::{RecommendedWatcher, RecursiveMode};
-use notify_debouncer_mini::{new_debouncer, DebouncedEvent, DebouncedEventKind, Debouncer};
+use notify_debouncer_mini::{new_debouncer, DebouncedEvent, Debouncer};
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs::{self, File, Metadata};
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
 use tauri::{AppHandle, Emitter, Manager};
+use walkdir::WalkDir;
+
+/// What happened to a session file, or to a project folder
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileWatchEventType {
+    Created,
+    /// Complete lines were added to the end of the file
+    Appended,
+    /// Rewritten in place; the whole file should be re-read
+    Changed,
+    Deleted,
+    Renamed,
+    ProjectCreated,
+}
+
+impl FileWatchEventType {
+    /// Name of the Tauri event the frontend listens to
+    pub fn event_name(self) -> &'static str {
+        match self {
+            Self::Created => "session-file-created",
+            Self::Appended => "session-file-appended",
+            Self::Changed => "session-file-changed",
+            Self::Deleted => "session-file-deleted",
+            Self::Renamed => "session-file-renamed",
+            Self::ProjectCreated => "project-created",
+        }
+    }
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Byte range of a file, end exclusive
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FileWatchEvent {
     pub project_path: String,
+    /// Session file; the project folder itself for `ProjectCreated`
     pub session_path: String,
-    pub event_type: String,
+    pub event_type: FileWatchEventType,
+    /// New lines of an `Appended` file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appended: Option<ByteRange>,
+    /// Where a `Renamed` file was before
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+}
+
+/// Last observed state of a session file
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileState {
+    len: u64,
+    modified: Option<SystemTime>,
+    /// End of the last complete line reported so far
+    line_end: u64,
+    /// Fingerprint of the bytes just before `line_end`
+    boundary: Option<u64>,
+}
+
+impl FileState {
+    fn read(path: &Path, meta: &Metadata) -> Self {
+        Self {
+            len: meta.len(),
+            modified: meta.modified().ok(),
+            line_end: meta.len(),
+            boundary: boundary_hash(path, meta.len()),
+        }
+    }
+
+    /// Same contents as far as a rename can tell
+    fn same_file(&self, other: &Self) -> bool {
+        self.len == other.len && self.modified == other.modified
+    }
+}
+
+fn is_session_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "jsonl")
+}
+
+/// How much of a file before its last reported line end is fingerprinted
+const BOUNDARY_BYTES: u64 = 256;
+
+/// Fingerprint of the bytes just before `end`
+///
+/// Appends leave them untouched, so a different fingerprint means the file
+/// was rewritten, as when the first message is edited to rename a session.
+fn boundary_hash(path: &Path, end: u64) -> Option<u64> {
+    let start = end.saturating_sub(BOUNDARY_BYTES);
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut bytes = Vec::new();
+    file.take(end - start).read_to_end(&mut bytes).ok()?;
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// End of the last complete line in `start..end` of a file, if there is one
+pub(crate) fn last_line_end(path: &Path, start: u64, end: u64) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut tail = Vec::new();
+    file.take(end - start).read_to_end(&mut tail).ok()?;
+    let newline = tail.iter().rposition(|&b| b == b'\n')?;
+    Some(start + newline as u64 + 1)
+}
+
+/// Session files and project folders seen so far under the projects directory
+///
+/// Raw debounced events only name paths; comparing them against this index
+/// tells creations, appends, deletions and renames apart.
+#[derive(Debug, Default)]
+struct SessionIndex {
+    root: PathBuf,
+    files: HashMap<PathBuf, FileState>,
+    projects: HashSet<PathBuf>,
+}
+
+impl SessionIndex {
+    fn scan(root: &Path) -> Self {
+        let mut index = Self {
+            root: root.to_path_buf(),
+            ..Self::default()
+        };
+        for entry in WalkDir::new(root).min_depth(1).into_iter().flatten() {
+            if entry.depth() == 1 && entry.file_type().is_dir() {
+                index.projects.insert(entry.path().to_path_buf());
+            } else if entry.file_type().is_file() && is_session_file(entry.path()) {
+                if let Ok(meta) = entry.metadata() {
+                    index.files.insert(
+                        entry.path().to_path_buf(),
+                        FileState::read(entry.path(), &meta),
+                    );
+                }
+            }
+        }
+        index
+    }
+
+    /// Update the index for one debounced batch of changed paths
+    fn apply(&mut self, paths: &[PathBuf]) -> Vec<FileWatchEvent> {
+        let mut present: BTreeMap<PathBuf, FileState> = BTreeMap::new();
+        let mut gone: Vec<PathBuf> = Vec::new();
+        let mut new_projects: Vec<PathBuf> = Vec::new();
+
+        for path in paths {
+            match fs::symlink_metadata(path) {
+                Ok(meta) if meta.is_dir() => {
+                    if path.parent() == Some(self.root.as_path())
+                        && self.projects.insert(path.clone())
+                    {
+                        new_projects.push(path.clone());
+                    }
+                    // A folder moved in brings its files without events of their own
+                    for entry in WalkDir::new(path).into_iter().flatten() {
+                        if entry.file_type().is_file() && is_session_file(entry.path()) {
+                            if let Ok(meta) = entry.metadata() {
+                                present.insert(
+                                    entry.path().to_path_buf(),
+                                    FileState::read(entry.path(), &meta),
+                                );
+                            }
+                        }
+                    }
+                }
+                Ok(meta) if meta.is_file() && is_session_file(path) => {
+                    present.insert(path.clone(), FileState::read(path, &meta));
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    // Also covers a whole folder moved or deleted
+                    gone.extend(self.files.keys().filter(|k| k.starts_with(path)).cloned());
+                    self.projects.retain(|p| !p.starts_with(path));
+                }
+            }
+        }
+
+        for path in present.keys() {
+            if let Some((project, _)) = extract_paths(path) {
+                if project.parent() == Some(self.root.as_path())
+                    && self.projects.insert(project.clone())
+                {
+                    new_projects.push(project);
+                }
+            }
+        }
+        new_projects.sort();
+        new_projects.dedup();
+        let mut events: Vec<FileWatchEvent> = new_projects
+            .iter()
+            .map(|project| FileWatchEvent {
+                project_path: project.to_string_lossy().to_string(),
+                session_path: project.to_string_lossy().to_string(),
+                event_type: FileWatchEventType::ProjectCreated,
+                appended: None,
+                previous_path: None,
+            })
+            .collect();
+
+        let mut removed: Vec<(PathBuf, FileState)> = gone
+            .into_iter()
+            .filter_map(|path| self.files.remove(&path).map(|state| (path, state)))
+            .collect();
+        for (path, state) in present {
+            let (event_type, appended, previous_path) = match self.files.get(&path).copied() {
+                None => {
+                    let renamed_from = removed
+                        .iter()
+                        .position(|(_, old)| old.same_file(&state))
+                        .map(|i| removed.remove(i).0);
+                    match renamed_from {
+                        Some(old) => (FileWatchEventType::Renamed, None, Some(old)),
+                        None => (FileWatchEventType::Created, None, None),
+                    }
+                }
+                Some(old) => match self.update(&path, old, state) {
+                    Some((event_type, appended)) => (event_type, appended, None),
+                    None => continue,
+                },
+            };
+            if event_type != FileWatchEventType::Appended {
+                self.files.insert(path.clone(), state);
+            }
+            events.extend(session_event(&path, event_type, appended, previous_path));
+        }
+        for (path, _) in removed {
+            events.extend(session_event(
+                &path,
+                FileWatchEventType::Deleted,
+                None,
+                None,
+            ));
+        }
+        events
+    }
+
+    /// Record the new state of a known file and classify the change
+    fn update(
+        &mut self,
+        path: &Path,
+        old: FileState,
+        new: FileState,
+    ) -> Option<(FileWatchEventType, Option<ByteRange>)> {
+        let rewritten = old
+            .boundary
+            .is_some_and(|boundary| boundary_hash(path, old.line_end) != Some(boundary));
+        if new.len < old.len || rewritten {
+            return Some((FileWatchEventType::Changed, None));
+        }
+        if new.len > old.line_end {
+            // Only whole lines are reported; a partial one waits for the next event
+            let Some(line_end) = last_line_end(path, old.line_end, new.len) else {
+                self.files.insert(
+                    path.to_path_buf(),
+                    FileState {
+                        line_end: old.line_end,
+                        boundary: old.boundary,
+                        ..new
+                    },
+                );
+                return None;
+            };
+            self.files.insert(
+                path.to_path_buf(),
+                FileState {
+                    line_end,
+                    boundary: boundary_hash(path, line_end),
+                    ..new
+                },
+            );
+            return Some((
+                FileWatchEventType::Appended,
+                Some(ByteRange {
+                    start: old.line_end,
+                    end: line_end,
+                }),
+            ));
+        }
+        (new.len == old.len && new.modified != old.modified)
+            .then_some((FileWatchEventType::Changed, None))
+    }
+}
+
+fn session_event(
+    path: &Path,
+    event_type: FileWatchEventType,
+    appended: Option<ByteRange>,
+    previous_path: Option<PathBuf>,
+) -> Option<FileWatchEvent> {
+    let (project_path, session_path) = extract_paths(path)?;
+    Some(FileWatchEvent {
+        project_path: project_path.to_string_lossy().to_string(),
+        session_path: session_path.to_string_lossy().to_string(),
+        event_type,
+        appended,
+        previous_path: previous_path.map(|p| p.to_string_lossy().to_string()),
+    })
+}
+
+/// Debounce used when the caller does not choose one
+const DEFAULT_DEBOUNCE_MS: u64 = 500;
+/// How often a watched directory is checked for having disappeared or come back
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// Errors kept per watcher
+const MAX_RECENT_ERRORS: usize = 20;
+
+/// Status of one watched Claude folder
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileWatcherStatus {
+    pub root: String,
+    pub projects_path: String,
+    pub debounce_ms: u64,
+    /// False while the projects directory is missing
+    pub active: bool,
+    /// Watch events emitted since the watcher started
+    pub event_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_event_at: Option<String>,
+    /// Times the watcher was rebuilt after its directory came back
+    pub recoveries: u32,
+    /// Most recent errors, oldest first
+    pub errors: Vec<String>,
+}
+
+/// Counters shared with a watcher's event callback
+#[derive(Debug, Default)]
+struct WatcherStats {
+    event_count: u64,
+    last_event_at: Option<DateTime<Utc>>,
+    recoveries: u32,
+    errors: VecDeque<String>,
+}
+
+impl WatcherStats {
+    fn record_events(&mut self, count: usize) {
+        self.event_count += count as u64;
+        self.last_event_at = Some(Utc::now());
+    }
+
+    fn record_error(&mut self, error: String) {
+        if self.errors.len() == MAX_RECENT_ERRORS {
+            self.errors.pop_front();
+        }
+        self.errors.push_back(error);
+    }
+}
+
+type SharedStats = Arc<Mutex<WatcherStats>>;
+
+/// A watched Claude folder
+struct WatchedRoot {
+    projects_path: PathBuf,
+    debounce: Duration,
+    /// `None` while the projects directory is missing
+    debouncer: Option<Debouncer<RecommendedWatcher>>,
+    /// Identity of the directory the debouncer watches
+    identity: Option<u64>,
+    stats: SharedStats,
+    /// Tells the health check thread to exit
+    stopped: Arc<AtomicBool>,
+}
+
+impl WatchedRoot {
+    fn status(&self, root: &Path) -> FileWatcherStatus {
+        let stats = self.stats.lock().unwrap_or_else(PoisonError::into_inner);
+        FileWatcherStatus {
+            root: root.to_string_lossy().to_string(),
+            projects_path: self.projects_path.to_string_lossy().to_string(),
+            debounce_ms: u64::try_from(self.debounce.as_millis()).unwrap_or(u64::MAX),
+            active: self.debouncer.is_some(),
+            event_count: stats.event_count,
+            last_event_at: stats.last_event_at.map(|at| at.to_rfc3339()),
+            recoveries: stats.recoveries,
+            errors: stats.errors.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Running file watchers, keyed by canonical Claude folder
+#[derive(Default)]
+pub struct WatcherRegistry(Mutex<HashMap<PathBuf, WatchedRoot>>);
+
+/// Identifies a directory, so one removed and recreated between checks is noticed
+fn dir_identity(path: &Path) -> Option<u64> {
+    let meta = fs::metadata(path).ok().filter(Metadata::is_dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(meta.ino())
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        Some(meta.creation_time())
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        meta.created()
+            .ok()?
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .and_then(|age| u64::try_from(age.as_nanos()).ok())
+    }
+}
+
+/// Watch a projects directory, emitting typed events and counting them in `stats`
+fn watch_projects(
+    app_handle: &AppHandle,
+    projects_path: &Path,
+    debounce: Duration,
+    stats: &SharedStats,
+) -> Result<Debouncer<RecommendedWatcher>, String> {
+    let app_handle = app_handle.clone();
+    let stats = Arc::clone(stats);
+    let mut index = SessionIndex::scan(projects_path);
+    let followed: FollowedSessions = app_handle.state::<FollowedSessions>().inner().clone();
+    let mut debouncer = new_debouncer(
+        debounce,
+        move |result: Result<Vec<DebouncedEvent>, notify::Error>| match result {
+            Ok(events) => {
+                let paths: Vec<PathBuf> = events.into_iter().map(|event| event.path).collect();
+                let events = index.apply(&paths);
+                if !events.is_empty() {
+                    if let Ok(mut stats) = stats.lock() {
+                        stats.record_events(events.len());
+                    }
+                }
+                for event in &events {
+                    emit_watch_event(&app_handle, event);
+                    if let Some(tail) = tail_followed(&followed, event) {
+                        emit_session_tail(&app_handle, &tail);
+                    }
+                }
+            }
+            Err(error) => {
+                log::error!("File watcher error: {error:?}");
+                if let Ok(mut stats) = stats.lock() {
+                    stats.record_error(error.to_string());
+                }
+            }
+        },
+    )
+    .map_err(|e| format!("Failed to create file watcher: {e}"))?;
+
+    debouncer
+        .watcher()
+        .watch(projects_path, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch directory: {e}"))?;
+    Ok(debouncer)
 }
 
-type WatcherMap = Arc<Mutex<Option<Debouncer<RecommendedWatcher>>>>;
+/// Drop a watcher whose directory went away, and rebuild it once the directory is back
+///
+/// Rebuilding rescans the directory, so it runs without the registry locked.
+/// Returns false once the root is no longer registered under this health check.
+fn check_root(app_handle: &AppHandle, key: &Path, stopped: &Arc<AtomicBool>) -> bool {
+    let registry: tauri::State<WatcherRegistry> = app_handle.state();
+    let (identity, projects_path, debounce, stats) = {
+        let Ok(mut roots) = registry.0.lock() else {
+            return false;
+        };
+        let Some(root) = roots
+            .get_mut(key)
+            .filter(|root| Arc::ptr_eq(&root.stopped, stopped))
+        else {
+            return false;
+        };
+        let identity = dir_identity(&root.projects_path);
+        if identity == root.identity {
+            return true;
+        }
+        if root.debouncer.take().is_some() {
+            let error = format!(
+                "Projects directory disappeared: {}",
+                root.projects_path.display()
+            );
+            log::warn!("{error}");
+            if let Ok(mut stats) = root.stats.lock() {
+                stats.record_error(error);
+            }
+        }
+        root.identity = None;
+        if identity.is_none() {
+            return true;
+        }
+        (
+            identity,
+            root.projects_path.clone(),
+            root.debounce,
+            Arc::clone(&root.stats),
+        )
+    };
 
-/// Start watching the Claude projects directory for file changes
+    let result = watch_projects(app_handle, &projects_path, debounce, &stats);
+    let Ok(mut roots) = registry.0.lock() else {
+        return false;
+    };
+    let Some(root) = roots
+        .get_mut(key)
+        .filter(|root| Arc::ptr_eq(&root.stopped, stopped))
+    else {
+        return false;
+    };
+    match result {
+        Ok(debouncer) => {
+            root.debouncer = Some(debouncer);
+            root.identity = identity;
+            if let Ok(mut stats) = stats.lock() {
+                stats.recoveries += 1;
+            }
+            log::info!("File watcher recovered for: {}", projects_path.display());
+            // Changes made while it was gone were missed, so listeners should reload
+            if let Err(e) = app_handle.emit("file-watcher-recovered", root.status(key)) {
+                log::error!("Failed to emit watcher recovery: {e}");
+            }
+        }
+        Err(error) => {
+            if let Ok(mut stats) = stats.lock() {
+                stats.record_error(error);
+            }
+        }
+    }
+    true
+}
+
+fn spawn_health_check(app_handle: AppHandle, key: PathBuf, stopped: Arc<AtomicBool>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(HEALTH_CHECK_INTERVAL);
+        if stopped.load(Ordering::Relaxed) || !check_root(&app_handle, &key, &stopped) {
+            return;
+        }
+    });
+}
+
+/// Start watching a Claude folder's projects directory for file changes
+///
+/// Each folder gets its own watcher; starting one that is already watched
+/// restarts it. `debounce_ms` defaults to 500 and is kept within 50..=10000.
 #[tauri::command]
 pub async fn start_file_watcher(
     app_handle: AppHandle,
     claude_folder_path: String,
+    debounce_ms: Option<u64>,
 ) -> Result<String, String> {
     let base_path = PathBuf::from(&claude_folder_path);
     let projects_path = base_path.join("projects");
@@ -56,85 +596,78 @@ pub async fn start_file_watcher(
         ));
     }
 
-    // Create a debounced watcher
-    let app_handle_clone = app_handle.clone();
-    let mut debouncer = new_debouncer(
-        Duration::from_millis(500),
-        move |result: Result<Vec<DebouncedEvent>, notify::Error>| match result {
-            Ok(events) => {
-                for event in events {
-                    handle_file_event(&app_handle_clone, &event);
-                }
-            }
-            Err(error) => {
-                log::error!("File watcher error: {error:?}");
-            }
+    let debounce =
+        Duration::from_millis(debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS).clamp(50, 10_000));
+    let stats = SharedStats::default();
+    let debouncer = watch_projects(&app_handle, &canonical_projects, debounce, &stats)?;
+    let stopped = Arc::new(AtomicBool::new(false));
+
+    // Keep the debouncer in app state so it is not dropped
+    let registry: tauri::State<WatcherRegistry> = app_handle.state();
+    let mut roots = registry.0.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let previous = roots.insert(
+        canonical_base.clone(),
+        WatchedRoot {
+            identity: dir_identity(&canonical_projects),
+            projects_path: canonical_projects.clone(),
+            debounce,
+            debouncer: Some(debouncer),
+            stats,
+            stopped: Arc::clone(&stopped),
         },
-    )
-    .map_err(|e| format!("Failed to create file watcher: {e}"))?;
-
-    // Start watching the canonicalized projects directory recursively
-    debouncer
-        .watcher()
-        .watch(&canonical_projects, RecursiveMode::Recursive)
-        .map_err(|e| format!("Failed to watch directory: {e}"))?;
-
-    // Store the debouncer in app state to prevent it from being dropped
-    let watcher_state: tauri::State<WatcherMap> = app_handle.state();
-    let mut watcher = watcher_state.lock().unwrap();
-    *watcher = Some(debouncer);
+    );
+    drop(roots);
+    if let Some(previous) = previous {
+        previous.stopped.store(true, Ordering::Relaxed);
+    }
+    spawn_health_check(app_handle.clone(), canonical_base, stopped);
 
     log::info!("File watcher started for: {}", canonical_projects.display());
     Ok("watcher-started".to_string())
 }
 
-/// Stop the file watcher
+/// Stop watching one Claude folder, or every folder when none is given
+///
+/// Stopping a folder that is not being watched is not an error.
 #[tauri::command]
-pub async fn stop_file_watcher(app_handle: AppHandle) -> Result<(), String> {
-    let watcher_state: tauri::State<WatcherMap> = app_handle.state();
-    let mut watcher = watcher_state.lock().unwrap();
-
-    if watcher.is_some() {
-        *watcher = None;
-        log::info!("File watcher stopped");
-        Ok(())
-    } else {
-        Err("No active file watcher found".to_string())
-    }
-}
-
-fn handle_file_event(app_handle: &AppHandle, event: &DebouncedEvent) {
-    let path = &event.path;
-
-    // Only process .jsonl files
-    if let Some(ext) = path.extension() {
-        if ext != "jsonl" {
-            return;
+pub async fn stop_file_watcher(
+    app_handle: AppHandle,
+    claude_folder_path: Option<String>,
+) -> Result<(), String> {
+    let registry: tauri::State<WatcherRegistry> = app_handle.state();
+    let mut roots = registry.0.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let removed: Vec<WatchedRoot> = match claude_folder_path {
+        Some(path) => {
+            let key = fs::canonicalize(&path).unwrap_or_else(|_| PathBuf::from(&path));
+            roots.remove(&key).into_iter().collect()
         }
-    } else {
-        return;
-    }
-
-    // Extract project path and session path
-    let Some((project_path, session_path)) = extract_paths(path) else {
-        log::warn!("Could not extract paths from: {}", path.display());
-        return;
-    };
-
-    // Determine event type based on DebouncedEventKind
-    // All events are treated as "changed" since debouncer aggregates events
-    let event_type = match event.kind {
-        DebouncedEventKind::Any | DebouncedEventKind::AnyContinuous | _ => "session-file-changed",
+        None => roots.drain().map(|(_, root)| root).collect(),
     };
+    drop(roots);
+    for root in &removed {
+        root.stopped.store(true, Ordering::Relaxed);
+    }
+    if !removed.is_empty() {
+        log::info!("Stopped {} file watcher(s)", removed.len());
+    }
+    Ok(())
+}
 
-    let watch_event = FileWatchEvent {
-        project_path: project_path.to_string_lossy().to_string(),
-        session_path: session_path.to_string_lossy().to_string(),
-        event_type: event_type.to_string(),
-    };
+/// Every running file watcher with its event counts and recent errors
+#[tauri::command]
+pub async fn get_file_watcher_status(
+    app_handle: AppHandle,
+) -> Result<Vec<FileWatcherStatus>, String> {
+    let registry: tauri::State<WatcherRegistry> = app_handle.state();
+    let roots = registry.0.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let mut statuses: Vec<FileWatcherStatus> =
+        roots.iter().map(|(key, root)| root.status(key)).collect();
+    statuses.sort_by(|a, b| a.root.cmp(&b.root));
+    Ok(statuses)
+}
 
-    // Emit Tauri event to frontend
-    if let Err(e) = app_handle.emit(event_type, &watch_event) {
+fn emit_watch_event(app_handle: &AppHandle, event: &FileWatchEvent) {
+    if let Err(e) = app_handle.emit(event.event_type.event_name(), event) {
         log::error!("Failed to emit file watch event: {e}");
     }
 }
@@ -208,4 +741,138 @@ mod tests {
 
         assert!(result.is_none());
     }
+
+    fn append(path: &Path, content: &str) {
+        use std::io::Write;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn kinds(events: &[FileWatchEvent]) -> Vec<FileWatchEventType> {
+        events.iter().map(|e| e.event_type).collect()
+    }
+
+    #[test]
+    fn test_session_index_classifies_changes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("projects");
+        let project = root.join("-work-app");
+        fs::create_dir_all(&project).unwrap();
+        let existing = project.join("existing.jsonl");
+        append(&existing, "{\"a\":1}\n");
+        let mut index = SessionIndex::scan(&root);
+
+        // Only the complete line is reported; the partial one waits
+        append(&existing, "{\"b\":2}\n{\"c\"");
+        let events = index.apply(std::slice::from_ref(&existing));
+        assert_eq!(kinds(&events), vec![FileWatchEventType::Appended]);
+        assert_eq!(events[0].appended, Some(ByteRange { start: 8, end: 16 }));
+        assert!(events[0].project_path.ends_with("-work-app"));
+
+        append(&existing, ":3}\n");
+        let events = index.apply(std::slice::from_ref(&existing));
+        assert_eq!(events[0].appended, Some(ByteRange { start: 16, end: 24 }));
+        assert!(index.apply(std::slice::from_ref(&existing)).is_empty());
+
+        fs::write(&existing, "{}\n").unwrap();
+        let events = index.apply(std::slice::from_ref(&existing));
+        assert_eq!(kinds(&events), vec![FileWatchEventType::Changed]);
+
+        // Rewriting the first line grows the file without appending to it
+        fs::write(&existing, "{\"renamed\":true}\n").unwrap();
+        let events = index.apply(std::slice::from_ref(&existing));
+        assert_eq!(kinds(&events), vec![FileWatchEventType::Changed]);
+        append(&existing, "{}\n");
+        let events = index.apply(std::slice::from_ref(&existing));
+        assert_eq!(kinds(&events), vec![FileWatchEventType::Appended]);
+
+        // New project with a new session
+        let other = root.join("-work-other");
+        fs::create_dir_all(&other).unwrap();
+        let created = other.join("new.jsonl");
+        append(&created, "{}\n");
+        let events = index.apply(&[other.clone(), created.clone()]);
+        assert_eq!(
+            kinds(&events),
+            vec![
+                FileWatchEventType::ProjectCreated,
+                FileWatchEventType::Created
+            ]
+        );
+        assert_eq!(events[1].session_path, created.to_string_lossy());
+
+        let renamed = other.join("renamed.jsonl");
+        fs::rename(&created, &renamed).unwrap();
+        let events = index.apply(&[created.clone(), renamed.clone()]);
+        assert_eq!(kinds(&events), vec![FileWatchEventType::Renamed]);
+        assert_eq!(
+            events[0].previous_path.as_deref(),
+            Some(created.to_string_lossy().as_ref())
+        );
+
+        // A deleted folder reports each of its sessions
+        fs::remove_dir_all(&project).unwrap();
+        let events = index.apply(std::slice::from_ref(&project));
+        assert_eq!(kinds(&events), vec![FileWatchEventType::Deleted]);
+        assert_eq!(events[0].session_path, existing.to_string_lossy());
+
+        // Non-session files are ignored
+        let notes = other.join("notes.txt");
+        fs::write(&notes, "x").unwrap();
+        assert!(index.apply(&[notes]).is_empty());
+    }
+
+    #[test]
+    fn test_watcher_status() {
+        let stats = SharedStats::default();
+        {
+            let mut stats = stats.lock().unwrap();
+            stats.record_events(3);
+            for i in 0..=MAX_RECENT_ERRORS {
+                stats.record_error(format!("error {i}"));
+            }
+        }
+        let root = WatchedRoot {
+            projects_path: PathBuf::from("/home/me/.claude/projects"),
+            debounce: Duration::from_millis(250),
+            debouncer: None,
+            identity: None,
+            stats,
+            stopped: Arc::new(AtomicBool::new(false)),
+        };
+
+        let status = root.status(Path::new("/home/me/.claude"));
+        assert_eq!(status.root, "/home/me/.claude");
+        assert_eq!(status.debounce_ms, 250);
+        assert!(!status.active);
+        assert_eq!(status.event_count, 3);
+        assert!(status.last_event_at.is_some());
+        // Only the most recent errors are kept
+        assert_eq!(status.errors.len(), MAX_RECENT_ERRORS);
+        assert_eq!(status.errors[0], "error 1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dir_identity_changes_when_recreated() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("projects");
+        assert_eq!(dir_identity(&dir), None);
+
+        fs::create_dir(&dir).unwrap();
+        let first = dir_identity(&dir);
+        assert!(first.is_some());
+        // The old directory is kept so its inode cannot be reused
+        fs::rename(&dir, temp.path().join("old")).unwrap();
+        fs::create_dir(&dir).unwrap();
+        assert_ne!(dir_identity(&dir), first);
+
+        let file = temp.path().join("file");
+        fs::write(&file, "x").unwrap();
+        assert_eq!(dir_identity(&file), None);
+    }
 }
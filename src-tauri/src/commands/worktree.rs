@@ -0,0 +1,304 @@
+//! Worktree families
+//!
+//! Claude Code starts a separate project folder for every worktree of a
+//! repository. This module gathers the main checkout, its linked worktrees
+//! (including deleted ones) and their project folders into one family with
+//! stats across all of them.
+
+use super::git::{list_worktrees, open_repository};
+use super::project::scan_projects;
+use super::session::load_project_sessions;
+use super::stats::summarize_project_dirs;
+use crate::models::{
+    ClaudeProject, GitWorktreeType, ProjectStatsSummary, SessionContextFilter, WorktreeFamily,
+    WorktreeFamilyMember,
+};
+use git2::Repository;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// `path` with its longest existing prefix resolved, so spellings of a
+/// deleted directory still compare equal
+fn comparable_path(path: &str) -> PathBuf {
+    let path = Path::new(path.trim_end_matches('/'));
+    for ancestor in path.ancestors() {
+        if let Ok(resolved) = ancestor.canonicalize() {
+            return path
+                .strip_prefix(ancestor)
+                .map_or_else(|_| path.to_path_buf(), |rest| resolved.join(rest));
+        }
+    }
+    path.to_path_buf()
+}
+
+fn member(path: String, projects: Vec<ClaudeProject>) -> WorktreeFamilyMember {
+    WorktreeFamilyMember {
+        exists: Path::new(&path).is_dir(),
+        path,
+        worktree: None,
+        branches: Vec::new(),
+        projects,
+    }
+}
+
+/// Repository containing `path`
+///
+/// A deleted worktree is found through a known main checkout that still
+/// lists it. Its parent directories are not searched, since they may belong
+/// to an unrelated repository such as a dotfiles checkout of `$HOME`.
+fn open_family_repo(path: &Path, projects: &[ClaudeProject]) -> Result<Repository, String> {
+    if path.exists() {
+        return open_repository(path);
+    }
+    let key = comparable_path(&path.to_string_lossy());
+    let main_checkouts: BTreeSet<&str> = projects
+        .iter()
+        .filter_map(|p| {
+            let info = p.git_info.as_ref()?;
+            match info.worktree_type {
+                GitWorktreeType::Main => Some(p.actual_path.as_str()),
+                GitWorktreeType::Linked => info.main_project_path.as_deref(),
+                GitWorktreeType::NotGit => None,
+            }
+        })
+        .collect();
+    main_checkouts
+        .into_iter()
+        .filter_map(|main| Repository::open(main).ok())
+        .find(|repo| {
+            list_worktrees(repo)
+                .is_ok_and(|worktrees| worktrees.iter().any(|w| comparable_path(&w.path) == key))
+        })
+        .ok_or_else(|| format!("Not a git repository: {}", path.display()))
+}
+
+/// The family of the repository containing `path`, without sessions or stats
+fn find_family(path: &Path, projects: Vec<ClaudeProject>) -> Result<WorktreeFamily, String> {
+    let repo = open_family_repo(path, &projects)?;
+    let worktrees = list_worktrees(&repo)?;
+    let main_path = worktrees.iter().find(|w| w.is_main).map_or_else(
+        || {
+            repo.commondir()
+                .to_string_lossy()
+                .trim_end_matches('/')
+                .to_string()
+        },
+        |w| w.path.clone(),
+    );
+    let main_key = comparable_path(&main_path);
+
+    let mut members: Vec<WorktreeFamilyMember> = worktrees
+        .into_iter()
+        .map(|worktree| WorktreeFamilyMember {
+            branches: worktree.head.branch.iter().cloned().collect(),
+            worktree: Some(worktree.clone()),
+            ..member(worktree.path, Vec::new())
+        })
+        .collect();
+    let keys: Vec<PathBuf> = members.iter().map(|m| comparable_path(&m.path)).collect();
+
+    // Checkouts that still point at this repository after git pruned them
+    let mut forgotten: BTreeMap<PathBuf, WorktreeFamilyMember> = BTreeMap::new();
+    for project in projects {
+        let key = comparable_path(&project.actual_path);
+        if let Some(index) = keys.iter().position(|k| *k == key) {
+            members[index].projects.push(project);
+            continue;
+        }
+        let linked_here = project
+            .git_info
+            .as_ref()
+            .and_then(|info| info.main_project_path.as_deref())
+            .is_some_and(|main| comparable_path(main) == main_key);
+        if linked_here {
+            forgotten
+                .entry(key)
+                .or_insert_with(|| member(project.actual_path.clone(), Vec::new()))
+                .projects
+                .push(project);
+        }
+    }
+    members.extend(forgotten.into_values());
+
+    let project_paths = members
+        .iter()
+        .flat_map(|m| m.projects.iter().map(|p| p.path.clone()))
+        .collect();
+    Ok(WorktreeFamily {
+        main_path,
+        members,
+        project_paths,
+        stats: ProjectStatsSummary::default(),
+    })
+}
+
+/// The main checkout, every linked worktree and their projects, with stats
+/// across the whole family
+///
+/// `actual_path` may be any checkout of the repository, or a worktree
+/// directory that has since been deleted.
+#[tauri::command]
+pub async fn get_worktree_family(
+    claude_path: String,
+    actual_path: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<WorktreeFamily, String> {
+    let path = PathBuf::from(&actual_path);
+    if !path.is_absolute() {
+        return Err("Path must be absolute".to_string());
+    }
+    let projects = scan_projects(claude_path, None).await?;
+    let mut family = tauri::async_runtime::spawn_blocking(move || find_family(&path, projects))
+        .await
+        .map_err(|e| format!("Task join error: {e}"))??;
+
+    for member in &mut family.members {
+        for project in &member.projects {
+            for session in load_project_sessions(project.path.clone(), None).await? {
+                for branch in session.git_branches {
+                    if !member.branches.contains(&branch) {
+                        member.branches.push(branch);
+                    }
+                }
+            }
+        }
+    }
+
+    let dirs: Vec<PathBuf> = family.project_paths.iter().map(PathBuf::from).collect();
+    let project_name = Path::new(&family.main_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown")
+        .to_string();
+    family.stats = tauri::async_runtime::spawn_blocking(move || {
+        summarize_project_dirs(
+            &dirs,
+            project_name,
+            start_date,
+            end_date,
+            &SessionContextFilter::default(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Task join error: {e}"))?;
+    Ok(family)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::git::tests::commit_files;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_session(projects: &Path, cwd: &Path, session_id: &str, branch: &str) {
+        let folder = projects.join(crate::utils::encode_project_path(&cwd.to_string_lossy()));
+        fs::create_dir_all(&folder).unwrap();
+        let line = serde_json::json!({
+            "uuid": "u1",
+            "sessionId": session_id,
+            "timestamp": "2025-06-26T10:00:00Z",
+            "type": "user",
+            "cwd": cwd,
+            "gitBranch": branch,
+            "message": {"role": "user", "content": "Hello"},
+        });
+        fs::write(
+            folder.join(format!("{session_id}.jsonl")),
+            format!("{line}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_comparable_path() {
+        let temp = TempDir::new().unwrap();
+        let resolved = temp.path().canonicalize().unwrap();
+        let gone = temp.path().join("gone").join("deeper");
+        assert_eq!(
+            comparable_path(&format!("{}/", gone.to_string_lossy())),
+            resolved.join("gone").join("deeper")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_worktree_family() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let claude = root.join(".claude");
+        let projects = claude.join("projects");
+
+        // An unrelated repository around everything, like a dotfiles checkout of $HOME
+        git2::Repository::init(&root).unwrap();
+        let main = root.join("app");
+        let repo = git2::Repository::init(&main).unwrap();
+        commit_files(&repo, 1_750_928_400, "Initial", &[("README.md", "app\n")]);
+        let feature = root.join("app-feature");
+        repo.worktree("feature", &feature, None).unwrap();
+        let gone = root.join("app-gone");
+        repo.worktree("gone", &gone, None).unwrap();
+        // Pruned from git while its directory survives
+        let pruned = root.join("app-pruned");
+        repo.worktree("pruned", &pruned, None)
+            .unwrap()
+            .prune(Some(git2::WorktreePruneOptions::new().valid(true)))
+            .unwrap();
+
+        write_session(&projects, &main, "m1", "main");
+        write_session(&projects, &feature, "f1", "feature");
+        write_session(&projects, &feature, "f2", "feature-spike");
+        write_session(&projects, &gone, "g1", "gone");
+        write_session(&projects, &pruned, "p1", "pruned");
+        write_session(&projects, &root.join("other"), "o1", "main");
+        fs::remove_dir_all(&gone).unwrap();
+
+        let claude_path = claude.to_string_lossy().to_string();
+        // Reachable from any member, including the deleted one
+        for from in [&main, &feature, &gone] {
+            let family = get_worktree_family(
+                claude_path.clone(),
+                from.to_string_lossy().to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(family.main_path, main.to_string_lossy());
+            assert_eq!(family.members.len(), 4);
+            assert_eq!(family.project_paths.len(), 4);
+            assert_eq!(family.stats.total_sessions, 5);
+
+            let [main_member, feature_member, gone_member, pruned_member] = &family.members[..]
+            else {
+                panic!("expected four members");
+            };
+            assert!(main_member.worktree.as_ref().unwrap().is_main);
+            assert_eq!(main_member.projects.len(), 1);
+
+            assert!(feature_member.exists);
+            let mut branches = feature_member.branches.clone();
+            branches.sort();
+            assert_eq!(branches, vec!["feature", "feature-spike"]);
+            assert_eq!(feature_member.branches[0], "feature");
+
+            assert!(!gone_member.exists);
+            assert!(gone_member.worktree.as_ref().unwrap().prunable);
+            assert_eq!(gone_member.branches, vec!["gone"]);
+            assert_eq!(gone_member.projects.len(), 1);
+
+            assert!(pruned_member.exists);
+            assert!(pruned_member.worktree.is_none());
+            assert_eq!(pruned_member.branches, vec!["pruned"]);
+        }
+
+        assert!(get_worktree_family(
+            claude_path,
+            root.join("other").to_string_lossy().to_string(),
+            None,
+            None
+        )
+        .await
+        .is_err());
+    }
+}
@@ -6,38 +6,55 @@ pub mod utils;
 pub mod test_utils;
 
 use crate::commands::{
+    archive::{archive_sessions, restore_archived_sessions},
+    bundle::{export_session_bundle, import_session_bundle, inspect_session_bundle},
+    claude_dir::get_claude_dir_resolution,
     claude_settings::{
         get_all_mcp_servers, get_all_settings, get_claude_json_config, get_mcp_servers,
         get_settings_by_scope, read_text_file, save_mcp_servers, save_settings, write_text_file,
     },
+    commits::correlate_session_commits,
+    config_watcher::{start_config_watcher, stop_config_watcher, ConfigWatcherState},
+    export::{
+        export_project_html, export_session_html, export_session_markdown, get_redaction_detectors,
+        preview_session_redactions, redact_text_for_sharing, render_session_as_markdown,
+    },
     feedback::{get_system_info, open_github_issues, send_feedback},
+    git::{get_git_commit_files, get_git_head, get_git_log_page, get_git_worktrees},
     mcp_presets::{delete_mcp_preset, get_mcp_preset, load_mcp_presets, save_mcp_preset},
+    merge::{
+        detect_moved_projects, get_merged_project_stats_summary, get_merged_recent_edits,
+        load_merged_project_sessions,
+    },
     metadata::{
         get_metadata_folder_path, get_session_display_name, is_project_hidden, load_user_metadata,
         save_user_metadata, update_project_metadata, update_session_metadata, update_user_settings,
         MetadataState,
     },
     project::{get_claude_folder_path, get_git_log, scan_projects, validate_claude_folder},
+    roots::{add_data_root, list_data_roots, remove_data_root},
     session::{
-        get_recent_edits, get_session_message_count, load_project_sessions, load_session_messages,
+        audit_secret_leaks, export_secret_audit, get_checkpoint_file_content, get_disk_usage,
+        get_recent_edit_content, get_recent_edits, get_session_checkpoints,
+        get_session_message_count, load_project_sessions, load_session_messages,
         load_session_messages_paginated, rename_session_native, reset_session_native_name,
-        restore_file, search_messages,
+        restore_checkpoint_file, restore_file, search_messages,
     },
     settings::{delete_preset, get_preset, load_presets, save_preset},
     stats::{
         get_global_stats_summary, get_project_stats_summary, get_project_token_stats,
         get_session_comparison, get_session_token_stats,
     },
+    tail::{follow_session, unfollow_session, FollowedSessions},
     unified_presets::{
         delete_unified_preset, get_unified_preset, load_unified_presets, save_unified_preset,
     },
-    watcher::{start_file_watcher, stop_file_watcher},
+    watcher::{get_file_watcher_status, start_file_watcher, stop_file_watcher, WatcherRegistry},
+    worktree::get_worktree_family,
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    use std::sync::{Arc, Mutex};
-
     #[allow(unused_mut)]
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
@@ -51,22 +68,43 @@ pub fn run() {
 
     builder
         .manage(MetadataState::default())
-        .manage(Arc::new(Mutex::new(None))
-            as Arc<
-                Mutex<Option<notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>>>,
-            >)
+        .manage(FollowedSessions::default())
+        .manage(ConfigWatcherState::default())
+        .manage(WatcherRegistry::default())
         .invoke_handler(tauri::generate_handler![
             get_claude_folder_path,
+            get_claude_dir_resolution,
             validate_claude_folder,
             scan_projects,
+            list_data_roots,
+            add_data_root,
+            remove_data_root,
+            detect_moved_projects,
+            load_merged_project_sessions,
+            get_merged_project_stats_summary,
+            get_merged_recent_edits,
             get_git_log,
+            get_git_log_page,
+            get_git_commit_files,
+            get_git_head,
+            get_git_worktrees,
+            get_worktree_family,
+            correlate_session_commits,
             load_project_sessions,
             load_session_messages,
             load_session_messages_paginated,
             get_session_message_count,
             search_messages,
+            audit_secret_leaks,
+            get_disk_usage,
+            export_secret_audit,
             get_recent_edits,
+            get_recent_edit_content,
             restore_file,
+            // File history checkpoint commands
+            get_session_checkpoints,
+            get_checkpoint_file_content,
+            restore_checkpoint_file,
             get_session_token_stats,
             get_project_token_stats,
             get_project_stats_summary,
@@ -110,12 +148,31 @@ pub fn run() {
             // File I/O commands for export/import
             write_text_file,
             read_text_file,
+            // Session export commands
+            render_session_as_markdown,
+            export_session_markdown,
+            export_session_html,
+            export_project_html,
+            preview_session_redactions,
+            redact_text_for_sharing,
+            get_redaction_detectors,
+            // Session bundle commands
+            archive_sessions,
+            restore_archived_sessions,
+            export_session_bundle,
+            inspect_session_bundle,
+            import_session_bundle,
             // Native session rename commands
             rename_session_native,
             reset_session_native_name,
             // File watcher commands
             start_file_watcher,
-            stop_file_watcher
+            stop_file_watcher,
+            get_file_watcher_status,
+            follow_session,
+            unfollow_session,
+            start_config_watcher,
+            stop_config_watcher
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
@@ -2,9 +2,19 @@
 //!
 //! This module contains all the data structures used throughout the application.
 
+mod archive;
+mod audit;
+mod bundle;
+mod checkpoint;
+mod claude_dir;
+mod correlation;
+mod disk_usage;
 mod edit;
+mod git;
+mod merge;
 mod message;
 mod metadata;
+mod roots;
 mod session;
 mod stats;
 
@@ -12,8 +22,18 @@ mod stats;
 mod snapshot_tests;
 
 // Re-export all types for backward compatibility
+pub use archive::*;
+pub use audit::*;
+pub use bundle::*;
+pub use checkpoint::*;
+pub use claude_dir::*;
+pub use correlation::*;
+pub use disk_usage::*;
 pub use edit::*;
+pub use git::*;
+pub use merge::*;
 pub use message::*;
 pub use metadata::*;
+pub use roots::*;
 pub use session::*;
 pub use stats::*;
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// Which sessions to move into the archive
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ArchiveOptions {
+    /// Archive sessions not modified in this many days
+    pub older_than_days: Option<u32>,
+    /// Archive every session of projects hidden in user settings
+    pub include_hidden_projects: bool,
+    /// Only report what would be archived
+    pub dry_run: bool,
+}
+
+/// Why a session was selected for archiving
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveReason {
+    Age,
+    HiddenProject,
+}
+
+/// A session moved (or, in a dry run, to be moved) into the archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSessionEntry {
+    pub project_name: String,
+    pub session_id: String,
+    pub source_path: String,
+    pub archive_path: String,
+    /// The session file plus its subagent transcripts
+    pub file_count: usize,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    pub last_modified: String,
+    pub reason: ArchiveReason,
+}
+
+/// A session that could not be archived and was left live
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveFailure {
+    pub session_path: String,
+    pub error: String,
+}
+
+/// Result of an archive run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveReport {
+    pub dry_run: bool,
+    pub sessions: Vec<ArchivedSessionEntry>,
+    #[serde(default)]
+    pub failed: Vec<ArchiveFailure>,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    /// Space freed under `~/.claude/projects`, net of the archive's own size
+    pub bytes_reclaimed: u64,
+}
+
+/// An archived session and where it was (or would be) restored to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RestoredSession {
+    pub archive_path: String,
+    pub file_path: String,
+}
+
+/// Outcome of restoring archived sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveRestoreResult {
+    pub restored: Vec<RestoredSession>,
+    /// Left in the archive because a live session already exists at `file_path`
+    pub skipped: Vec<RestoredSession>,
+}
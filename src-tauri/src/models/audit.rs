@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Options for the secret-leak audit
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SecretAuditOptions {
+    /// Also report emails and IP addresses, not just credentials
+    pub include_pii: bool,
+}
+
+/// File formats the audit can be exported as
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    Json,
+    Sarif,
+}
+
+/// A secret exposed to the model in one message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretLeakFinding {
+    pub project_name: String,
+    pub session_id: String,
+    pub file_path: String,
+    /// 1-based line in the session file
+    pub line_number: usize,
+    pub timestamp: String,
+    pub message_type: String,
+    /// JSON path inside the entry, e.g. `message.content[1].input.command`
+    pub location: String,
+    pub detector: String,
+    /// Masked preview, never the full secret
+    pub preview: String,
+    /// Stable hash for grouping repeated leaks of the same secret
+    pub fingerprint: String,
+}
+
+/// Result of scanning the whole history for leaked secrets
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecretAuditReport {
+    pub scanned_files: usize,
+    pub scanned_messages: usize,
+    pub sessions_affected: usize,
+    pub unique_secrets: usize,
+    pub by_detector: BTreeMap<String, usize>,
+    /// Most recent first
+    pub findings: Vec<SecretLeakFinding>,
+}
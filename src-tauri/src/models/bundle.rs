@@ -0,0 +1,79 @@
+use super::SessionMetadata;
+use serde::{Deserialize, Serialize};
+
+/// Current `.cchv` bundle format version
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// A file stored in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundledFile {
+    /// Path relative to the project folder, e.g. `<id>/subagents/agent-1.jsonl`
+    pub relative_path: String,
+    pub size: u64,
+    /// Hex-encoded SHA-256 of the file contents
+    pub sha256: String,
+}
+
+/// One session and everything that belongs to it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundledSession {
+    pub session_id: String,
+    /// Encoded project folder name under `projects/`
+    pub project_dir: String,
+    pub original_path: String,
+    /// Tags, notes and custom name from `UserMetadata`
+    pub metadata: Option<SessionMetadata>,
+    /// The session file first, then its subagent transcripts
+    pub files: Vec<BundledFile>,
+}
+
+/// `manifest.json` at the root of a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub created_at: String,
+    pub app_version: String,
+    pub sessions: Vec<BundledSession>,
+}
+
+/// What to do when a bundled session already exists in the target folder
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleConflictPolicy {
+    /// Leave the existing session alone and report it
+    #[default]
+    Skip,
+    /// Replace the existing session; only used when the user confirms
+    Overwrite,
+}
+
+/// A bundled session that is already present
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleConflict {
+    pub session_id: String,
+    pub existing_path: String,
+}
+
+/// Bundle contents plus conflicts with the target folder, shown before importing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleInspection {
+    pub manifest: BundleManifest,
+    pub conflicts: Vec<BundleConflict>,
+}
+
+/// A session written by an import
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImportedSession {
+    pub session_id: String,
+    pub file_path: String,
+    /// Whether an existing session was replaced
+    pub overwritten: bool,
+}
+
+/// Outcome of importing a bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundleImportResult {
+    pub imported: Vec<ImportedSession>,
+    /// Sessions left untouched because they already exist
+    pub skipped: Vec<BundleConflict>,
+}
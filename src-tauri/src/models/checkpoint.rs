@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// A single file tracked by a `file-history-snapshot` checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrackedFileBackup {
+    /// Absolute path of the tracked file
+    pub file_path: String,
+    /// Backup file name under `~/.claude/file-history/<session>/`
+    /// (None means the file did not exist when the checkpoint was taken)
+    pub backup_file_name: Option<String>,
+    /// Backup version counter maintained by Claude Code
+    pub version: Option<u32>,
+    /// When the backup was written
+    pub backup_time: Option<String>,
+    /// Whether the backup file is still present on disk
+    pub backup_exists: bool,
+}
+
+/// A rewind checkpoint recorded before a user message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCheckpoint {
+    /// ID of the message the checkpoint belongs to
+    pub message_id: String,
+    pub timestamp: String,
+    /// Number of `isSnapshotUpdate` entries merged into this checkpoint
+    pub update_count: usize,
+    pub files: Vec<TrackedFileBackup>,
+}
+
+/// All checkpoints for one session, in file order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionCheckpoints {
+    pub session_id: String,
+    /// Backup directory (`~/.claude/file-history/<session>`), if it could be resolved
+    pub file_history_dir: Option<String>,
+    pub checkpoints: Vec<FileCheckpoint>,
+}
+
+/// A backed up file's content as of a checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckpointFileContent {
+    /// The content as text; invalid UTF-8 is replaced
+    pub content: String,
+    /// The backup is not text (invalid UTF-8 or NUL bytes), so `content` is lossy
+    pub is_binary: bool,
+}
+
+impl CheckpointFileContent {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Self {
+                content: text.to_string(),
+                is_binary: text.contains('\0'),
+            },
+            Err(_) => Self {
+                content: String::from_utf8_lossy(bytes).into_owned(),
+                is_binary: true,
+            },
+        }
+    }
+}
+
+/// Outcome of restoring a file to a checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointRestoreAction {
+    /// File contents were replaced with the backup
+    Restored,
+    /// File did not exist at the checkpoint and was removed
+    Removed,
+    /// File did not exist at the checkpoint and is already absent
+    Unchanged,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_checkpoint_serialization() {
+        let checkpoint = FileCheckpoint {
+            message_id: "msg-1".to_string(),
+            timestamp: "2025-06-26T10:00:00Z".to_string(),
+            update_count: 1,
+            files: vec![TrackedFileBackup {
+                file_path: "/project/src/main.rs".to_string(),
+                backup_file_name: Some("abc123@v2".to_string()),
+                version: Some(2),
+                backup_time: Some("2025-06-26T10:00:00Z".to_string()),
+                backup_exists: true,
+            }],
+        };
+
+        let serialized = serde_json::to_string(&checkpoint).unwrap();
+        let deserialized: FileCheckpoint = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized.message_id, "msg-1");
+        assert_eq!(deserialized.files.len(), 1);
+        assert_eq!(deserialized.files[0], checkpoint.files[0]);
+    }
+
+    #[test]
+    fn test_checkpoint_restore_action_serialization() {
+        assert_eq!(
+            serde_json::to_string(&CheckpointRestoreAction::Removed).unwrap(),
+            "\"removed\""
+        );
+    }
+}
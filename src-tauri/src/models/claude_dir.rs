@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a Claude folder candidate was found
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaudeDirSource {
+    /// `$CLAUDE_CONFIG_DIR`
+    ConfigDirEnv,
+    /// `~/.claude`
+    Home,
+    /// `$XDG_CONFIG_HOME/claude`, or `~/.config/claude`
+    XdgConfig,
+    /// A Windows user's `.claude` seen from WSL under `/mnt/c/Users`
+    Wsl,
+}
+
+/// A location that may hold Claude Code data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaudeDirCandidate {
+    pub path: String,
+    pub source: ClaudeDirSource,
+    pub exists: bool,
+    /// Whether the folder contains a `projects/` directory
+    pub has_projects: bool,
+}
+
+/// The Claude folder the app uses and how it was chosen
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaudeDirResolution {
+    pub path: String,
+    pub source: ClaudeDirSource,
+    /// `.claude.json` belonging to the chosen folder
+    pub global_config_path: String,
+    /// Every location checked, in priority order
+    pub candidates: Vec<ClaudeDirCandidate>,
+}
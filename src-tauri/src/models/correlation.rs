@@ -0,0 +1,69 @@
+use super::GitCommit;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Why a commit was linked to a session
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitMatchReason {
+    /// Committed while the session was active
+    DuringSession,
+    /// Committed shortly after the session's last message
+    AfterSession,
+    /// Touches files the session edited
+    EditedFiles,
+    /// The commit message credits Claude as co-author
+    ClaudeCoAuthor,
+}
+
+/// How loosely commits are matched to sessions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CorrelationOptions {
+    /// Commits up to this long after a session's last message count as `after_session`
+    pub after_session_minutes: u32,
+    /// Commits touching edited files, or co-authored by Claude, match up to this long after a session
+    pub file_match_days: u32,
+}
+
+impl Default for CorrelationOptions {
+    fn default() -> Self {
+        Self {
+            after_session_minutes: 60,
+            file_match_days: 7,
+        }
+    }
+}
+
+/// A commit in the correlation window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelatedCommit {
+    pub commit: GitCommit,
+    /// Files changed, relative to the repository root
+    pub files: Vec<String>,
+    pub claude_co_authored: bool,
+}
+
+/// One session ↔ commit match
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionCommitLink {
+    /// Session file path
+    pub session_path: String,
+    pub session_id: String,
+    pub commit_hash: String,
+    pub reasons: Vec<CommitMatchReason>,
+    /// Files both edited in the session and changed by the commit
+    pub shared_files: Vec<String>,
+}
+
+/// Sessions of a project matched against commits of its repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommitCorrelation {
+    /// Newest first
+    pub commits: Vec<CorrelatedCommit>,
+    pub links: Vec<SessionCommitLink>,
+    /// Session file path → commit hashes, newest first
+    pub session_commits: HashMap<String, Vec<String>>,
+    /// Commit hash → session file paths
+    pub commit_sessions: HashMap<String, Vec<String>>,
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// Bytes of session data by kind of content
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContentBreakdown {
+    pub text: u64,
+    pub thinking: u64,
+    /// Tool inputs written by the model
+    pub tool_calls: u64,
+    /// Tool output, including the `toolUseResult` copy kept on each entry
+    pub tool_results: u64,
+    /// Base64 image data
+    pub images: u64,
+    /// `file-history-snapshot` entries
+    pub snapshots: u64,
+    /// JSON structure, ids, usage and everything else
+    pub other: u64,
+}
+
+impl ContentBreakdown {
+    pub fn add(&mut self, other: &ContentBreakdown) {
+        self.text += other.text;
+        self.thinking += other.thinking;
+        self.tool_calls += other.tool_calls;
+        self.tool_results += other.tool_results;
+        self.images += other.images;
+        self.snapshots += other.snapshots;
+        self.other += other.other;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.text
+            + self.thinking
+            + self.tool_calls
+            + self.tool_results
+            + self.images
+            + self.snapshots
+            + self.other
+    }
+
+    /// Name of the largest category
+    pub fn dominant(&self) -> &'static str {
+        [
+            ("text", self.text),
+            ("thinking", self.thinking),
+            ("tool_calls", self.tool_calls),
+            ("tool_results", self.tool_results),
+            ("images", self.images),
+            ("snapshots", self.snapshots),
+            ("other", self.other),
+        ]
+        .into_iter()
+        .max_by_key(|(_, bytes)| *bytes)
+        .map_or("other", |(name, _)| name)
+    }
+}
+
+/// Disk usage of one session file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDiskUsage {
+    pub file_path: String,
+    /// Transcript size, inflated for archived sessions; the breakdown adds up to it
+    pub total_bytes: u64,
+    /// Size of the file on disk, compressed for archived sessions
+    pub disk_bytes: u64,
+    pub line_count: usize,
+    pub breakdown: ContentBreakdown,
+}
+
+/// Disk usage of one project folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDiskUsage {
+    pub name: String,
+    pub path: String,
+    pub total_bytes: u64,
+    pub disk_bytes: u64,
+    /// Non-transcript files such as caches and indexes
+    pub other_files_bytes: u64,
+    pub breakdown: ContentBreakdown,
+    /// Largest first
+    pub sessions: Vec<SessionDiskUsage>,
+}
+
+/// One of the largest lines across all sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeLine {
+    pub project_name: String,
+    pub file_path: String,
+    /// 1-based line in the session file
+    pub line_number: usize,
+    pub bytes: u64,
+    pub message_type: String,
+    pub timestamp: Option<String>,
+    /// Category holding most of the line's bytes
+    pub dominant_content: String,
+}
+
+/// Disk usage of `~/.claude/projects`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskUsageReport {
+    pub total_bytes: u64,
+    pub disk_bytes: u64,
+    pub breakdown: ContentBreakdown,
+    /// Largest first
+    pub projects: Vec<ProjectDiskUsage>,
+    /// Largest first
+    pub largest_lines: Vec<LargeLine>,
+}
@@ -1,17 +1,90 @@
 use serde::{Deserialize, Serialize};
 
+/// How far a reconstructed file content can be trusted
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayStatus {
+    /// Content was written verbatim or matches the recorded `structuredPatch`
+    Verified,
+    /// Every replacement applied, but there was no patch to check against
+    #[default]
+    Unverified,
+    /// A replacement was not found or the result disagrees with the patch
+    Mismatched,
+}
+
+/// Where an edit was recorded, used to load its contents on demand
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct EditSource {
+    pub session_file: String,
+    /// Byte offset of the JSONL line holding the tool result
+    pub line_offset: usize,
+}
+
 /// Recent file edit information for recovery purposes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecentFileEdit {
     pub file_path: String,
     pub timestamp: String,
     pub session_id: String,
-    pub operation_type: String, // "edit" or "write"
+    pub operation_type: String, // "edit", "write" or "notebook"
     pub content_after_change: String,
     pub original_content: Option<String>,
     pub lines_added: usize,
     pub lines_removed: usize,
     pub cwd: Option<String>, // Working directory when edit was made
+    /// Confidence in `content_after_change`
+    #[serde(default)]
+    pub replay_status: ReplayStatus,
+    /// Number of replacements whose `old_string` was not found
+    #[serde(default)]
+    pub failed_replacements: usize,
+    /// Session line the edit came from, set by the recent edits scan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<EditSource>,
+}
+
+/// A file that a Bash command or an unreplayable `NotebookEdit` may have
+/// changed without a recoverable body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellModifiedFile {
+    pub file_path: String,
+    pub timestamp: String,
+    pub session_id: String,
+    /// The Bash command that referenced the path, or a `NotebookEdit …` summary
+    pub command: String,
+    pub cwd: Option<String>,
+}
+
+/// Which working directories count as "inside the project" for recent edits
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum CwdScope {
+    /// Only files under the most common `cwd` of the project's sessions
+    #[default]
+    MostCommon,
+    /// No working directory filtering
+    All,
+    /// Only files under one of the listed directories
+    Explicit { cwds: Vec<String> },
+}
+
+/// Filters for the recent edits query; empty fields match everything
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RecentEditsFilter {
+    pub session_ids: Vec<String>,
+    /// Inclusive RFC 3339 lower bound
+    pub since: Option<String>,
+    /// Inclusive RFC 3339 upper bound
+    pub until: Option<String>,
+    /// Glob patterns (`*`, `**`, `?`) matched against the absolute path or the
+    /// path relative to the edit's `cwd`
+    pub include_globs: Vec<String>,
+    pub exclude_globs: Vec<String>,
+    /// `edit`, `write` or `notebook`
+    pub operation_types: Vec<String>,
+    pub cwd_scope: CwdScope,
 }
 
 /// Result container for recent edits query
@@ -39,6 +112,9 @@ mod tests {
             lines_added: 5,
             lines_removed: 3,
             cwd: Some("/path/to".to_string()),
+            replay_status: ReplayStatus::Verified,
+            failed_replacements: 0,
+            source: None,
         };
 
         let serialized = serde_json::to_string(&edit).unwrap();
@@ -66,6 +142,9 @@ mod tests {
             lines_added: 1,
             lines_removed: 0,
             cwd: None,
+            replay_status: ReplayStatus::Verified,
+            failed_replacements: 0,
+            source: None,
         };
 
         let serialized = serde_json::to_string(&edit).unwrap();
@@ -90,6 +169,9 @@ mod tests {
                     lines_added: 1,
                     lines_removed: 0,
                     cwd: Some("/project".to_string()),
+                    replay_status: ReplayStatus::Verified,
+                    failed_replacements: 0,
+                    source: None,
                 },
                 RecentFileEdit {
                     file_path: "/file2.rs".to_string(),
@@ -101,6 +183,9 @@ mod tests {
                     lines_added: 2,
                     lines_removed: 0,
                     cwd: Some("/project".to_string()),
+                    replay_status: ReplayStatus::Verified,
+                    failed_replacements: 0,
+                    source: None,
                 },
             ],
             total_edits_count: 5,
@@ -0,0 +1,86 @@
+use super::{ClaudeProject, GitCommit, ProjectStatsSummary};
+use serde::{Deserialize, Serialize};
+
+/// One page of a repository's history, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLogPage {
+    pub commits: Vec<GitCommit>,
+    pub offset: usize,
+    pub has_more: bool,
+}
+
+/// How a commit changed a file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitChangeStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Copied,
+    TypeChange,
+    Other,
+}
+
+/// A file changed by a commit, compared with its first parent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitFileChange {
+    /// Path relative to the repository root, with `/` separators
+    pub path: String,
+    /// Previous path of a renamed or copied file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    pub status: GitChangeStatus,
+    pub additions: usize,
+    pub deletions: usize,
+    pub binary: bool,
+}
+
+/// What `HEAD` points at
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GitHead {
+    /// Checked-out branch; `None` when detached
+    pub branch: Option<String>,
+    /// Commit hash; `None` on a branch with no commits yet
+    pub commit: Option<String>,
+    pub detached: bool,
+}
+
+/// A working tree of a repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitWorktree {
+    /// Name of a linked worktree; `None` for the main checkout
+    pub name: Option<String>,
+    pub path: String,
+    pub is_main: bool,
+    pub head: GitHead,
+    pub locked: bool,
+    /// Linked worktree whose directory is gone
+    pub prunable: bool,
+}
+
+/// A checkout in a worktree family and the Claude projects recorded in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeFamilyMember {
+    pub path: String,
+    /// The worktree as git lists it; `None` once git no longer tracks it
+    pub worktree: Option<GitWorktree>,
+    /// Whether the directory still exists
+    pub exists: bool,
+    /// Checked-out branch first, then the other branches its sessions ran on
+    pub branches: Vec<String>,
+    pub projects: Vec<ClaudeProject>,
+}
+
+/// A repository's main checkout and every worktree made from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeFamily {
+    /// Main checkout, or the git directory of a bare repository
+    pub main_path: String,
+    /// Main checkout first, then linked worktrees by name, then checkouts git has forgotten
+    pub members: Vec<WorktreeFamilyMember>,
+    /// Storage paths of every member's projects
+    pub project_paths: Vec<String>,
+    /// Stats across all of `project_paths`
+    pub stats: ProjectStatsSummary,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Evidence that two project folders hold the history of the same project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MergeSignal {
+    /// Both checkouts have the same `origin` remote (normalized)
+    GitRemote { url: String },
+    /// Both checkouts share a root commit
+    GitRootCommit { commit: String },
+    /// One project's sessions ran in the other's directory
+    CwdLineage { cwd: String },
+    /// The same session transcript exists in both folders
+    SharedSession { session_id: String },
+    /// The project directory is gone and one with the same name exists elsewhere,
+    /// backed by another signal or sessions in the same subdirectory of both
+    MovedFolder { folder_name: String },
+}
+
+/// Project folders that look like one project split by a move or rename
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectMergeSuggestion {
+    /// Storage folder of the most recently active project
+    pub primary_path: String,
+    /// Every storage folder in the group, most recently active first
+    pub project_paths: Vec<String>,
+    /// Decoded project directories, in the same order
+    pub actual_paths: Vec<String>,
+    pub signals: Vec<MergeSignal>,
+}
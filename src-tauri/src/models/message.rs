@@ -45,6 +45,11 @@ pub struct RawLogEntry {
     #[serde(rename = "isSidechain")]
     pub is_sidechain: Option<bool>,
     pub cwd: Option<String>,
+    /// Branch checked out when the entry was written
+    #[serde(rename = "gitBranch")]
+    pub git_branch: Option<String>,
+    /// Claude Code version that wrote the entry
+    pub version: Option<String>,
 
     // Cost and performance metrics (2025 additions)
     #[serde(rename = "costUSD")]
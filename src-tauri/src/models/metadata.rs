@@ -213,6 +213,38 @@ pub struct UserSettings {
     /// Project tree grouping mode: "none", "worktree", or "directory"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grouping_mode: Option<String>,
+
+    /// Custom regex rules applied by the export redaction pass
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redaction_rules: Vec<RedactionRule>,
+
+    /// Built-in redaction detectors the user has turned off (e.g., "email")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_redaction_detectors: Vec<String>,
+
+    /// Extra Claude data folders, e.g. other `CLAUDE_CONFIG_DIR`s or copies from a VM
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data_roots: Vec<DataRoot>,
+}
+
+/// User-defined redaction rule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RedactionRule {
+    /// Label used in placeholders and the redaction report
+    pub name: String,
+    /// Regular expression; a `secret` capture group limits what gets replaced
+    pub pattern: String,
+}
+
+/// A named Claude data folder registered by the user
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DataRoot {
+    /// Unique label that projects from this folder are tagged with
+    pub name: String,
+    /// The Claude folder, i.e. the one containing `projects/`
+    pub path: String,
 }
 
 #[cfg(test)]
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Which data roots a command operates on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RootSelection {
+    /// The default root and every registered root
+    All,
+    /// Roots by name; `default` is the folder the app was opened with
+    Only(Vec<String>),
+}
+
+/// A data root as listed in settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataRootInfo {
+    pub name: String,
+    pub path: String,
+    pub is_default: bool,
+    /// Whether the folder and its `projects/` directory are currently reachable
+    pub available: bool,
+}
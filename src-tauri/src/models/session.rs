@@ -23,6 +23,38 @@ pub struct GitInfo {
     pub main_project_path: Option<String>,
 }
 
+/// How a project's filesystem path was decoded from its storage folder
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PathDecodeMethod {
+    /// `cwd` recorded on the project's session entries
+    SessionCwd,
+    /// `originalPath` in `sessions-index.json`
+    SessionsIndex,
+    /// Folder name split where directories exist on disk
+    Filesystem,
+    /// Folder name split on its first hyphens
+    Heuristic,
+    /// Not a Claude project folder; the storage path is used as is
+    StoragePath,
+}
+
+/// How much to trust a decoded project path
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum PathConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// How `ClaudeProject::actual_path` was obtained
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PathResolution {
+    pub method: PathDecodeMethod,
+    pub confidence: PathConfidence,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudeProject {
     pub name: String,
@@ -36,6 +68,18 @@ pub struct ClaudeProject {
     /// Git worktree 정보
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git_info: Option<GitInfo>,
+    /// Sessions of this project that live in the archive
+    #[serde(default)]
+    pub archived_session_count: usize,
+    /// Whether `message_count` is estimated from file size rather than counted
+    #[serde(default)]
+    pub is_estimate: bool,
+    /// Name of the data root the project was found in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+    /// How `actual_path` was decoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_resolution: Option<PathResolution>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +95,43 @@ pub struct ClaudeSession {
     pub has_tool_use: bool,
     pub has_errors: bool,
     pub summary: Option<String>,
+    /// Read-only session stored compressed in the archive
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Branch checked out at the session's latest message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    /// Every branch the session ran on, in order of first use
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_branches: Vec<String>,
+    /// Claude Code version that wrote the session's latest message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Limits search and stats to messages recorded on a branch or by a Claude Code version
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SessionContextFilter {
+    pub git_branch: Option<String>,
+    pub version: Option<String>,
+}
+
+impl SessionContextFilter {
+    pub fn is_empty(&self) -> bool {
+        self.git_branch.is_none() && self.version.is_none()
+    }
+
+    /// Whether a message with this branch and version passes the filter
+    pub fn matches(&self, git_branch: Option<&str>, version: Option<&str>) -> bool {
+        self.git_branch
+            .as_deref()
+            .map_or(true, |wanted| git_branch == Some(wanted))
+            && self
+                .version
+                .as_deref()
+                .map_or(true, |wanted| version == Some(wanted))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +161,10 @@ mod tests {
             has_tool_use: true,
             has_errors: false,
             summary: Some("Test conversation".to_string()),
+            is_archived: false,
+            git_branch: None,
+            git_branches: Vec::new(),
+            version: None,
         };
 
         let serialized = serde_json::to_string(&session).unwrap();
@@ -202,6 +202,10 @@ mod project_snapshots {
             message_count: 1337,
             last_modified: "2025-01-15T10:30:00Z".to_string(),
             git_info: None,
+            archived_session_count: 0,
+            is_estimate: false,
+            root: None,
+            path_resolution: None,
         };
 
         assert_json_snapshot!("claude_project", project);
@@ -226,6 +230,10 @@ mod session_snapshots {
             has_tool_use: true,
             has_errors: false,
             summary: Some("Test conversation summary".to_string()),
+            is_archived: false,
+            git_branch: None,
+            git_branches: Vec::new(),
+            version: None,
         };
 
         assert_json_snapshot!("claude_session", session);
@@ -301,6 +309,9 @@ mod edit_snapshots {
             lines_added: 3,
             lines_removed: 1,
             cwd: Some("/path/to".to_string()),
+            replay_status: ReplayStatus::Verified,
+            failed_replacements: 0,
+            source: None,
         };
 
         assert_json_snapshot!("recent_file_edit", edit);
@@ -319,6 +330,9 @@ mod edit_snapshots {
                 lines_added: 5,
                 lines_removed: 2,
                 cwd: Some("/project".to_string()),
+                replay_status: ReplayStatus::Verified,
+                failed_replacements: 0,
+                source: None,
             }],
             total_edits_count: 10,
             unique_files_count: 3,
@@ -58,6 +58,19 @@ pub struct ProjectStatsSummary {
     pub daily_stats: Vec<DailyStats>,
     pub activity_heatmap: Vec<ActivityHeatmap>,
     pub token_distribution: TokenDistribution,
+    /// Usage per git branch, most tokens first
+    #[serde(default)]
+    pub branch_stats: Vec<BranchStats>,
+}
+
+/// Usage of the messages recorded on one git branch
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BranchStats {
+    pub git_branch: String,
+    pub session_count: usize,
+    pub message_count: usize,
+    pub total_tokens: u64,
+    pub token_distribution: TokenDistribution,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -118,6 +131,9 @@ pub struct GlobalStatsSummary {
     pub most_used_tools: Vec<ToolUsageStats>,
     pub model_distribution: Vec<ModelStats>,
     pub top_projects: Vec<ProjectRanking>,
+    /// Usage per git branch, most tokens first
+    #[serde(default)]
+    pub branch_stats: Vec<BranchStats>,
 }
 
 #[cfg(test)]
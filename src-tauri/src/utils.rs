@@ -1,6 +1,8 @@
-use crate::models::{GitInfo, GitWorktreeType};
+use crate::models::{GitInfo, GitWorktreeType, PathConfidence, PathDecodeMethod, PathResolution};
 use memchr::memchr_iter;
+use std::collections::HashMap;
 use std::fs;
+use std::hash::BuildHasher;
 use std::path::Path;
 
 /// Estimated average bytes per JSONL line (used for capacity pre-allocation)
@@ -78,42 +80,129 @@ pub fn estimate_message_count_from_size(file_size: u64) -> usize {
 /// - `/Users/jack/.claude/projects/-tmp-feature-my-project` → `/tmp/feature-my-project`
 ///
 /// This function uses filesystem existence checks to correctly decode paths
-/// where the project name itself contains hyphens.
+/// where the project name itself contains hyphens. Prefer
+/// [`resolve_project_path`] when the sessions' `cwd`s are at hand.
 pub fn decode_project_path(session_storage_path: &str) -> String {
-    // 1. Try reading originalPath from sessions-index.json (most reliable)
-    let index_path = Path::new(session_storage_path).join("sessions-index.json");
+    resolve_project_path(session_storage_path, &HashMap::new()).0
+}
+
+/// Folder name Claude Code stores a project under: every character other
+/// than an ASCII letter or digit becomes `-`
+pub fn encode_project_path(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// The most common `cwd`, preferring those that encode to `folder_name`
+fn dominant_cwd<'a, S: BuildHasher>(
+    cwd_counts: &'a HashMap<String, usize, S>,
+    folder_name: &str,
+) -> Option<(&'a str, bool)> {
+    cwd_counts
+        .iter()
+        .filter(|(cwd, _)| !cwd.is_empty())
+        .map(|(cwd, count)| {
+            (
+                cwd.as_str(),
+                encode_project_path(cwd) == folder_name,
+                *count,
+            )
+        })
+        // Encoding matches first, then the most entries, then the shortest path
+        .max_by(|a, b| {
+            (a.1, a.2)
+                .cmp(&(b.1, b.2))
+                .then_with(|| b.0.len().cmp(&a.0.len()))
+                .then_with(|| b.0.cmp(a.0))
+        })
+        .map(|(cwd, matches, _)| (cwd, matches))
+}
+
+/// Decode a project's filesystem path and report how it was decoded
+///
+/// `cwd_counts` tallies the `cwd` field of the project's session entries.
+/// A `cwd` that encodes back to the folder name is exact; `sessions-index.json`
+/// comes next, then the most common `cwd`, filesystem probing, and finally a
+/// hyphen-splitting guess.
+pub fn resolve_project_path<S: BuildHasher>(
+    session_storage_path: &str,
+    cwd_counts: &HashMap<String, usize, S>,
+) -> (String, PathResolution) {
+    let resolved = |path: String, method, confidence| (path, PathResolution { method, confidence });
+
+    // The encoded folder name, whichever Claude folder it lives in
+    const MARKER: &str = ".claude/projects/";
+    let storage = Path::new(session_storage_path);
+    let encoded = match session_storage_path.find(MARKER) {
+        Some(marker_pos) => Some(&session_storage_path[marker_pos + MARKER.len()..]),
+        None if storage.parent().and_then(Path::file_name) == Some("projects".as_ref()) => {
+            storage.file_name().and_then(|n| n.to_str())
+        }
+        None => None,
+    };
+
+    // 1. A cwd that encodes to this folder is where Claude Code was started
+    let cwd = encoded.and_then(|encoded| dominant_cwd(cwd_counts, encoded));
+    if let Some((cwd, true)) = cwd {
+        return resolved(
+            cwd.to_string(),
+            PathDecodeMethod::SessionCwd,
+            PathConfidence::High,
+        );
+    }
+
+    // 2. originalPath from sessions-index.json
+    let index_path = storage.join("sessions-index.json");
     if let Ok(content) = std::fs::read_to_string(&index_path) {
         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&content) {
             if let Some(original) = parsed.get("originalPath").and_then(|v| v.as_str()) {
                 if !original.is_empty() && Path::new(original).is_absolute() {
-                    return original.to_string();
+                    return resolved(
+                        original.to_string(),
+                        PathDecodeMethod::SessionsIndex,
+                        PathConfidence::High,
+                    );
                 }
             }
         }
     }
 
-    // 2. Fallback: decode from encoded directory name
-    const MARKER: &str = ".claude/projects/";
-    if let Some(marker_pos) = session_storage_path.find(MARKER) {
-        let encoded = &session_storage_path[marker_pos + MARKER.len()..];
+    // 3. The most common cwd, e.g. when a long folder name was shortened
+    if let Some((cwd, false)) = cwd {
+        return resolved(
+            cwd.to_string(),
+            PathDecodeMethod::SessionCwd,
+            PathConfidence::Medium,
+        );
+    }
+
+    // 4. Decode the folder name
+    if let Some(encoded) = encoded {
         if let Some(stripped) = encoded.strip_prefix('-') {
             // Try filesystem-based decoding (recursive)
             if let Some(path) = decode_with_filesystem_check(stripped) {
-                return path;
+                return resolved(path, PathDecodeMethod::Filesystem, PathConfidence::Medium);
             }
 
             // Fallback: use heuristic decoding (still uses original encoded with dash)
             let parts: Vec<&str> = encoded.splitn(4, '-').collect();
-            if parts.len() >= 4 {
-                return format!("/{}/{}/{}", parts[1], parts[2], parts[3]);
-            } else if parts.len() == 3 {
-                return format!("/{}/{}", parts[1], parts[2]);
-            } else if parts.len() == 2 {
-                return format!("/{}", parts[1]);
+            let guess = match parts.len() {
+                4.. => Some(format!("/{}/{}/{}", parts[1], parts[2], parts[3])),
+                3 => Some(format!("/{}/{}", parts[1], parts[2])),
+                2 => Some(format!("/{}", parts[1])),
+                _ => None,
+            };
+            if let Some(guess) = guess {
+                return resolved(guess, PathDecodeMethod::Heuristic, PathConfidence::Low);
             }
         }
     }
-    session_storage_path.to_string()
+    resolved(
+        session_storage_path.to_string(),
+        PathDecodeMethod::StoragePath,
+        PathConfidence::Low,
+    )
 }
 
 /// Decode path by checking filesystem existence at each possible split point
@@ -444,6 +533,64 @@ mod tests {
         assert_eq!(decode_project_path("/some/other/path"), "/some/other/path");
     }
 
+    #[test]
+    fn test_encode_project_path() {
+        assert_eq!(
+            encode_project_path("/Users/jack/claude-code-history-viewer"),
+            "-Users-jack-claude-code-history-viewer"
+        );
+        assert_eq!(
+            encode_project_path("/Users/jack/.config/my_app"),
+            "-Users-jack--config-my-app"
+        );
+    }
+
+    #[test]
+    fn test_resolve_project_path_prefers_matching_cwd() {
+        let storage = "/Users/jack/.claude/projects/-Users-jack-client-claude-code-history-viewer";
+        let cwd_counts = HashMap::from([
+            // Claude Code was started here; later entries ran in a subfolder
+            (
+                "/Users/jack/client/claude-code-history-viewer".to_string(),
+                3,
+            ),
+            (
+                "/Users/jack/client/claude-code-history-viewer/src-tauri".to_string(),
+                10,
+            ),
+        ]);
+        let (path, resolution) = resolve_project_path(storage, &cwd_counts);
+        assert_eq!(path, "/Users/jack/client/claude-code-history-viewer");
+        assert_eq!(resolution.method, PathDecodeMethod::SessionCwd);
+        assert_eq!(resolution.confidence, PathConfidence::High);
+
+        // Without a matching cwd the most common one is used, with less confidence
+        let cwd_counts = HashMap::from([("/Users/jack/elsewhere".to_string(), 2)]);
+        let (path, resolution) = resolve_project_path(storage, &cwd_counts);
+        assert_eq!(path, "/Users/jack/elsewhere");
+        assert_eq!(resolution.confidence, PathConfidence::Medium);
+
+        // Without any cwd the hyphen heuristic is a low-confidence guess
+        let (path, resolution) = resolve_project_path(storage, &HashMap::new());
+        assert_eq!(path, "/Users/jack/client-claude-code-history-viewer");
+        assert_eq!(resolution.method, PathDecodeMethod::Heuristic);
+        assert_eq!(resolution.confidence, PathConfidence::Low);
+    }
+
+    #[test]
+    fn test_resolve_project_path_outside_dot_claude() {
+        // Folders under a CLAUDE_CONFIG_DIR are recognized by their `projects` parent
+        let cwd_counts = HashMap::from([("/srv/my-app".to_string(), 1)]);
+        let (path, resolution) =
+            resolve_project_path("/opt/claude-config/projects/-srv-my-app", &cwd_counts);
+        assert_eq!(path, "/srv/my-app");
+        assert_eq!(resolution.method, PathDecodeMethod::SessionCwd);
+        assert_eq!(resolution.confidence, PathConfidence::High);
+
+        let (_, resolution) = resolve_project_path("/some/other/path", &cwd_counts);
+        assert_eq!(resolution.method, PathDecodeMethod::StoragePath);
+    }
+
     #[test]
     fn test_extract_main_git_dir_valid() {
         assert_eq!(
@@ -15,6 +15,7 @@ import { Button } from "@/components/ui/button";
 import { LoadingState } from "@/components/ui/loading";
 import { RefreshCw, FolderTree } from "lucide-react";
 import { useMCPServers } from "@/hooks/useMCPServers";
+import { listenConfigChanges, startConfigWatcher, stopConfigWatcher } from "@/services/watcherApi";
 import type {
   AllSettingsResponse,
   SettingsScope,
@@ -122,6 +123,7 @@ export const UnifiedSettingsManager: React.FC<UnifiedSettingsManagerProps> = ({
     userSettings: mcpUserSettings,
     userMcpFile: mcpUserMcpFile,
     projectMcpFile: mcpProjectMcpFile,
+    loadAllMCPServers,
     saveMCPServers,
   } = useMCPServers(projectPath);
 
@@ -143,6 +145,36 @@ export const UnifiedSettingsManager: React.FC<UnifiedSettingsManagerProps> = ({
     loadSettings();
   }, [loadSettings]);
 
+  // Reload when Claude Code or an editor changes a config file
+  React.useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    startConfigWatcher(projectPath)
+      .then(() =>
+        listenConfigChanges((change) => {
+          // settings.json also holds legacy MCP servers
+          if (change.kind === "user_settings") {
+            loadSettings();
+            loadAllMCPServers();
+          } else if (change.kind === "project_settings" || change.kind === "local_settings") {
+            loadSettings();
+          } else {
+            loadAllMCPServers();
+          }
+        })
+      )
+      .then((fn) => {
+        if (cancelled) fn();
+        else unlisten = fn;
+      })
+      .catch((err) => console.error("Failed to watch config files:", err));
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      stopConfigWatcher().catch(() => {});
+    };
+  }, [projectPath, loadSettings, loadAllMCPServers]);
+
   // Parse current settings
   const currentSettings: ClaudeCodeSettings = React.useMemo(() => {
     const content = allSettings?.[activeScope] ?? null;
@@ -167,6 +199,11 @@ export const UnifiedSettingsManager: React.FC<UnifiedSettingsManagerProps> = ({
           scope,
           content: JSON.stringify(newSettings, null, 2),
           projectPath: scope !== "user" ? effectiveProjectPath : undefined,
+          // Hashes were read for the current project only
+          expectedHash:
+            scope === "user" || effectiveProjectPath === projectPath
+              ? allSettings?.hashes?.[scope]
+              : undefined,
         });
         await loadSettings();
       } catch (err) {
@@ -174,7 +211,7 @@ export const UnifiedSettingsManager: React.FC<UnifiedSettingsManagerProps> = ({
         throw err;
       }
     },
-    [activeScope, projectPath, loadSettings]
+    [activeScope, projectPath, allSettings, loadSettings]
   );
 
   const isReadOnly = activeScope === "managed";
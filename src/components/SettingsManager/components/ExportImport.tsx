@@ -189,6 +189,8 @@ export const ExportImport: React.FC<ExportImportProps> = ({
         scope: importScope,
         content: JSON.stringify(importedSettings, null, 2),
         projectPath: importScope !== "user" ? projectPath : undefined,
+        // Rejected if the file changed since the settings were loaded
+        expectedHash: allSettings?.hashes?.[importScope],
       });
 
       onImport?.();
@@ -311,6 +313,8 @@ export const ExportImport: React.FC<ExportImportProps> = ({
         await invoke("save_settings", {
           scope: "user",
           content: JSON.stringify(importedBackup.scopes.user, null, 2),
+          // Rejected if the file changed since the settings were loaded
+          expectedHash: allSettings?.hashes?.user,
         });
       }
       if (importedBackup.scopes.project) {
@@ -322,6 +326,7 @@ export const ExportImport: React.FC<ExportImportProps> = ({
           scope: "project",
           content: JSON.stringify(importedBackup.scopes.project, null, 2),
           projectPath,
+          expectedHash: allSettings?.hashes?.project,
         });
       }
       if (importedBackup.scopes.local) {
@@ -333,6 +338,7 @@ export const ExportImport: React.FC<ExportImportProps> = ({
           scope: "local",
           content: JSON.stringify(importedBackup.scopes.local, null, 2),
           projectPath,
+          expectedHash: allSettings?.hashes?.local,
         });
       }
 
@@ -166,6 +166,8 @@ export const ActionPanel: React.FC = () => {
         scope: importScope,
         content: JSON.stringify(importedSettings, null, 2),
         projectPath: importScope !== "user" ? projectPath : undefined,
+        // Rejected if the file changed since the settings were loaded
+        expectedHash: allSettings?.hashes?.[importScope],
       });
 
       await loadSettings();
@@ -45,12 +45,15 @@ describe('useFileWatcher', () => {
       renderHook(() => useFileWatcher({ enabled: true }));
 
       await waitFor(() => {
-        expect(mockListen).toHaveBeenCalledTimes(3);
+        expect(mockListen).toHaveBeenCalledTimes(6);
       });
 
       expect(mockListen).toHaveBeenCalledWith('session-file-changed', expect.any(Function));
       expect(mockListen).toHaveBeenCalledWith('session-file-created', expect.any(Function));
       expect(mockListen).toHaveBeenCalledWith('session-file-deleted', expect.any(Function));
+      expect(mockListen).toHaveBeenCalledWith('session-file-appended', expect.any(Function));
+      expect(mockListen).toHaveBeenCalledWith('session-file-renamed', expect.any(Function));
+      expect(mockListen).toHaveBeenCalledWith('project-created', expect.any(Function));
     });
 
     it('should default to enabled when no options provided', async () => {
@@ -60,7 +63,7 @@ describe('useFileWatcher', () => {
       renderHook(() => useFileWatcher());
 
       await waitFor(() => {
-        expect(mockListen).toHaveBeenCalledTimes(3);
+        expect(mockListen).toHaveBeenCalledTimes(6);
       });
     });
 
@@ -84,12 +87,12 @@ describe('useFileWatcher', () => {
       const { unmount } = renderHook(() => useFileWatcher({ enabled: true }));
 
       await waitFor(() => {
-        expect(mockListen).toHaveBeenCalledTimes(3);
+        expect(mockListen).toHaveBeenCalledTimes(6);
       });
 
       unmount();
 
-      expect(mockUnlisten).toHaveBeenCalledTimes(3);
+      expect(mockUnlisten).toHaveBeenCalledTimes(6);
     });
   });
 
@@ -111,7 +114,7 @@ describe('useFileWatcher', () => {
       );
 
       await waitFor(() => {
-        expect(mockListen).toHaveBeenCalledTimes(3);
+        expect(mockListen).toHaveBeenCalledTimes(6);
       });
 
       // Simulate event
@@ -146,7 +149,7 @@ describe('useFileWatcher', () => {
       );
 
       await waitFor(() => {
-        expect(mockListen).toHaveBeenCalledTimes(3);
+        expect(mockListen).toHaveBeenCalledTimes(6);
       });
 
       const eventPayload = {
@@ -179,7 +182,7 @@ describe('useFileWatcher', () => {
       );
 
       await waitFor(() => {
-        expect(mockListen).toHaveBeenCalledTimes(3);
+        expect(mockListen).toHaveBeenCalledTimes(6);
       });
 
       const eventPayload = {
@@ -241,6 +244,44 @@ describe('useFileWatcher', () => {
 
       vi.useRealTimers();
     });
+
+    it('should merge appended ranges batched by the debounce', async () => {
+      vi.useFakeTimers();
+
+      const mockUnlisten = vi.fn();
+      let capturedCallback: ((event: { payload: unknown }) => void) | undefined;
+
+      mockListen.mockImplementation((eventName, callback) => {
+        if (eventName === 'session-file-appended') {
+          capturedCallback = callback;
+        }
+        return Promise.resolve(mockUnlisten);
+      });
+
+      const onSessionAppended = vi.fn();
+      renderHook(() =>
+        useFileWatcher({ enabled: true, onSessionAppended, debounceMs: 300 })
+      );
+
+      await vi.advanceTimersByTimeAsync(10);
+
+      const appended = (start: number, end: number) => ({
+        projectPath: '/test/project',
+        sessionPath: '/test/session.jsonl',
+        eventType: 'appended' as const,
+        appended: { start, end },
+      });
+
+      capturedCallback?.({ payload: appended(100, 180) });
+      capturedCallback?.({ payload: appended(180, 260) });
+
+      await vi.advanceTimersByTimeAsync(350);
+
+      expect(onSessionAppended).toHaveBeenCalledTimes(1);
+      expect(onSessionAppended).toHaveBeenCalledWith(appended(100, 260));
+
+      vi.useRealTimers();
+    });
   });
 
   describe('manual control', () => {
@@ -269,14 +310,14 @@ describe('useFileWatcher', () => {
       const { result } = renderHook(() => useFileWatcher({ enabled: true }));
 
       await waitFor(() => {
-        expect(mockListen).toHaveBeenCalledTimes(3);
+        expect(mockListen).toHaveBeenCalledTimes(6);
       });
 
       act(() => {
         result.current.stopWatching();
       });
 
-      expect(mockUnlisten).toHaveBeenCalledTimes(3);
+      expect(mockUnlisten).toHaveBeenCalledTimes(6);
     });
 
     it('should set isWatching to false after stopWatching', async () => {
@@ -5,22 +5,33 @@ import { toast } from 'sonner';
 /**
  * Event payload structure from Tauri file watcher
  */
-interface FileWatcherEvent {
+export interface FileWatcherEvent {
   projectPath: string;
+  /** Session file; the project folder itself for `project_created` */
   sessionPath: string;
-  eventType: 'changed' | 'created' | 'deleted';
+  eventType: 'changed' | 'created' | 'appended' | 'deleted' | 'renamed' | 'project_created';
+  /** Byte range of the complete lines added, for `appended` */
+  appended?: { start: number; end: number };
+  /** Where the file was before, for `renamed` */
+  previousPath?: string;
 }
 
 /**
  * Configuration options for the file watcher hook
  */
 interface UseFileWatcherOptions {
-  /** Callback fired when a session file is modified */
+  /** Callback fired when a session file is rewritten and must be re-read */
   onSessionChanged?: (event: FileWatcherEvent) => void;
+  /** Callback fired when lines are appended to a session file */
+  onSessionAppended?: (event: FileWatcherEvent) => void;
   /** Callback fired when a new session file is created */
   onSessionCreated?: (event: FileWatcherEvent) => void;
   /** Callback fired when a session file is deleted */
   onSessionDeleted?: (event: FileWatcherEvent) => void;
+  /** Callback fired when a session file is renamed or moved */
+  onSessionRenamed?: (event: FileWatcherEvent) => void;
+  /** Callback fired when a new project folder appears */
+  onProjectCreated?: (event: FileWatcherEvent) => void;
   /** Whether file watching is enabled (default: true) */
   enabled?: boolean;
   /** Debounce delay in milliseconds to batch rapid changes (default: 300) */
@@ -60,8 +71,11 @@ export interface UseFileWatcherResult {
 export function useFileWatcher(options: UseFileWatcherOptions = {}): UseFileWatcherResult {
   const {
     onSessionChanged,
+    onSessionAppended,
     onSessionCreated,
     onSessionDeleted,
+    onSessionRenamed,
+    onProjectCreated,
     enabled = true,
     debounceMs = 300,
   } = options;
@@ -70,6 +84,8 @@ export function useFileWatcher(options: UseFileWatcherOptions = {}): UseFileWatc
   const isWatchingRef = useRef(false);
   const unlistenersRef = useRef<UnlistenFn[]>([]);
   const debounceTimersRef = useRef<Map<string, NodeJS.Timeout>>(new Map());
+  /** Appended ranges waiting on a debounce timer, merged per session */
+  const pendingAppendsRef = useRef<Map<string, FileWatcherEvent>>(new Map());
   /** Cancellation token: incremented on stop/unmount to abort in-flight startWatching */
   const watchVersionRef = useRef(0);
 
@@ -87,9 +103,26 @@ export function useFileWatcher(options: UseFileWatcherOptions = {}): UseFileWatc
         clearTimeout(existingTimer);
       }
 
+      // Batched appends must still cover every new line
+      let pending = event;
+      if (event.appended) {
+        const previous = pendingAppendsRef.current.get(key);
+        if (previous?.appended) {
+          pending = {
+            ...event,
+            appended: {
+              start: Math.min(previous.appended.start, event.appended.start),
+              end: Math.max(previous.appended.end, event.appended.end),
+            },
+          };
+        }
+        pendingAppendsRef.current.set(key, pending);
+      }
+
       const timer = setTimeout(() => {
-        callback(event);
+        callback(pending);
         debounceTimersRef.current.delete(key);
+        pendingAppendsRef.current.delete(key);
       }, debounceMs);
 
       debounceTimersRef.current.set(key, timer);
@@ -107,6 +140,7 @@ export function useFileWatcher(options: UseFileWatcherOptions = {}): UseFileWatc
     // Clear all debounce timers
     debounceTimersRef.current.forEach((timer) => clearTimeout(timer));
     debounceTimersRef.current.clear();
+    pendingAppendsRef.current.clear();
 
     // Unlisten from all events
     for (const unlisten of unlistenersRef.current) {
@@ -134,40 +168,28 @@ export function useFileWatcher(options: UseFileWatcherOptions = {}): UseFileWatc
 
     try {
       const unlisteners: UnlistenFn[] = [];
-
-      // Listen to file changed events
-      const unlistenChanged = await listen<FileWatcherEvent>('session-file-changed', (event) => {
-        createDebouncedCallback(onSessionChanged, event.payload);
-      });
-
-      // Check cancellation before continuing
-      if (watchVersionRef.current !== version) {
-        unlistenChanged();
-        return;
-      }
-      unlisteners.push(unlistenChanged);
-
-      // Listen to file created events
-      const unlistenCreated = await listen<FileWatcherEvent>('session-file-created', (event) => {
-        createDebouncedCallback(onSessionCreated, event.payload);
-      });
-
-      if (watchVersionRef.current !== version) {
-        unlisteners.forEach((fn) => fn());
-        return;
+      const handlers: [string, ((event: FileWatcherEvent) => void) | undefined][] = [
+        ['session-file-changed', onSessionChanged],
+        ['session-file-appended', onSessionAppended],
+        ['session-file-created', onSessionCreated],
+        ['session-file-deleted', onSessionDeleted],
+        ['session-file-renamed', onSessionRenamed],
+        ['project-created', onProjectCreated],
+      ];
+
+      for (const [eventName, callback] of handlers) {
+        const unlisten = await listen<FileWatcherEvent>(eventName, (event) => {
+          createDebouncedCallback(callback, event.payload);
+        });
+
+        // Check cancellation before continuing
+        if (watchVersionRef.current !== version) {
+          unlisten();
+          unlisteners.forEach((fn) => fn());
+          return;
+        }
+        unlisteners.push(unlisten);
       }
-      unlisteners.push(unlistenCreated);
-
-      // Listen to file deleted events
-      const unlistenDeleted = await listen<FileWatcherEvent>('session-file-deleted', (event) => {
-        createDebouncedCallback(onSessionDeleted, event.payload);
-      });
-
-      if (watchVersionRef.current !== version) {
-        unlisteners.forEach((fn) => fn());
-        return;
-      }
-      unlisteners.push(unlistenDeleted);
 
       unlistenersRef.current = unlisteners;
       isWatchingRef.current = true;
@@ -178,7 +200,15 @@ export function useFileWatcher(options: UseFileWatcherOptions = {}): UseFileWatc
       isWatchingRef.current = false;
       setIsWatching(false);
     }
-  }, [onSessionChanged, onSessionCreated, onSessionDeleted, createDebouncedCallback]);
+  }, [
+    onSessionChanged,
+    onSessionAppended,
+    onSessionCreated,
+    onSessionDeleted,
+    onSessionRenamed,
+    onProjectCreated,
+    createDebouncedCallback,
+  ]);
 
   /**
    * Auto-start/stop based on enabled prop
@@ -60,7 +60,7 @@
  * - `<project>/.mcp.json` - Project-specific MCP config
  */
 
-import { useCallback, useEffect, useState } from "react";
+import { useCallback, useEffect, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type { MCPServerConfig, MCPSource, AllMCPServersResponse } from "../types";
 
@@ -123,6 +123,8 @@ export const useMCPServers = (projectPath?: string): UseMCPServersResult => {
 
   const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  /** File hashes from the last load, so saves cannot overwrite edits made elsewhere */
+  const hashesRef = useRef<AllMCPServersResponse["hashes"]>({});
 
   /**
    * Load all MCP servers from all sources
@@ -149,6 +151,7 @@ export const useMCPServers = (projectPath?: string): UseMCPServersResult => {
 
       // Project source
       setProjectMcpFile(response.projectMcpFile ?? {});
+      hashesRef.current = response.hashes ?? {};
     } catch (err) {
       const errorMessage = err instanceof Error ? err.message : String(err);
       setError(errorMessage);
@@ -180,6 +183,9 @@ export const useMCPServers = (projectPath?: string): UseMCPServersResult => {
           source,
           servers: JSON.stringify(servers),
           projectPath: effectiveProjectPath,
+          // Hashes were read for the current project only
+          expectedHash:
+            effectiveProjectPath === projectPath ? hashesRef.current?.[source] : undefined,
         });
 
         // Reload from backend after save to ensure consistency
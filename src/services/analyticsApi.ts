@@ -11,8 +11,14 @@ import type {
   PaginatedTokenStats,
   ProjectStatsSummary,
   SessionComparison,
+  SessionContextFilter,
   PaginatedRecentEdits,
+  RecentEditsFilter,
+  RecentFileEdit,
+  EditSource,
   GlobalStatsSummary,
+  DiskUsageReport,
+  RootSelection,
 } from "../types";
 
 // ============================================================================
@@ -54,6 +60,8 @@ export interface FetchProjectTokenStatsOptions {
   limit?: number;
   start_date?: string;
   end_date?: string;
+  /** Count only messages from this branch or Claude Code version */
+  filter?: SessionContextFilter;
 }
 
 /**
@@ -63,7 +71,7 @@ export async function fetchProjectTokenStats(
   projectPath: string,
   options: FetchProjectTokenStatsOptions = {}
 ): Promise<PaginatedTokenStats> {
-  const { offset = 0, limit = DEFAULT_PAGE_SIZE, start_date, end_date } = options;
+  const { offset = 0, limit = DEFAULT_PAGE_SIZE, start_date, end_date, filter } = options;
   const start = performance.now();
 
   const response = await invoke<PaginatedTokenStats>("get_project_token_stats", {
@@ -72,6 +80,7 @@ export async function fetchProjectTokenStats(
     limit,
     startDate: start_date,
     endDate: end_date,
+    filter,
   });
 
   if (import.meta.env.DEV) {
@@ -93,15 +102,16 @@ export async function fetchProjectTokenStats(
  */
 export async function fetchProjectStatsSummary(
   projectPath: string,
-  options: { start_date?: string; end_date?: string } = {}
+  options: { start_date?: string; end_date?: string; filter?: SessionContextFilter } = {}
 ): Promise<ProjectStatsSummary> {
-  const { start_date, end_date } = options;
+  const { start_date, end_date, filter } = options;
   const start = performance.now();
 
   const summary = await invoke<ProjectStatsSummary>("get_project_stats_summary", {
     projectPath,
     startDate: start_date,
     endDate: end_date,
+    filter,
   });
 
   if (import.meta.env.DEV) {
@@ -147,6 +157,7 @@ export async function fetchSessionComparison(
 export interface FetchRecentEditsOptions {
   offset?: number;
   limit?: number;
+  filter?: RecentEditsFilter;
 }
 
 /**
@@ -156,13 +167,14 @@ export async function fetchRecentEdits(
   projectPath: string,
   options: FetchRecentEditsOptions = {}
 ): Promise<PaginatedRecentEdits> {
-  const { offset = 0, limit = DEFAULT_PAGE_SIZE } = options;
+  const { offset = 0, limit = DEFAULT_PAGE_SIZE, filter } = options;
   const start = performance.now();
 
   const result = await invoke<PaginatedRecentEdits>("get_recent_edits", {
     projectPath,
     offset,
     limit,
+    filter,
   });
 
   if (import.meta.env.DEV) {
@@ -175,20 +187,39 @@ export async function fetchRecentEdits(
   return result;
 }
 
+/**
+ * Load the full contents of a single recent edit
+ */
+export async function fetchRecentEditContent(
+  source: EditSource,
+  filePath: string
+): Promise<RecentFileEdit> {
+  return invoke<RecentFileEdit>("get_recent_edit_content", {
+    sessionFile: source.session_file,
+    lineOffset: source.line_offset,
+    filePath,
+  });
+}
+
 // ============================================================================
 // Global Stats API
 // ============================================================================
 
 /**
- * Fetch global statistics across all projects
+ * Fetch global statistics across all projects, optionally spanning several data
+ * roots and limited to messages matching a branch/version filter
  */
 export async function fetchGlobalStatsSummary(
-  claudePath: string
+  claudePath: string,
+  roots?: RootSelection,
+  filter?: SessionContextFilter
 ): Promise<GlobalStatsSummary> {
   const start = performance.now();
 
   const summary = await invoke<GlobalStatsSummary>("get_global_stats_summary", {
     claudePath,
+    roots,
+    filter,
   });
 
   if (import.meta.env.DEV) {
@@ -201,3 +232,17 @@ export async function fetchGlobalStatsSummary(
   return summary;
 }
 
+
+// ============================================================================
+// Disk Usage API
+// ============================================================================
+
+/**
+ * Fetch bytes per project and session by content type, plus the largest lines
+ */
+export async function fetchDiskUsage(
+  claudePath: string,
+  topLines?: number
+): Promise<DiskUsageReport> {
+  return invoke<DiskUsageReport>("get_disk_usage", { claudePath, topLines });
+}
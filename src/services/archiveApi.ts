@@ -0,0 +1,37 @@
+/**
+ * Session Archive API Service
+ *
+ * Tauri API calls for archiving old sessions and restoring them.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  ArchiveOptions,
+  ArchiveReport,
+  ArchiveRestoreResult,
+} from "../types";
+
+/**
+ * Compress old sessions and sessions of hidden projects into the archive.
+ * With `dry_run` set, only reports what would be archived and the space reclaimed.
+ */
+export async function archiveSessions(
+  claudePath: string,
+  options: ArchiveOptions
+): Promise<ArchiveReport> {
+  return invoke<ArchiveReport>("archive_sessions", { claudePath, options });
+}
+
+/**
+ * Move archived sessions (by `.jsonl.gz` path) back into the Claude folder;
+ * sessions that already exist there are skipped
+ */
+export async function restoreArchivedSessions(
+  claudePath: string,
+  archivePaths: string[]
+): Promise<ArchiveRestoreResult> {
+  return invoke<ArchiveRestoreResult>("restore_archived_sessions", {
+    claudePath,
+    archivePaths,
+  });
+}
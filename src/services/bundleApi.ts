@@ -0,0 +1,54 @@
+/**
+ * Session Bundle API Service
+ *
+ * Tauri API calls for exporting and importing `.cchv` session bundles.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  BundleConflictPolicy,
+  BundleImportResult,
+  BundleInspection,
+  BundleManifest,
+} from "../types";
+
+/**
+ * Bundle sessions, their subagent transcripts and metadata into a `.cchv` file
+ */
+export async function exportSessionBundle(
+  sessionPaths: string[],
+  outputPath: string
+): Promise<BundleManifest> {
+  return invoke<BundleManifest>("export_session_bundle", {
+    sessionPaths,
+    outputPath,
+  });
+}
+
+/**
+ * Read a bundle and list sessions that already exist in the Claude folder
+ */
+export async function inspectSessionBundle(
+  bundlePath: string,
+  claudePath: string
+): Promise<BundleInspection> {
+  return invoke<BundleInspection>("inspect_session_bundle", {
+    bundlePath,
+    claudePath,
+  });
+}
+
+/**
+ * Import a bundle; existing sessions are skipped unless `policy` is "overwrite"
+ */
+export async function importSessionBundle(
+  bundlePath: string,
+  claudePath: string,
+  policy?: BundleConflictPolicy
+): Promise<BundleImportResult> {
+  return invoke<BundleImportResult>("import_session_bundle", {
+    bundlePath,
+    claudePath,
+    policy,
+  });
+}
@@ -0,0 +1,51 @@
+/**
+ * Checkpoint API Service
+ *
+ * Tauri API calls for listing file-history checkpoints and restoring files from them.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  CheckpointFileContent,
+  CheckpointRestoreAction,
+  SessionCheckpoints,
+} from "../types";
+
+/**
+ * List the file-history checkpoints recorded in a session
+ */
+export async function getSessionCheckpoints(
+  sessionPath: string
+): Promise<SessionCheckpoints> {
+  return invoke<SessionCheckpoints>("get_session_checkpoints", { sessionPath });
+}
+
+/**
+ * Read a file as it was at a checkpoint; `null` if it did not exist then
+ */
+export async function getCheckpointFileContent(
+  sessionPath: string,
+  messageId: string,
+  filePath: string
+): Promise<CheckpointFileContent | null> {
+  return invoke<CheckpointFileContent | null>("get_checkpoint_file_content", {
+    sessionPath,
+    messageId,
+    filePath,
+  });
+}
+
+/**
+ * Restore a file to its state at a checkpoint; files that did not exist then are removed
+ */
+export async function restoreCheckpointFile(
+  sessionPath: string,
+  messageId: string,
+  filePath: string
+): Promise<CheckpointRestoreAction> {
+  return invoke<CheckpointRestoreAction>("restore_checkpoint_file", {
+    sessionPath,
+    messageId,
+    filePath,
+  });
+}
@@ -0,0 +1,16 @@
+/**
+ * Claude Folder API Service
+ *
+ * Tauri API call reporting which Claude folder the app uses and why.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { ClaudeDirResolution } from "../types";
+
+/**
+ * Resolve the Claude folder, honoring `CLAUDE_CONFIG_DIR`, XDG config and
+ * Windows-side installs under WSL, and list every location that was checked
+ */
+export async function getClaudeDirResolution(): Promise<ClaudeDirResolution> {
+  return invoke<ClaudeDirResolution>("get_claude_dir_resolution");
+}
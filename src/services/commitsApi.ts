@@ -0,0 +1,24 @@
+/**
+ * Commit Correlation API Service
+ *
+ * Tauri API calls for linking sessions to the git commits they produced.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { CommitCorrelation, CorrelationOptions } from "../types";
+
+/**
+ * Match a project's sessions with commits of `repoPath` by timing,
+ * edited files and Claude co-author trailers.
+ */
+export async function correlateSessionCommits(
+  projectPath: string,
+  repoPath: string,
+  options?: CorrelationOptions
+): Promise<CommitCorrelation> {
+  return invoke<CommitCorrelation>("correlate_session_commits", {
+    projectPath,
+    repoPath,
+    options,
+  });
+}
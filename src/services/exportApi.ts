@@ -0,0 +1,139 @@
+/**
+ * Export API Service
+ *
+ * Tauri API calls for rendering and exporting sessions.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  AuditExportFormat,
+  HtmlExportOptions,
+  MarkdownExportOptions,
+  RedactedText,
+  RedactionReport,
+  SecretAuditOptions,
+  SecretAuditReport,
+} from "../types";
+
+// ============================================================================
+// Markdown Export API
+// ============================================================================
+
+/**
+ * Render a session as Markdown (e.g. for copying to the clipboard)
+ */
+export async function renderSessionMarkdown(
+  sessionPath: string,
+  options?: MarkdownExportOptions
+): Promise<string> {
+  return invoke<string>("render_session_as_markdown", { sessionPath, options });
+}
+
+/**
+ * Write a session as Markdown to an allowed export location
+ *
+ * Returns what was redacted (empty unless `options.redact` is set)
+ */
+export async function exportSessionMarkdown(
+  sessionPath: string,
+  outputPath: string,
+  options?: MarkdownExportOptions
+): Promise<RedactionReport> {
+  return invoke<RedactionReport>("export_session_markdown", {
+    sessionPath,
+    outputPath,
+    options,
+  });
+}
+
+// ============================================================================
+// HTML Export API
+// ============================================================================
+
+/**
+ * Write a session as a single self-contained HTML file
+ */
+export async function exportSessionHtml(
+  sessionPath: string,
+  outputPath: string,
+  options?: HtmlExportOptions
+): Promise<RedactionReport> {
+  return invoke<RedactionReport>("export_session_html", {
+    sessionPath,
+    outputPath,
+    options,
+  });
+}
+
+/**
+ * Write all sessions of a project as one HTML file with a table of contents
+ */
+export async function exportProjectHtml(
+  projectPath: string,
+  outputPath: string,
+  options?: HtmlExportOptions
+): Promise<RedactionReport> {
+  return invoke<RedactionReport>("export_project_html", {
+    projectPath,
+    outputPath,
+    options,
+  });
+}
+
+// ============================================================================
+// Redaction API
+// ============================================================================
+
+/**
+ * Show what redaction would replace in a session before exporting it
+ */
+export async function previewSessionRedactions(
+  sessionPath: string
+): Promise<RedactionReport> {
+  return invoke<RedactionReport>("preview_session_redactions", { sessionPath });
+}
+
+/**
+ * Redact a snippet before copying or sharing it
+ */
+export async function redactTextForSharing(text: string): Promise<RedactedText> {
+  return invoke<RedactedText>("redact_text_for_sharing", { text });
+}
+
+/**
+ * Names of the built-in redaction detectors
+ */
+export async function getRedactionDetectors(): Promise<string[]> {
+  return invoke<string[]>("get_redaction_detectors");
+}
+
+// ============================================================================
+// Secret-Leak Audit API
+// ============================================================================
+
+/**
+ * Scan every session for credentials exposed to the model
+ */
+export async function auditSecretLeaks(
+  claudePath: string,
+  options?: SecretAuditOptions
+): Promise<SecretAuditReport> {
+  return invoke<SecretAuditReport>("audit_secret_leaks", { claudePath, options });
+}
+
+/**
+ * Run the audit and write it as JSON or SARIF
+ */
+export async function exportSecretAudit(
+  claudePath: string,
+  outputPath: string,
+  format: AuditExportFormat,
+  options?: SecretAuditOptions
+): Promise<SecretAuditReport> {
+  return invoke<SecretAuditReport>("export_secret_audit", {
+    claudePath,
+    outputPath,
+    format,
+    options,
+  });
+}
@@ -0,0 +1,77 @@
+/**
+ * Git API Service
+ *
+ * Tauri API calls for reading a project's git repository. They work without
+ * a `git` binary on the PATH.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  GitFileChange,
+  GitHead,
+  GitLogPage,
+  GitWorktree,
+  WorktreeFamily,
+} from "../types";
+
+export interface GitLogOptions {
+  /** RFC 3339 lower bound on the author date */
+  since?: string;
+  /** RFC 3339 upper bound on the author date */
+  until?: string;
+  /** Include commits from every branch, not only HEAD */
+  allBranches?: boolean;
+}
+
+/** A page of commits, newest first; empty when the path is not a repository */
+export async function fetchGitLogPage(
+  actualPath: string,
+  offset: number,
+  limit: number,
+  options: GitLogOptions = {}
+): Promise<GitLogPage> {
+  return invoke<GitLogPage>("get_git_log_page", {
+    actualPath,
+    offset,
+    limit,
+    since: options.since,
+    until: options.until,
+    allBranches: options.allBranches,
+  });
+}
+
+/** Files changed by a commit, with added/deleted line counts */
+export async function fetchGitCommitFiles(
+  actualPath: string,
+  commitHash: string
+): Promise<GitFileChange[]> {
+  return invoke<GitFileChange[]>("get_git_commit_files", { actualPath, commitHash });
+}
+
+/** Current branch and HEAD commit */
+export async function fetchGitHead(actualPath: string): Promise<GitHead> {
+  return invoke<GitHead>("get_git_head", { actualPath });
+}
+
+/** The main checkout and every linked worktree of the repository */
+export async function fetchGitWorktrees(actualPath: string): Promise<GitWorktree[]> {
+  return invoke<GitWorktree[]>("get_git_worktrees", { actualPath });
+}
+
+/**
+ * The main checkout, every linked worktree (deleted ones included) and their
+ * projects, with stats across the whole family
+ */
+export async function fetchWorktreeFamily(
+  claudePath: string,
+  actualPath: string,
+  startDate?: string,
+  endDate?: string
+): Promise<WorktreeFamily> {
+  return invoke<WorktreeFamily>("get_worktree_family", {
+    claudePath,
+    actualPath,
+    startDate,
+    endDate,
+  });
+}
@@ -0,0 +1,67 @@
+/**
+ * Project Merge API Service
+ *
+ * Tauri API calls for finding projects split by a move or rename and
+ * viewing them as one merged project.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  ClaudeSession,
+  PaginatedRecentEdits,
+  ProjectMergeSuggestion,
+  ProjectStatsSummary,
+  RecentEditsFilter,
+  SessionContextFilter,
+} from "../types";
+
+/**
+ * Find project folders that share a git remote, root commit or `cwd` lineage.
+ * Pass a suggestion's `projectPaths` to the merged views below.
+ */
+export async function detectMovedProjects(
+  claudePath: string
+): Promise<ProjectMergeSuggestion[]> {
+  return invoke<ProjectMergeSuggestion[]>("detect_moved_projects", { claudePath });
+}
+
+/** Sessions of all merged project folders, newest first */
+export async function loadMergedProjectSessions(
+  projectPaths: string[],
+  excludeSidechain?: boolean
+): Promise<ClaudeSession[]> {
+  return invoke<ClaudeSession[]>("load_merged_project_sessions", {
+    projectPaths,
+    excludeSidechain,
+  });
+}
+
+/** Stats summary across all merged project folders */
+export async function fetchMergedProjectStatsSummary(
+  projectPaths: string[],
+  startDate?: string,
+  endDate?: string,
+  filter?: SessionContextFilter
+): Promise<ProjectStatsSummary> {
+  return invoke<ProjectStatsSummary>("get_merged_project_stats_summary", {
+    projectPaths,
+    startDate,
+    endDate,
+    filter,
+  });
+}
+
+/** Recent edits across all merged project folders */
+export async function fetchMergedRecentEdits(
+  projectPaths: string[],
+  offset?: number,
+  limit?: number,
+  filter?: RecentEditsFilter
+): Promise<PaginatedRecentEdits> {
+  return invoke<PaginatedRecentEdits>("get_merged_recent_edits", {
+    projectPaths,
+    offset,
+    limit,
+    filter,
+  });
+}
@@ -0,0 +1,28 @@
+/**
+ * Data Roots API Service
+ *
+ * Tauri API calls for registering additional Claude folders.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { DataRoot, DataRootInfo } from "../types";
+
+/**
+ * List the default root (the folder the app was opened with) and every registered root
+ */
+export async function listDataRoots(claudePath: string): Promise<DataRootInfo[]> {
+  return invoke<DataRootInfo[]>("list_data_roots", { claudePath });
+}
+
+/**
+ * Register a Claude folder under `name`. `path` may be the folder itself or
+ * its parent (e.g. a home directory containing `.claude`).
+ */
+export async function addDataRoot(name: string, path: string): Promise<DataRoot[]> {
+  return invoke<DataRoot[]>("add_data_root", { name, path });
+}
+
+/** Forget a registered root; its files are left untouched */
+export async function removeDataRoot(name: string): Promise<DataRoot[]> {
+  return invoke<DataRoot[]>("remove_data_root", { name });
+}
@@ -0,0 +1,83 @@
+/**
+ * Watcher API Service
+ *
+ * Tauri API calls for the session file watchers, following an active session
+ * and watching Claude Code's config files. New messages arrive as
+ * `session-tail` events while a file watcher is running; config edits arrive
+ * as `config-file-changed`.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { ConfigFileChange, FileWatcherStatus, SessionTailEvent } from "../types";
+
+/**
+ * Watch a Claude folder's sessions. Each folder gets its own watcher, and
+ * starting one that is already watched restarts it with the new debounce.
+ */
+export async function startFileWatcher(
+  claudeFolderPath: string,
+  debounceMs?: number
+): Promise<void> {
+  await invoke<string>("start_file_watcher", { claudeFolderPath, debounceMs });
+}
+
+/** Stop watching a Claude folder, or every folder when none is given */
+export async function stopFileWatcher(claudeFolderPath?: string): Promise<void> {
+  return invoke<void>("stop_file_watcher", { claudeFolderPath });
+}
+
+/** Every running file watcher with its event counts and recent errors */
+export async function fetchFileWatcherStatus(): Promise<FileWatcherStatus[]> {
+  return invoke<FileWatcherStatus[]>("get_file_watcher_status");
+}
+
+/**
+ * Subscribe to watchers rebuilt after their directory came back. Changes made
+ * while it was missing were not reported, so sessions should be reloaded.
+ */
+export async function listenFileWatcherRecovered(
+  onRecovered: (status: FileWatcherStatus) => void
+): Promise<UnlistenFn> {
+  return listen<FileWatcherStatus>("file-watcher-recovered", (event) => onRecovered(event.payload));
+}
+
+/**
+ * Start pushing new messages of a session. Resolves to the byte offset the
+ * tail starts from; messages before it are loaded as usual.
+ */
+export async function followSession(sessionPath: string): Promise<number> {
+  return invoke<number>("follow_session", { sessionPath });
+}
+
+/** Stop pushing new messages of a session */
+export async function unfollowSession(sessionPath: string): Promise<void> {
+  return invoke<void>("unfollow_session", { sessionPath });
+}
+
+/** Subscribe to new messages of every followed session */
+export async function listenSessionTail(
+  onTail: (event: SessionTailEvent) => void
+): Promise<UnlistenFn> {
+  return listen<SessionTailEvent>("session-tail", (event) => onTail(event.payload));
+}
+
+/**
+ * Watch settings, MCP config and ~/.claude.json, plus the project's files when
+ * a project path is given. Replaces the previous config watcher.
+ */
+export async function startConfigWatcher(projectPath?: string): Promise<void> {
+  return invoke<void>("start_config_watcher", { projectPath });
+}
+
+/** Stop watching config files */
+export async function stopConfigWatcher(): Promise<void> {
+  return invoke<void>("stop_config_watcher");
+}
+
+/** Subscribe to config files changed on disk */
+export async function listenConfigChanges(
+  onChange: (change: ConfigFileChange) => void
+): Promise<UnlistenFn> {
+  return listen<ConfigFileChange>("config-file-changed", (event) => onChange(event.payload));
+}
@@ -0,0 +1,59 @@
+/**
+ * Session Archive Types
+ *
+ * Old sessions moved into compressed, read-only storage under
+ * `~/.claude-history-viewer/archive/`, one folder per data root.
+ */
+
+/** Which sessions to move into the archive */
+export interface ArchiveOptions {
+  /** Archive sessions not modified in this many days */
+  older_than_days?: number;
+  /** Archive every session of projects hidden in user settings */
+  include_hidden_projects?: boolean;
+  /** Only report what would be archived */
+  dry_run?: boolean;
+}
+
+/** Why a session was selected for archiving */
+export type ArchiveReason = "age" | "hidden_project";
+
+export interface ArchivedSessionEntry {
+  project_name: string;
+  session_id: string;
+  source_path: string;
+  archive_path: string;
+  /** The session file plus its subagent transcripts */
+  file_count: number;
+  original_bytes: number;
+  compressed_bytes: number;
+  last_modified: string;
+  reason: ArchiveReason;
+}
+
+/** A session that could not be archived and was left live */
+export interface ArchiveFailure {
+  session_path: string;
+  error: string;
+}
+
+export interface ArchiveReport {
+  dry_run: boolean;
+  sessions: ArchivedSessionEntry[];
+  failed: ArchiveFailure[];
+  original_bytes: number;
+  compressed_bytes: number;
+  /** Space freed under `~/.claude/projects`, net of the archive's own size */
+  bytes_reclaimed: number;
+}
+
+export interface RestoredSession {
+  archive_path: string;
+  file_path: string;
+}
+
+export interface ArchiveRestoreResult {
+  restored: RestoredSession[];
+  /** Left in the archive because a live session already exists at `file_path` */
+  skipped: RestoredSession[];
+}
@@ -0,0 +1,63 @@
+/**
+ * Session Bundle Types
+ *
+ * `.cchv` archives used to move sessions between machines.
+ */
+
+import type { SessionMetadata } from "./core/project";
+
+/** A file stored in a bundle */
+export interface BundledFile {
+  /** Path relative to the project folder, e.g. `<id>/subagents/agent-1.jsonl` */
+  relative_path: string;
+  size: number;
+  /** Hex-encoded SHA-256 of the file contents */
+  sha256: string;
+}
+
+/** One session and everything that belongs to it */
+export interface BundledSession {
+  session_id: string;
+  /** Encoded project folder name under `projects/` */
+  project_dir: string;
+  original_path: string;
+  /** Tags, notes and custom name from user metadata */
+  metadata: SessionMetadata | null;
+  /** The session file first, then its subagent transcripts */
+  files: BundledFile[];
+}
+
+/** `manifest.json` at the root of a bundle */
+export interface BundleManifest {
+  format_version: number;
+  created_at: string;
+  app_version: string;
+  sessions: BundledSession[];
+}
+
+/** What to do when a bundled session already exists */
+export type BundleConflictPolicy = "skip" | "overwrite";
+
+export interface BundleConflict {
+  session_id: string;
+  existing_path: string;
+}
+
+/** Bundle contents plus conflicts, shown before importing */
+export interface BundleInspection {
+  manifest: BundleManifest;
+  conflicts: BundleConflict[];
+}
+
+export interface ImportedSession {
+  session_id: string;
+  file_path: string;
+  /** Whether an existing session was replaced */
+  overwritten: boolean;
+}
+
+export interface BundleImportResult {
+  imported: ImportedSession[];
+  /** Sessions left untouched because they already exist */
+  skipped: BundleConflict[];
+}
@@ -0,0 +1,43 @@
+/**
+ * Checkpoint Types
+ *
+ * File-history checkpoints recorded by Claude Code's rewind feature, with
+ * backups under `~/.claude/file-history/<session-id>/`.
+ */
+
+/** A single file tracked by a checkpoint */
+export interface TrackedFileBackup {
+  file_path: string;
+  /** `null` means the file did not exist when the checkpoint was taken */
+  backup_file_name: string | null;
+  version: number | null;
+  backup_time: string | null;
+  /** Whether the backup file is still present on disk */
+  backup_exists: boolean;
+}
+
+/** A rewind checkpoint recorded before a user message */
+export interface FileCheckpoint {
+  message_id: string;
+  timestamp: string;
+  /** Number of snapshot updates merged into this checkpoint */
+  update_count: number;
+  files: TrackedFileBackup[];
+}
+
+/** All checkpoints for one session, in file order */
+export interface SessionCheckpoints {
+  session_id: string;
+  file_history_dir: string | null;
+  checkpoints: FileCheckpoint[];
+}
+
+/** A backed up file's content as of a checkpoint */
+export interface CheckpointFileContent {
+  /** Invalid UTF-8 is replaced when `is_binary` is set */
+  content: string;
+  is_binary: boolean;
+}
+
+/** Outcome of restoring a file to a checkpoint */
+export type CheckpointRestoreAction = "restored" | "removed" | "unchanged";
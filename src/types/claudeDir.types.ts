@@ -0,0 +1,33 @@
+/**
+ * Claude Folder Resolution Types
+ *
+ * Where Claude Code data was found and how the app chose it.
+ */
+
+/** Where a Claude folder candidate was found */
+export type ClaudeDirSource =
+  /** `$CLAUDE_CONFIG_DIR` */
+  | "config_dir_env"
+  /** `~/.claude` */
+  | "home"
+  /** `$XDG_CONFIG_HOME/claude` or `~/.config/claude` */
+  | "xdg_config"
+  /** A Windows user's `.claude` seen from WSL under `/mnt/c/Users` */
+  | "wsl";
+
+export interface ClaudeDirCandidate {
+  path: string;
+  source: ClaudeDirSource;
+  exists: boolean;
+  /** Whether the folder contains a `projects/` directory */
+  has_projects: boolean;
+}
+
+export interface ClaudeDirResolution {
+  path: string;
+  source: ClaudeDirSource;
+  /** `.claude.json` belonging to the chosen folder */
+  global_config_path: string;
+  /** Every location checked, in priority order */
+  candidates: ClaudeDirCandidate[];
+}
@@ -422,6 +422,8 @@ export interface AllSettingsResponse {
   local: string | null;
   /** Managed settings JSON */
   managed: string | null;
+  /** Content hash of each scope that was read, to pass back as `expectedHash` when saving */
+  hashes?: Partial<Record<SettingsScope, string>>;
 }
 
 /**
@@ -457,6 +459,8 @@ export interface AllMCPServersResponse {
   userClaudeJson: Record<string, MCPServerConfig> | null;
   /** MCP servers from ~/.claude.json projects.<path>.mcpServers (official local-scoped) */
   localClaudeJson: Record<string, MCPServerConfig> | null;
+  /** Content hash of the file behind each source, to pass back as `expectedHash` when saving */
+  hashes?: Partial<Record<MCPSource, string>>;
 }
 
 /**
@@ -471,6 +475,8 @@ export interface ClaudeJsonConfigResponse {
   projectSettings: ClaudeJsonProjectSettings | null;
   /** File path for reference */
   filePath: string;
+  /** Content hash, to pass back when saving MCP servers to ~/.claude.json */
+  contentHash: string;
 }
 
 /**
@@ -540,3 +546,23 @@ export interface SettingsPreset {
   /** Last update timestamp */
   updatedAt: string;
 }
+
+/**
+ * Config file changed on disk, from the `config-file-changed` event
+ */
+export type ConfigFileKind =
+  | "user_settings"
+  | "user_mcp"
+  | "claude_json"
+  | "project_settings"
+  | "local_settings"
+  | "project_mcp";
+
+export interface ConfigFileChange {
+  kind: ConfigFileKind;
+  path: string;
+  projectPath?: string;
+  exists: boolean;
+  /** Hash as the settings commands report it */
+  contentHash: string;
+}
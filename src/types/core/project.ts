@@ -58,6 +58,28 @@ export interface UserSettings {
   worktreeGroupingUserSet?: boolean;
   /** Project tree grouping mode: none, worktree, or directory */
   groupingMode?: GroupingMode;
+  /** Custom regex rules applied by the export redaction pass */
+  redactionRules?: RedactionRule[];
+  /** Built-in redaction detectors the user has turned off (e.g., "email") */
+  disabledRedactionDetectors?: string[];
+  /** Additional Claude folders scanned alongside the default one; changed only through the roots API */
+  dataRoots?: DataRoot[];
+}
+
+/** Named Claude folder registered in settings */
+export interface DataRoot {
+  /** Unique name used to select the root */
+  name: string;
+  /** Absolute path of the Claude folder (the one containing `projects/`) */
+  path: string;
+}
+
+/** User-defined redaction rule */
+export interface RedactionRule {
+  /** Label used in placeholders and the redaction report */
+  name: string;
+  /** Regular expression; a `secret` capture group limits what gets replaced */
+  pattern: string;
 }
 
 // ============================================================================
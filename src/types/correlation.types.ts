@@ -0,0 +1,51 @@
+/**
+ * Session ↔ Commit Correlation Types
+ *
+ * Sessions of a project matched against commits of its git repository.
+ */
+
+import type { GitCommit } from "./index";
+
+/** Why a commit was linked to a session */
+export type CommitMatchReason =
+  | "during_session"
+  | "after_session"
+  | "edited_files"
+  | "claude_co_author";
+
+/** How loosely commits are matched to sessions */
+export interface CorrelationOptions {
+  /** Commits up to this long after a session's last message count as `after_session` (default 60) */
+  after_session_minutes?: number;
+  /** Commits touching edited files, or co-authored by Claude, match up to this long after a session (default 7) */
+  file_match_days?: number;
+}
+
+/** A commit in the correlation window */
+export interface CorrelatedCommit {
+  commit: GitCommit;
+  /** Files changed, relative to the repository root */
+  files: string[];
+  claude_co_authored: boolean;
+}
+
+/** One session ↔ commit match */
+export interface SessionCommitLink {
+  /** Session file path */
+  session_path: string;
+  session_id: string;
+  commit_hash: string;
+  reasons: CommitMatchReason[];
+  /** Files both edited in the session and changed by the commit */
+  shared_files: string[];
+}
+
+export interface CommitCorrelation {
+  /** Newest first */
+  commits: CorrelatedCommit[];
+  links: SessionCommitLink[];
+  /** Session file path → commit hashes, newest first */
+  session_commits: Record<string, string[]>;
+  /** Commit hash → session file paths */
+  commit_sessions: Record<string, string[]>;
+}
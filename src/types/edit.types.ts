@@ -8,16 +8,69 @@
 // Recent File Edit
 // ============================================================================
 
+/**
+ * Confidence in a reconstructed file content
+ */
+export type ReplayStatus = "verified" | "unverified" | "mismatched";
+
+/**
+ * Session line an edit was recorded on, for loading its contents on demand
+ */
+export interface EditSource {
+  session_file: string;
+  line_offset: number;
+}
+
 export interface RecentFileEdit {
   file_path: string;
   timestamp: string;
   session_id: string;
-  operation_type: "edit" | "write";
+  operation_type: "edit" | "write" | "notebook";
   content_after_change: string;
   original_content?: string;
   lines_added: number;
   lines_removed: number;
   cwd?: string;
+  replay_status: ReplayStatus;
+  failed_replacements: number;
+  source?: EditSource;
+}
+
+/**
+ * A path a Bash command or an unreplayable NotebookEdit may have changed;
+ * its content cannot be recovered
+ */
+export interface ShellModifiedFile {
+  file_path: string;
+  timestamp: string;
+  session_id: string;
+  /** The Bash command, or a `NotebookEdit …` summary */
+  command: string;
+  cwd?: string;
+}
+
+// ============================================================================
+// Recent Edits Filter
+// ============================================================================
+
+/**
+ * Which working directories count as part of the project
+ */
+export type CwdScope =
+  | { mode: "most_common" }
+  | { mode: "all" }
+  | { mode: "explicit"; cwds: string[] };
+
+export interface RecentEditsFilter {
+  session_ids?: string[];
+  /** Inclusive RFC 3339 lower bound */
+  since?: string;
+  /** Inclusive RFC 3339 upper bound */
+  until?: string;
+  include_globs?: string[];
+  exclude_globs?: string[];
+  operation_types?: RecentFileEdit["operation_type"][];
+  cwd_scope?: CwdScope;
 }
 
 // ============================================================================
@@ -39,6 +92,9 @@ export interface PaginatedRecentEdits {
   total_edits_count: number;
   unique_files_count: number;
   project_cwd?: string;
+  shell_modified_files: ShellModifiedFile[];
+  /** Files that matched the filter but lie outside the cwd scope */
+  excluded_by_cwd: string[];
   offset: number;
   limit: number;
   has_more: boolean;
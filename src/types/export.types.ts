@@ -0,0 +1,97 @@
+/**
+ * Export Types
+ *
+ * Options for rendering sessions into shareable documents.
+ */
+
+// ============================================================================
+// Markdown Export
+// ============================================================================
+
+export interface MarkdownExportOptions {
+  include_thinking?: boolean;
+  /** Tool results longer than this are truncated; 0 disables the limit */
+  max_tool_result_chars?: number;
+  include_sidechain?: boolean;
+  include_timestamps?: boolean;
+  /** Replace secrets and PII before rendering */
+  redact?: boolean;
+}
+
+// ============================================================================
+// HTML Export
+// ============================================================================
+
+export interface HtmlExportOptions {
+  include_thinking?: boolean;
+  /** Tool results longer than this are truncated; 0 disables the limit */
+  max_tool_result_chars?: number;
+  include_sidechain?: boolean;
+  /** Replace secrets and PII before rendering */
+  redact?: boolean;
+}
+
+// ============================================================================
+// Redaction
+// ============================================================================
+
+/** One distinct secret replaced during redaction */
+export interface RedactionFinding {
+  /** e.g. "[REDACTED:github_token-1]" */
+  placeholder: string;
+  detector: string;
+  occurrences: number;
+  /** Enough of the secret to recognize it without revealing it */
+  preview: string;
+}
+
+/** Summary of what a redaction pass replaced */
+export interface RedactionReport {
+  total_redactions: number;
+  by_detector: Record<string, number>;
+  findings: RedactionFinding[];
+}
+
+export interface RedactedText {
+  text: string;
+  report: RedactionReport;
+}
+
+// ============================================================================
+// Secret-Leak Audit
+// ============================================================================
+
+export interface SecretAuditOptions {
+  /** Also report emails and IP addresses, not just credentials */
+  include_pii?: boolean;
+}
+
+export type AuditExportFormat = "json" | "sarif";
+
+/** A secret exposed to the model in one message */
+export interface SecretLeakFinding {
+  project_name: string;
+  session_id: string;
+  file_path: string;
+  /** 1-based line in the session file */
+  line_number: number;
+  timestamp: string;
+  message_type: string;
+  /** JSON path inside the entry, e.g. message.content[1].input.command */
+  location: string;
+  detector: string;
+  /** Masked preview, never the full secret */
+  preview: string;
+  /** Stable hash for grouping repeated leaks of the same secret */
+  fingerprint: string;
+}
+
+export interface SecretAuditReport {
+  scanned_files: number;
+  scanned_messages: number;
+  sessions_affected: number;
+  unique_secrets: number;
+  by_detector: Record<string, number>;
+  /** Most recent first */
+  findings: SecretLeakFinding[];
+}
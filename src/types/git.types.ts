@@ -0,0 +1,82 @@
+/**
+ * Git Types
+ *
+ * Repository history, changed files, HEAD and worktrees read by the backend
+ * through libgit2.
+ */
+
+import type { ClaudeProject, GitCommit, ProjectStatsSummary } from "./index";
+
+/** One page of a repository's history, newest first */
+export interface GitLogPage {
+  commits: GitCommit[];
+  offset: number;
+  has_more: boolean;
+}
+
+/** How a commit changed a file */
+export type GitChangeStatus =
+  | "added"
+  | "modified"
+  | "deleted"
+  | "renamed"
+  | "copied"
+  | "type_change"
+  | "other";
+
+/** A file changed by a commit, compared with its first parent */
+export interface GitFileChange {
+  /** Path relative to the repository root */
+  path: string;
+  /** Previous path of a renamed or copied file */
+  old_path?: string;
+  status: GitChangeStatus;
+  additions: number;
+  deletions: number;
+  binary: boolean;
+}
+
+/** What `HEAD` points at */
+export interface GitHead {
+  /** Checked-out branch; null when detached */
+  branch: string | null;
+  /** Commit hash; null on a branch with no commits yet */
+  commit: string | null;
+  detached: boolean;
+}
+
+/** A working tree of a repository */
+export interface GitWorktree {
+  /** Name of a linked worktree; null for the main checkout */
+  name: string | null;
+  path: string;
+  is_main: boolean;
+  head: GitHead;
+  locked: boolean;
+  /** Linked worktree whose directory is gone */
+  prunable: boolean;
+}
+
+/** A checkout in a worktree family and the Claude projects recorded in it */
+export interface WorktreeFamilyMember {
+  path: string;
+  /** The worktree as git lists it; null once git no longer tracks it */
+  worktree: GitWorktree | null;
+  /** Whether the directory still exists */
+  exists: boolean;
+  /** Checked-out branch first, then the other branches its sessions ran on */
+  branches: string[];
+  projects: ClaudeProject[];
+}
+
+/** A repository's main checkout and every worktree made from it */
+export interface WorktreeFamily {
+  /** Main checkout, or the git directory of a bare repository */
+  main_path: string;
+  /** Main checkout first, then linked worktrees by name, then checkouts git has forgotten */
+  members: WorktreeFamilyMember[];
+  /** Storage paths of every member's projects */
+  project_paths: string[];
+  /** Stats across all of `project_paths` */
+  stats: ProjectStatsSummary;
+}
@@ -102,6 +102,10 @@ export type {
   ClaudeProject,
   ClaudeSession,
   SearchFilters,
+  SessionContextFilter,
+  PathDecodeMethod,
+  PathConfidence,
+  PathResolution,
 } from "./core/session";
 
 // Project & Metadata Types
@@ -110,6 +114,8 @@ export type {
   ProjectMetadata,
   GroupingMode,
   UserSettings,
+  RedactionRule,
+  DataRoot,
   UserMetadata,
 } from "./core/project";
 export {
@@ -146,6 +152,8 @@ export type {
   ClaudeJsonProjectSettings,
   ScopedSettings,
   SettingsPreset,
+  ConfigFileKind,
+  ConfigFileChange,
 } from "./core/settings";
 export { SCOPE_PRIORITY } from "./core/settings";
 
@@ -198,13 +206,107 @@ export type {
   ModelStats,
   DateRange,
   ProjectStatsSummary,
+  BranchStats,
   ProjectRanking,
   SessionComparison,
   GlobalStatsSummary,
+  ContentBreakdown,
+  SessionDiskUsage,
+  ProjectDiskUsage,
+  LargeLine,
+  DiskUsageReport,
 } from "./stats.types";
 
 // Edit Types
-export type { RecentFileEdit, RecentEditsResult, PaginatedRecentEdits } from "./edit.types";
+export type {
+  RecentFileEdit,
+  EditSource,
+  RecentEditsResult,
+  PaginatedRecentEdits,
+  ReplayStatus,
+  ShellModifiedFile,
+  CwdScope,
+  RecentEditsFilter,
+} from "./edit.types";
+
+// Checkpoint Types
+export type {
+  TrackedFileBackup,
+  FileCheckpoint,
+  SessionCheckpoints,
+  CheckpointFileContent,
+  CheckpointRestoreAction,
+} from "./checkpoint.types";
+
+// Export Types
+export type {
+  AuditExportFormat,
+  HtmlExportOptions,
+  MarkdownExportOptions,
+  RedactedText,
+  RedactionFinding,
+  RedactionReport,
+  SecretAuditOptions,
+  SecretAuditReport,
+  SecretLeakFinding,
+} from "./export.types";
+
+// Session Bundle Types
+export type {
+  BundledFile,
+  BundledSession,
+  BundleManifest,
+  BundleConflictPolicy,
+  BundleConflict,
+  BundleInspection,
+  ImportedSession,
+  BundleImportResult,
+} from "./bundle.types";
+
+// Session Archive Types
+export type {
+  ArchiveOptions,
+  ArchiveReason,
+  ArchivedSessionEntry,
+  ArchiveFailure,
+  ArchiveReport,
+  RestoredSession,
+  ArchiveRestoreResult,
+} from "./archive.types";
+
+// Data Root Types
+export type { RootSelection, DataRootInfo } from "./roots.types";
+export { DEFAULT_DATA_ROOT } from "./roots.types";
+
+// Project Merge Types
+export type { MergeSignal, ProjectMergeSuggestion } from "./merge.types";
+
+// Session ↔ Commit Correlation Types
+export type {
+  CommitMatchReason,
+  CorrelationOptions,
+  CorrelatedCommit,
+  SessionCommitLink,
+  CommitCorrelation,
+} from "./correlation.types";
+
+// Git Types
+export type {
+  GitLogPage,
+  GitChangeStatus,
+  GitFileChange,
+  GitHead,
+  GitWorktree,
+  WorktreeFamilyMember,
+  WorktreeFamily,
+} from "./git.types";
+
+// Claude Folder Resolution Types
+export type {
+  ClaudeDirSource,
+  ClaudeDirCandidate,
+  ClaudeDirResolution,
+} from "./claudeDir.types";
 
 // Update Types
 export type {
@@ -245,3 +347,10 @@ export type {
   UpdateSettings,
 } from "./updateSettings";
 export { DEFAULT_UPDATE_SETTINGS } from "./updateSettings";
+
+// Watcher Types
+export type {
+  ByteRange,
+  FileWatcherStatus,
+  SessionTailEvent,
+} from "./watcher.types";
@@ -0,0 +1,32 @@
+/**
+ * Project Merge Types
+ *
+ * Project folders split by moving or renaming a repository, shown as one
+ * virtual project without moving any files.
+ */
+
+/** Evidence that two project folders hold the history of the same project */
+export type MergeSignal =
+  /** Both checkouts have the same `origin` remote (normalized) */
+  | { kind: "git_remote"; url: string }
+  /** Both checkouts share a root commit */
+  | { kind: "git_root_commit"; commit: string }
+  /** One project's sessions ran in the other's directory */
+  | { kind: "cwd_lineage"; cwd: string }
+  /** The same session transcript exists in both folders */
+  | { kind: "shared_session"; session_id: string }
+  /**
+   * The project directory is gone and one with the same name exists elsewhere,
+   * backed by another signal or sessions in the same subdirectory of both
+   */
+  | { kind: "moved_folder"; folder_name: string };
+
+export interface ProjectMergeSuggestion {
+  /** Storage folder of the most recently active project */
+  primary_path: string;
+  /** Every storage folder in the group, most recently active first */
+  project_paths: string[];
+  /** Decoded project directories, in the same order */
+  actual_paths: string[];
+  signals: MergeSignal[];
+}
@@ -0,0 +1,20 @@
+/**
+ * Data Root Types
+ *
+ * Named Claude folders that can be scanned, searched and aggregated together.
+ */
+
+/** Name of the root for the Claude folder the app was opened with */
+export const DEFAULT_DATA_ROOT = "default";
+
+/** Which roots a scan, search or stats command covers */
+export type RootSelection = "all" | { only: string[] };
+
+export interface DataRootInfo {
+  name: string;
+  path: string;
+  /** The Claude folder the app was opened with */
+  is_default: boolean;
+  /** Whether the folder currently contains a `projects` directory */
+  available: boolean;
+}
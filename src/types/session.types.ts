@@ -33,6 +33,26 @@ export interface GitCommit {
 // Project & Session
 // ============================================================================
 
+/** How a project's filesystem path was decoded */
+export type PathDecodeMethod =
+  /** `cwd` recorded on the project's session entries */
+  | "session_cwd"
+  /** `originalPath` in `sessions-index.json` */
+  | "sessions_index"
+  /** Folder name split where directories exist on disk */
+  | "filesystem"
+  /** Folder name split on its first hyphens */
+  | "heuristic"
+  /** Not a Claude project folder; the storage path is used as is */
+  | "storage_path";
+
+export type PathConfidence = "high" | "medium" | "low";
+
+export interface PathResolution {
+  method: PathDecodeMethod;
+  confidence: PathConfidence;
+}
+
 export interface ClaudeProject {
   name: string;
   /** Claude session storage path (e.g., "~/.claude/projects/-Users-jack-client-my-project") */
@@ -44,6 +64,14 @@ export interface ClaudeProject {
   last_modified: string;
   /** Git worktree 정보 */
   git_info?: GitInfo;
+  /** Sessions of this project that live in the archive */
+  archived_session_count?: number;
+  /** Whether `message_count` is estimated from file size rather than counted */
+  is_estimate?: boolean;
+  /** Registered data root the project was scanned from; absent for the default root */
+  root?: string;
+  /** How `actual_path` was decoded from the storage folder name */
+  path_resolution?: PathResolution;
 }
 
 export interface ClaudeSession {
@@ -59,6 +87,20 @@ export interface ClaudeSession {
   has_errors: boolean;
   summary?: string;
   relevance?: number;
+  /** Read-only session stored compressed in the archive */
+  is_archived?: boolean;
+  /** Branch checked out at the session's latest message */
+  git_branch?: string;
+  /** Every branch the session ran on, in order of first use */
+  git_branches?: string[];
+  /** Claude Code version that wrote the session's latest message */
+  version?: string;
+}
+
+/** Limits search and stats to messages recorded on a branch or by a Claude Code version */
+export interface SessionContextFilter {
+  git_branch?: string;
+  version?: string;
 }
 
 // ============================================================================
@@ -72,6 +114,10 @@ export interface SearchFilters {
   hasToolCalls?: boolean;
   hasErrors?: boolean;
   hasFileChanges?: boolean;
+  /** Only messages recorded on this git branch */
+  gitBranch?: string;
+  /** Only messages written by this Claude Code version */
+  version?: string;
 }
 
 // ============================================================================
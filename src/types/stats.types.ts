@@ -112,6 +112,22 @@ export interface ProjectStatsSummary {
     cache_creation: number;
     cache_read: number;
   };
+  /** Usage per git branch, most tokens first */
+  branch_stats?: BranchStats[];
+}
+
+/** Usage of the messages recorded on one git branch */
+export interface BranchStats {
+  git_branch: string;
+  session_count: number;
+  message_count: number;
+  total_tokens: number;
+  token_distribution: {
+    input: number;
+    output: number;
+    cache_creation: number;
+    cache_read: number;
+  };
 }
 
 export interface ProjectRanking {
@@ -156,4 +172,70 @@ export interface GlobalStatsSummary {
   most_used_tools: ToolUsageStats[];
   model_distribution: ModelStats[];
   top_projects: ProjectRanking[];
+  /** Usage per git branch, most tokens first */
+  branch_stats?: BranchStats[];
+}
+
+// ============================================================================
+// Disk Usage
+// ============================================================================
+
+/** Bytes of session data by kind of content */
+export interface ContentBreakdown {
+  text: number;
+  thinking: number;
+  /** Tool inputs written by the model */
+  tool_calls: number;
+  /** Tool output, including the `toolUseResult` copy kept on each entry */
+  tool_results: number;
+  /** Base64 image data */
+  images: number;
+  /** `file-history-snapshot` entries */
+  snapshots: number;
+  /** JSON structure, ids, usage and everything else */
+  other: number;
+}
+
+export interface SessionDiskUsage {
+  file_path: string;
+  /** Transcript size, inflated for archived sessions; the breakdown adds up to it */
+  total_bytes: number;
+  /** Size of the file on disk, compressed for archived sessions */
+  disk_bytes: number;
+  line_count: number;
+  breakdown: ContentBreakdown;
+}
+
+export interface ProjectDiskUsage {
+  name: string;
+  path: string;
+  total_bytes: number;
+  disk_bytes: number;
+  /** Non-transcript files such as caches and indexes */
+  other_files_bytes: number;
+  breakdown: ContentBreakdown;
+  /** Largest first */
+  sessions: SessionDiskUsage[];
+}
+
+export interface LargeLine {
+  project_name: string;
+  file_path: string;
+  /** 1-based line in the session file */
+  line_number: number;
+  bytes: number;
+  message_type: string;
+  timestamp: string | null;
+  /** Category holding most of the line's bytes */
+  dominant_content: keyof ContentBreakdown;
+}
+
+export interface DiskUsageReport {
+  total_bytes: number;
+  disk_bytes: number;
+  breakdown: ContentBreakdown;
+  /** Largest first */
+  projects: ProjectDiskUsage[];
+  /** Largest first */
+  largest_lines: LargeLine[];
 }
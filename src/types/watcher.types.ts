@@ -0,0 +1,40 @@
+/**
+ * Watcher Types
+ *
+ * Events the backend file watcher pushes while sessions are written.
+ */
+
+import type { ClaudeMessage } from "./index";
+
+/** Byte range of a file, end exclusive */
+export interface ByteRange {
+  start: number;
+  end: number;
+}
+
+/** New messages of a followed session */
+export interface SessionTailEvent {
+  sessionPath: string;
+  /** Bytes the messages were parsed from */
+  range: ByteRange;
+  messages: ClaudeMessage[];
+  /** The file shrank or was rewritten: reload it, then expect messages from `range.end` */
+  reset: boolean;
+}
+
+/** Status of one watched Claude folder */
+export interface FileWatcherStatus {
+  root: string;
+  projectsPath: string;
+  debounceMs: number;
+  /** False while the projects directory is missing */
+  active: boolean;
+  /** Watch events emitted since the watcher started */
+  eventCount: number;
+  /** RFC 3339 time of the last emitted event */
+  lastEventAt?: string;
+  /** Times the watcher was rebuilt after its directory came back */
+  recoveries: number;
+  /** Most recent errors, oldest first */
+  errors: string[];
+}